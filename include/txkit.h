@@ -28,11 +28,62 @@ enum TxKit_ImageDataType
      * Single-precision floating point (32 bits)
      */
     TxKit_ImageDataType_Float32,
+    /**
+     * Unsigned short integers (16 bits)
+     */
+    TxKit_ImageDataType_UInt16,
+    /**
+     * Half-precision floating point (16 bits)
+     */
+    TxKit_ImageDataType_Float16,
+    /**
+     * Signed integers (32 bits), holding unnormalized values such as ranks
+     *
+     * Methods computing normalized values do not support this type.
+     */
+    TxKit_ImageDataType_Int32,
 };
 #ifndef __cplusplus
 typedef uint32_t TxKit_ImageDataType;
 #endif // __cplusplus
 
+/**
+ * Type of a method parameter
+ */
+enum TxKit_ParamType
+#ifdef __cplusplus
+  : uint32_t
+#endif // __cplusplus
+ {
+    /**
+     * Unsigned 32-bit integer
+     */
+    TxKit_ParamType_UInt,
+    /**
+     * Signed 32-bit integer
+     */
+    TxKit_ParamType_Int,
+    /**
+     * 32-bit floating-point number
+     */
+    TxKit_ParamType_Float,
+    /**
+     * Vector of 2 floating-point numbers
+     */
+    TxKit_ParamType_Vector2,
+    /**
+     * Vector of 3 floating-point numbers
+     */
+    TxKit_ParamType_Vector3,
+    /**
+     * Vector of 4 floating-point numbers
+     */
+    TxKit_ParamType_Vector4,
+};
+#ifndef __cplusplus
+typedef uint32_t TxKit_ParamType;
+#endif // __cplusplus
+
 typedef struct TxKit_Context TxKit_Context;
 
 typedef struct TxKit_Context TxKit_Context;
@@ -59,6 +110,11 @@ typedef struct TxKit_MappedImageDataWrite TxKit_MappedImageDataWrite;
  */
 typedef struct TxKit_Method TxKit_Method;
 
+/**
+ * Wrapped method parameters for FFI
+ */
+typedef struct TxKit_Params TxKit_Params;
+
 /**
  * Wrapped registry for FFI
  */
@@ -73,10 +129,170 @@ typedef struct {
 
 typedef TxKit_ImageDimensions_usize TxKit_ImageDim;
 
+/**
+ * Window of the plane of a full image which is rendered into a target image
+ *
+ * Methods map their [0, 1] texture coordinates to the full image, so the size of a pixel in
+ * texture space is the inverse of the full image size. The window is placed anywhere on the
+ * unbounded plane of the full image, in full image pixels, and its pixels are `scale` full image
+ * pixels wide. Targets rendered with adjacent windows of the same full image thus stitch
+ * seamlessly, and with a scale of 1 and integer offsets they are identical to the matching part
+ * of a render of the full image.
+ */
+typedef struct {
+    /**
+     * horizontal position of the window origin on the full image plane, in full image pixels
+     */
+    float x;
+    /**
+     * vertical position of the window origin on the full image plane, in full image pixels
+     */
+    float y;
+    /**
+     * layer position of the window origin on the full image plane, in full image pixels
+     */
+    float z;
+    /**
+     * size of a window pixel, in full image pixels
+     */
+    float scale;
+    /**
+     * width of the full image, in pixels
+     */
+    uintptr_t width;
+    /**
+     * height of the full image, in pixels
+     */
+    uintptr_t height;
+    /**
+     * depth of the full image, in pixels
+     */
+    uintptr_t depth;
+} TxKit_ImageRegion;
+
+/**
+ * Description of a registered method
+ *
+ * Methods which do not expose their metadata only have their name set. Strings are valid until the
+ * registry is destroyed, or a plugin is loaded into it.
+ */
+typedef struct {
+    /**
+     * name of the method
+     */
+    const char *name;
+    /**
+     * short description of the method
+     */
+    const char *description;
+    /**
+     * name of the parameter type of the method
+     */
+    const char *params_type;
+    /**
+     * true if the method can compute images in CPU contexts
+     */
+    bool cpu;
+    /**
+     * true if the method can compute images in GPU contexts
+     */
+    bool gpu;
+    /**
+     * set of the supported element types, with bit `1 << t` set for the element type `t`
+     */
+    uint32_t element_types;
+    /**
+     * maximum number of dimensions of the images the method can compute
+     */
+    uintptr_t max_dimensions;
+} TxKit_MethodDesc;
+
+/**
+ * Description of a method parameter
+ */
+typedef struct {
+    /**
+     * name of the parameter, valid until the parameters are destroyed
+     */
+    const char *name;
+    /**
+     * documentation of the parameter, valid until the parameters are destroyed
+     */
+    const char *doc;
+    /**
+     * type of the parameter
+     */
+    TxKit_ParamType param_type;
+} TxKit_ParamDesc;
+
+typedef struct {
+    /**
+     * pseudo-random seed
+     */
+    uint32_t global_seed;
+    /**
+     * standard deviation of the energy filter, in pixels
+     */
+    float sigma;
+} TxKit_BlueNoiseParams;
+
+typedef struct {
+    /**
+     * pseudo-random seed
+     */
+    uint32_t global_seed;
+    /**
+     * lattice scale (size in pixels)
+     */
+    float scale;
+    /**
+     * noise the potential is built from
+     */
+    int32_t noise_potential;
+} TxKit_CurlNoiseParams;
+
 typedef struct {
     float alpha_value;
 } TxKit_DebugParams;
 
+typedef struct {
+    /**
+     * warp amplitude, in normalized texture coordinates
+     */
+    float amplitude;
+    /**
+     * number of times the warp is applied
+     */
+    int32_t warp_depth;
+    /**
+     * texture inputs, overriding the corresponding methods when bound
+     */
+    TxKit_ImageIo *io;
+} TxKit_DomainWarpParams;
+
+typedef struct {
+    /**
+     * number of octaves to sum
+     */
+    int32_t octaves;
+    /**
+     * frequency multiplier between successive octaves
+     */
+    float lacunarity;
+    /**
+     * amplitude multiplier between successive octaves
+     */
+    float gain;
+    /**
+     * fractal mode
+     */
+    int32_t fractal_mode;
+    /**
+     * offset for the ridged and hybrid multifractal modes
+     */
+    float offset;
+} TxKit_FractalParams;
+
 /**
  * A 2-dimensional vector.
  *
@@ -93,6 +309,25 @@ typedef struct {
     float y;
 } TxKit_Vector2_f32;
 
+typedef struct {
+    /**
+     * The x component of the vector.
+     */
+    float x;
+    /**
+     * The y component of the vector.
+     */
+    float y;
+    /**
+     * The z component of the vector.
+     */
+    float z;
+    /**
+     * The w component of the vector.
+     */
+    float w;
+} TxKit_Vector4_f32;
+
 typedef struct {
     /**
      * pseudo-random seed
@@ -110,6 +345,81 @@ typedef struct {
      * look-at parameter (if stats_mode == lookat) in [0, 1]^2
      */
     TxKit_Vector2_f32 stats_look_at;
+    /**
+     * cell lookahead: number of cells to check for contributions
+     */
+    int32_t noise_lookahead;
+    /**
+     * kernel count per cell
+     */
+    int32_t kernel_count;
+    /**
+     * kernel orientation mode
+     */
+    int32_t noise_orientation_mode;
+    /**
+     * noise weights (same values as the phasor noise weights)
+     */
+    int32_t noise_weights;
+    /**
+     * point distribution (same values as the phasor noise point distribution)
+     */
+    int32_t noise_point_distribution;
+    /**
+     * noise frequency (in oscillations / noise cell)
+     */
+    float noise_frequency;
+    /**
+     * noise angle (in radians)
+     */
+    float noise_angle;
+    /**
+     * jittering amount, 0 = no random, 1 = full subcell random
+     */
+    float jitter_amount;
+    /**
+     * max jittering subcells, 0 = no limit
+     */
+    int32_t jitter_max;
+    /**
+     * texture inputs
+     */
+    TxKit_ImageIo *io;
+} TxKit_GaborNoiseParams;
+
+typedef struct {
+    /**
+     * pseudo-random seed
+     */
+    uint32_t global_seed;
+    /**
+     * lattice scale (size in pixels)
+     */
+    float scale;
+    /**
+     * stats mode (0: normal, 1: process, 2: lookat)
+     */
+    int32_t stats_mode;
+    /**
+     * look-at parameter (if stats_mode == lookat) in [0, 1]^2
+     */
+    TxKit_Vector2_f32 stats_look_at;
+    /**
+     * output mode (0: value, 1: value and derivatives)
+     */
+    int32_t noise_output;
+    /**
+     * lattice dimensions (0: 2D, 1: 3D, 2: 4D)
+     */
+    int32_t noise_dimensions;
+    /**
+     * time coordinate of the 4D lattice, in lattice cells
+     */
+    float time;
+    /**
+     * lattice period along (x, y, z, time), in whole lattice cells (0: loop at the scale)
+     */
+    TxKit_Vector4_f32 period;
 } TxKit_GradientNoiseParams;
 
 typedef struct {
@@ -129,6 +439,10 @@ typedef struct {
      * look-at parameter (if stats_mode == lookat) in [0, 1]^2
      */
     TxKit_Vector2_f32 stats_look_at;
+    /**
+     * lattice period along (x, y), in whole lattice cells (0: loop at the scale)
+     */
+    TxKit_Vector2_f32 period;
     /**
      * cell lookahead: number of cells to check for contributions
      */
@@ -188,6 +502,18 @@ typedef struct {
      * look-at parameter (if stats_mode == lookat) in [0, 1]^2
      */
     TxKit_Vector2_f32 stats_look_at;
+    /**
+     * output mode (0: value, 1: value and derivatives)
+     */
+    int32_t noise_output;
+    /**
+     * lattice dimensions (0: 2D, 1: 3D, 2: 4D)
+     */
+    int32_t noise_dimensions;
+    /**
+     * time coordinate of the 4D lattice, in lattice cells
+     */
+    float time;
 } TxKit_SimplexNoiseParams;
 
 typedef struct {
@@ -207,6 +533,22 @@ typedef struct {
      * look-at parameter (if stats_mode == lookat) in [0, 1]^2
      */
     TxKit_Vector2_f32 stats_look_at;
+    /**
+     * output mode (0: value, 1: value and derivatives)
+     */
+    int32_t noise_output;
+    /**
+     * lattice dimensions (0: 2D, 1: 3D, 2: 4D)
+     */
+    int32_t noise_dimensions;
+    /**
+     * time coordinate of the 4D lattice, in lattice cells
+     */
+    float time;
+    /**
+     * lattice period along (x, y, z, time), in whole lattice cells (0: loop at the scale)
+     */
+    TxKit_Vector4_f32 period;
 } TxKit_ValueNoiseParams;
 
 typedef struct {
@@ -216,6 +558,53 @@ typedef struct {
     uint32_t global_seed;
 } TxKit_WhiteNoiseParams;
 
+typedef struct {
+    /**
+     * pseudo-random seed
+     */
+    uint32_t global_seed;
+    /**
+     * lattice scale (size in pixels)
+     */
+    float scale;
+    /**
+     * stats mode (0: normal, 1: process, 2: lookat)
+     */
+    int32_t stats_mode;
+    /**
+     * look-at parameter (if stats_mode == lookat) in [0, 1]^2
+     */
+    TxKit_Vector2_f32 stats_look_at;
+    /**
+     * cell lookahead: number of cells to check for points
+     */
+    int32_t noise_lookahead;
+    /**
+     * point count per cell
+     */
+    int32_t point_count;
+    /**
+     * noise output function
+     */
+    int32_t noise_output;
+    /**
+     * distance metric
+     */
+    int32_t distance_metric;
+    /**
+     * point distribution (same values as the phasor noise point distribution)
+     */
+    int32_t noise_point_distribution;
+    /**
+     * jittering amount, 0 = no random, 1 = full subcell random
+     */
+    float jitter_amount;
+    /**
+     * max jittering subcells, 0 = no limit
+     */
+    int32_t jitter_max;
+} TxKit_WorleyNoiseParams;
+
 #ifdef __cplusplus
 extern "C" {
 #endif // __cplusplus
@@ -351,6 +740,22 @@ int32_t txkit_image_io_set_texture_binding(TxKit_ImageIo *io,
  */
 TXKIT_API TxKit_MappedImageDataRead *txkit_image_map_read(const TxKit_Image *image);
 
+/**
+ * Get a pointer to the image pixels through the given map.
+ *
+ * Half-precision floating point values are returned as their raw IEEE 754 binary16 bits.
+ *
+ * # Parameters
+ *
+ * * `read_map`: map to access
+ *
+ * # Returns
+ *
+ * Pointer to the packed, row-major pixel data, or null if the conversion failed or the image
+ * has another memory layout.
+ */
+TXKIT_API const uint16_t *txkit_image_map_read_data_f16(const TxKit_MappedImageDataRead *read_map);
+
 /**
  * Get a pointer to the image pixels through the given map.
  *
@@ -360,7 +765,8 @@ TXKIT_API TxKit_MappedImageDataRead *txkit_image_map_read(const TxKit_Image *ima
  *
  * # Returns
  *
- * Pointer to the pixel data, or null if the conversion failed.
+ * Pointer to the packed, row-major pixel data, or null if the conversion failed or the image
+ * has another memory layout.
  */
 TXKIT_API const float *txkit_image_map_read_data_f32(const TxKit_MappedImageDataRead *read_map);
 
@@ -373,7 +779,36 @@ TXKIT_API const float *txkit_image_map_read_data_f32(const TxKit_MappedImageData
  *
  * # Returns
  *
- * Pointer to the pixel data, or null if the conversion failed.
+ * Pointer to the packed, row-major pixel data, or null if the conversion failed or the image
+ * has another memory layout.
+ */
+TXKIT_API const int32_t *txkit_image_map_read_data_i32(const TxKit_MappedImageDataRead *read_map);
+
+/**
+ * Get a pointer to the image pixels through the given map.
+ *
+ * # Parameters
+ *
+ * * `read_map`: map to access
+ *
+ * # Returns
+ *
+ * Pointer to the packed, row-major pixel data, or null if the conversion failed or the image
+ * has another memory layout.
+ */
+TXKIT_API const uint16_t *txkit_image_map_read_data_u16(const TxKit_MappedImageDataRead *read_map);
+
+/**
+ * Get a pointer to the image pixels through the given map.
+ *
+ * # Parameters
+ *
+ * * `read_map`: map to access
+ *
+ * # Returns
+ *
+ * Pointer to the packed, row-major pixel data, or null if the conversion failed or the image
+ * has another memory layout.
  */
 TXKIT_API const uint8_t *txkit_image_map_read_data_u8(const TxKit_MappedImageDataRead *read_map);
 
@@ -389,13 +824,30 @@ TXKIT_API TxKit_MappedImageDataWrite *txkit_image_map_write(TxKit_Image *image);
 /**
  * Get a pointer to the image pixels through the given map.
  *
+ * Half-precision floating point values are returned as their raw IEEE 754 binary16 bits.
+ *
  * # Parameters
  *
  * * `write_map`: map to access
  *
  * # Returns
  *
- * Pointer to the pixel data, or null if the conversion failed.
+ * Pointer to the packed, row-major pixel data, or null if the conversion failed or the image
+ * has another memory layout.
+ */
+TXKIT_API uint16_t *txkit_image_map_write_data_f16(TxKit_MappedImageDataWrite *write_map);
+
+/**
+ * Get a pointer to the image pixels through the given map.
+ *
+ * # Parameters
+ *
+ * * `write_map`: map to access
+ *
+ * # Returns
+ *
+ * Pointer to the packed, row-major pixel data, or null if the conversion failed or the image
+ * has another memory layout.
  */
 TXKIT_API float *txkit_image_map_write_data_f32(TxKit_MappedImageDataWrite *write_map);
 
@@ -408,7 +860,36 @@ TXKIT_API float *txkit_image_map_write_data_f32(TxKit_MappedImageDataWrite *writ
  *
  * # Returns
  *
- * Pointer to the pixel data, or null if the conversion failed.
+ * Pointer to the packed, row-major pixel data, or null if the conversion failed or the image
+ * has another memory layout.
+ */
+TXKIT_API int32_t *txkit_image_map_write_data_i32(TxKit_MappedImageDataWrite *write_map);
+
+/**
+ * Get a pointer to the image pixels through the given map.
+ *
+ * # Parameters
+ *
+ * * `write_map`: map to access
+ *
+ * # Returns
+ *
+ * Pointer to the packed, row-major pixel data, or null if the conversion failed or the image
+ * has another memory layout.
+ */
+TXKIT_API uint16_t *txkit_image_map_write_data_u16(TxKit_MappedImageDataWrite *write_map);
+
+/**
+ * Get a pointer to the image pixels through the given map.
+ *
+ * # Parameters
+ *
+ * * `write_map`: map to access
+ *
+ * # Returns
+ *
+ * Pointer to the packed, row-major pixel data, or null if the conversion failed or the image
+ * has another memory layout.
  */
 TXKIT_API uint8_t *txkit_image_map_write_data_u8(TxKit_MappedImageDataWrite *write_map);
 
@@ -426,6 +907,37 @@ TXKIT_API uint8_t *txkit_image_map_write_data_u8(TxKit_MappedImageDataWrite *wri
  */
 TXKIT_API TxKit_Image *txkit_image_new_cpu(TxKit_ImageDim dim, TxKit_ImageDataType element_type);
 
+/**
+ * Create a new image for CPU-based computations over a caller-owned buffer, without copying it
+ *
+ * # Parameters
+ *
+ * * `dim`: dimensions of the image
+ * * `element_type`: type of the elements in the image
+ * * `data`: pointer to the first element of the image
+ * * `strides`: distance, in elements, between consecutive layers, rows, pixels and channels (4
+ *   values), or null for tightly packed elements
+ * * `release`: function called with `data` and `user_data` when the image is destroyed, or null
+ *   if the caller keeps ownership of the buffer
+ * * `user_data`: pointer passed to `release`
+ *
+ * # Returns
+ *
+ * Allocated image, or null if an error occurred. `release` is not called in this case.
+ *
+ * # Safety
+ *
+ * `data` must be valid for reads and writes of all the elements of the image until it is
+ * destroyed, and `strides` must be null or point to 4 values.
+ */
+TXKIT_API
+TxKit_Image *txkit_image_new_cpu_from_buffer(TxKit_ImageDim dim,
+                                             TxKit_ImageDataType element_type,
+                                             void *data,
+                                             const uintptr_t *strides,
+                                             void (*release)(void*, void*),
+                                             void *user_data);
+
 /**
  * Create a new 1D image for GPU-based computations
  *
@@ -477,6 +989,32 @@ TxKit_Image *txkit_image_new_gpu_3d(TxKit_ImageDim dim,
                                     TxKit_ImageDataType element_type,
                                     const TxKit_Context *context);
 
+/**
+ * Return the region of the full image rendered into the image
+ *
+ * # Parameters
+ *
+ * * `image`: target image
+ */
+TXKIT_API TxKit_ImageRegion txkit_image_region(const TxKit_Image *image);
+
+/**
+ * Set the region of the full image rendered into the image
+ *
+ * Methods computing the image then render this window of the full image, so that adjacent
+ * regions can be rendered separately and stitched together.
+ *
+ * # Parameters
+ *
+ * * `image`: target image
+ * * `region`: window of the full image plane, with a positive scale and a non-empty full image
+ *
+ * # Returns
+ *
+ * TxKit_SUCCESS if no error occurred, else a non-zero code.
+ */
+TXKIT_API int32_t txkit_image_set_region(TxKit_Image *image, TxKit_ImageRegion region);
+
 /**
  * Unmap a mapped image.
  *
@@ -515,6 +1053,8 @@ TXKIT_API int32_t txkit_image_upload(TxKit_Image *image);
  * * `params`: pointer to the parameter structure for this method
  * * `params_size`: size of the parameter structure
  *
+ * Methods write at most 4 channels of `tgt`, and fill the other ones with zeros.
+ *
  * # Returns
  *
  * TxKit_SUCCESS if no error occurred, else a non-zero code.
@@ -526,6 +1066,26 @@ int32_t txkit_method_compute(TxKit_Context *ctx,
                              const void *params,
                              uintptr_t params_size);
 
+/**
+ * Compute an image using the given method and parameters
+ *
+ * # Parameters
+ *
+ * * `ctx`: context to use for computing the image
+ * * `method`: texturing method
+ * * `tgt`: target image to be computed
+ * * `params`: parameters created from this method
+ *
+ * # Returns
+ *
+ * TxKit_SUCCESS if no error occurred, else a non-zero code.
+ */
+TXKIT_API
+int32_t txkit_method_compute_params(TxKit_Context *ctx,
+                                    TxKit_Method *method,
+                                    TxKit_Image *tgt,
+                                    const TxKit_Params *params);
+
 /**
  * Destroy a method
  *
@@ -550,6 +1110,133 @@ TXKIT_API void txkit_method_destroy(TxKit_Method *method);
  */
 TXKIT_API TxKit_Method *txkit_method_new(const TxKit_Registry *registry, const char *method_name);
 
+/**
+ * Create the default parameters of a method
+ *
+ * # Parameters
+ *
+ * * `method`: method to create the parameters of
+ *
+ * # Returns
+ *
+ * Null pointer if the method does not expose its parameters, otherwise pointer to the allocated
+ * parameters.
+ */
+TXKIT_API TxKit_Params *txkit_method_params_new(const TxKit_Method *method);
+
+/**
+ * Get the number of parameters that can be accessed by name
+ *
+ * # Parameters
+ *
+ * * `params`: method parameters
+ */
+TXKIT_API uintptr_t txkit_params_count(const TxKit_Params *params);
+
+/**
+ * Describe a parameter
+ *
+ * # Parameters
+ *
+ * * `params`: method parameters
+ * * `index`: index of the parameter, less than `txkit_params_count(params)`
+ * * `desc`: description of the parameter to fill
+ *
+ * # Returns
+ *
+ * TxKit_SUCCESS if no error occurred, else a non-zero code.
+ */
+TXKIT_API
+int32_t txkit_params_describe(const TxKit_Params *params,
+                              uintptr_t index,
+                              TxKit_ParamDesc *desc);
+
+/**
+ * Destroy method parameters
+ *
+ * # Parameters
+ *
+ * * `params`: parameters to destroy
+ */
+TXKIT_API void txkit_params_destroy(TxKit_Params *params);
+
+/**
+ * Get the value of a parameter
+ *
+ * # Parameters
+ *
+ * * `params`: method parameters
+ * * `name`: name of the parameter
+ * * `values`: pointer to the components of the value to fill
+ * * `count`: number of components in `values`, which must match the parameter type
+ *
+ * # Returns
+ *
+ * TxKit_SUCCESS if no error occurred, else a non-zero code.
+ *
+ * # Safety
+ *
+ * `values` must point to at least `count` values.
+ */
+TXKIT_API
+int32_t txkit_params_get(const TxKit_Params *params,
+                         const char *name,
+                         double *values,
+                         uintptr_t count);
+
+/**
+ * Set the value of a parameter
+ *
+ * # Parameters
+ *
+ * * `params`: method parameters
+ * * `name`: name of the parameter
+ * * `values`: pointer to the components of the value
+ * * `count`: number of components in `values`, which must match the parameter type
+ *
+ * # Returns
+ *
+ * TxKit_SUCCESS if no error occurred, else a non-zero code.
+ *
+ * # Safety
+ *
+ * `values` must point to at least `count` values.
+ */
+TXKIT_API
+int32_t txkit_params_set(TxKit_Params *params,
+                         const char *name,
+                         const double *values,
+                         uintptr_t count);
+
+/**
+ * Get the number of methods in a registry
+ *
+ * # Parameters
+ *
+ * * `registry`: registry of methods
+ */
+TXKIT_API uintptr_t txkit_registry_count(const TxKit_Registry *registry);
+
+/**
+ * Describe a registered method
+ *
+ * Methods are sorted by name.
+ *
+ * # Parameters
+ *
+ * * `registry`: registry of methods
+ * * `index`: index of the method, less than `txkit_registry_count(registry)`
+ * * `desc`: description of the method to fill
+ *
+ * # Returns
+ *
+ * TxKit_SUCCESS if no error occurred, else a non-zero code.
+ */
+TXKIT_API
+int32_t txkit_registry_describe(const TxKit_Registry *registry,
+                                uintptr_t index,
+                                TxKit_MethodDesc *desc);
+
 /**
  * Destroy a registry
  *
@@ -559,6 +1246,27 @@ TXKIT_API TxKit_Method *txkit_method_new(const TxKit_Registry *registry, const c
  */
 TXKIT_API void txkit_registry_destroy(TxKit_Registry *registry);
 
+/**
+ * Load the methods of a plugin shared library into a registry
+ *
+ * Loading a plugin invalidates the method descriptions previously returned for this registry.
+ *
+ * # Parameters
+ *
+ * * `registry`: registry to load the methods into
+ * * `path`: path to the shared library, as raw bytes on unix and UTF-8 on other platforms
+ *
+ * # Returns
+ *
+ * TxKit_SUCCESS if no error occurred, else a non-zero code.
+ *
+ * # Safety
+ *
+ * Loading a library runs its initialization code, and the methods of the plugin are trusted to be
+ * sound.
+ */
+TXKIT_API int32_t txkit_registry_load_plugin(TxKit_Registry *registry, const char *path);
+
 /**
  * Create a new registry with txkit built-in methods registered
  *
@@ -0,0 +1,66 @@
+#version 460 core
+
+/**
+ * @file curl_noise.frag
+ * @brief Curl noise fragment shader
+ * @author Vincent Tavernier <vince.tavernier@gmail.com>
+ * @see https://doi.org/10.1145/1276377.1276435
+ *
+ * Divergence-free vector field, computed as the curl of a noise potential
+ * using analytic derivatives. 2D targets use a scalar potential, 3D targets
+ * use a vector potential made of three decorrelated noises. Only the gradient
+ * potential loops at the scale, the simplex lattice cannot loop along the
+ * image axes.
+ */
+
+layout(location = 0) in vec3 uv;
+layout(location = 0) out vec4 o_FragColor;
+
+// Required built-ins
+#include "noise.glsl"
+#include "shared.glsl"
+
+// Noise statistics helpers
+#include "noise_stats.glsl"
+
+// Noises with derivatives
+#include "noise_derivatives.glsl"
+
+layout(location = 20) uniform int noise_potential;
+
+#define CURL_POTENTIAL_GRADIENT 0
+#define CURL_POTENTIAL_SIMPLEX 1
+
+/// Gradient of one component of the 3D vector potential
+vec3 potential3(vec3 p, uint i) {
+    uint seed = hash(PARAM_GLOBAL_SEED + i);
+
+    if (noise_potential == CURL_POTENTIAL_SIMPLEX) {
+        return simplexNoised(p, seed).yzw;
+    }
+
+    return gradientNoised(p, seed).yzw;
+}
+
+void main() {
+    if (iResolution.z > 1u) {
+        vec3 p = uv * PARAM_SCALE;
+        vec3 a = potential3(p, 0u);
+        vec3 b = potential3(p, 1u);
+        vec3 c = potential3(p, 2u);
+
+        o_FragColor = vec4(c.y - b.z, a.z - c.x, b.x - a.y, 1.0);
+    } else {
+        vec3 n;
+
+        if (noise_potential == CURL_POTENTIAL_SIMPLEX) {
+            n = simplexNoised(latticeSample(uv.xy, LATTICE_MODE_SIMPLEX_2D));
+        } else {
+            n = gradientNoised(latticeSample(uv.xy, LATTICE_MODE_RECT_2D));
+        }
+
+        o_FragColor = vec4(n.z, -n.y, 0.0, 1.0);
+    }
+}
+
+// vim: ft=glsl.doxygen
@@ -16,7 +16,7 @@ layout(location = 0) out vec4 o_FragColor;
 layout(location = 10) uniform float alpha_value;
 
 void main() {
-    ivec3 px = ivec3(uv * vec3(iResolution));
+    ivec3 px = ivec3(floor(uv * vec3(iResolution)));
 
     o_FragColor = vec4(px, alpha_value);
 }
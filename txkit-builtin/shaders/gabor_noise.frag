@@ -0,0 +1,109 @@
+#version 460 core
+
+/**
+ * @file gabor_noise.frag
+ * @brief Gabor noise fragment shader
+ * @author Vincent Tavernier <vince.tavernier@gmail.com>
+ * @see https://doi.org/10.1145/1531326.1531360
+ *
+ * Sparse convolution Gabor noise, using the same kernel distribution as
+ * phasor noise.
+ */
+
+layout(location = 0) in vec3 uv;
+layout(location = 0) out vec4 o_FragColor;
+
+// Required built-ins
+#include "noise.glsl"
+#include "shared.glsl"
+
+// Noise statistics helpers
+#include "noise_stats.glsl"
+
+// Fast rng
+#include "lcg.glsl"
+
+// Point distributions
+#include "points.glsl"
+
+// Sparse convolution kernels
+#include "kernels.glsl"
+
+layout(location = 22) uniform int noise_orientation_mode;
+
+#define GABOR_ORIENTATION_ISOTROPIC 0
+#define GABOR_ORIENTATION_ANISOTROPIC 1
+#define GABOR_ORIENTATION_FIELD 2
+
+float gabor(vec2 x, Kernel k) {
+    float gaus, osc;
+    float b = (1. / scale) * (1. / scale) * M_PI;
+
+    gaus = exp(-b * dot(x, x));
+    osc = 2. * M_PI * dot(x, k.angle) + k.phase;
+
+    return k.weight * gaus * cos(osc);
+}
+
+float noiseCell(vec2 pos, ivec2 cell, uint seed) {
+    float res = 0.;
+
+    // Seed the random number generator
+    LCG rng = lcgSeed(hash(cell, seed));
+
+    // Compute impulse count
+    PointSet ps = kernelsInit(rng);
+
+    // Fixed number of impulses per cell
+    for (int i = 0; i < ps.count; ++i) {
+        // Sample kernel parameters
+        vec4 fo = vec4(0.);
+        if (noise_orientation_mode == GABOR_ORIENTATION_FIELD) {
+            fo = kernelsField(pos, cell);
+        }
+
+        // Generate a kernel
+        Kernel k = kernelsNext(rng, ps, i, fo);
+
+        if (noise_orientation_mode == GABOR_ORIENTATION_ISOTROPIC) {
+            float w = M_2PI * lcgNext01(rng);
+            k.angle = noise_frequency / scale * vec2(cos(w), sin(w));
+        }
+
+        // Compute contribution
+        res += gabor(scale * (pos - k.pos), k);
+    }
+
+    return res;
+}
+
+vec3 noise(LatticeNoiseSample s) {
+    // Compute Gabor noise value
+    float res = 0.;
+
+    ivec2 cell = s.cell;
+    for (cell.x = s.cell.x - noise_lookahead;
+         cell.x <= s.cell.x + noise_lookahead; ++cell.x) {
+        for (cell.y = s.cell.y - noise_lookahead;
+             cell.y <= s.cell.y + noise_lookahead; ++cell.y) {
+            // Make sure the noise tiles correctly
+            ivec2 looped_cell = latticeLoop(cell);
+
+            // Compute the position of the current point relative to the target
+            // cell
+            vec2 position =
+                s.position - vec2(cell.x - s.cell.x, cell.y - s.cell.y);
+
+            // Add contribution of target noise cell
+            res += noiseCell(position, looped_cell, s.seed);
+        }
+    }
+
+    return vec3(to01(res / kernel_count));
+}
+
+void main() {
+    o_FragColor = vec4(noise(latticeSample(uv.xy, LATTICE_MODE_RECT_2D)), 1.);
+}
+
+// vim: ft=glsl.doxygen
@@ -2,12 +2,12 @@
 
 /**
  * @file gradient_noise.frag
- * @brief 2D gradient noise fragment shader
+ * @brief Gradient noise fragment shader
  * @author inigo quilez
  * @author Vincent Tavernier <vince.tavernier@gmail.com>
  * @see https://www.shadertoy.com/view/XdXGW8
  *
- * 2D Gradient noise, adapted for use in txkit.
+ * 2D, 3D and 4D gradient noise, adapted for use in txkit.
  */
 
 layout(location = 0) in vec3 uv;
@@ -17,9 +17,20 @@ layout(location = 0) out vec4 o_FragColor;
 #include "noise.glsl"
 #include "shared.glsl"
 
+/// Lattice period along (x, y, z, time), in cells
+layout(location = 23) uniform vec4 period;
+#define PARAM_PERIOD period
+
 // Noise statistics helpers
 #include "noise_stats.glsl"
 
+// Noises with derivatives
+#include "noise_derivatives.glsl"
+
+layout(location = 20) uniform int noise_output;
+layout(location = 21) uniform int noise_dimensions;
+layout(location = 22) uniform float time;
+
 vec2 noisehash(uvec2 p, uint seed) {
     float x = 2. * M_PI * tofloat11(hash(p, seed));
     return vec2(cos(x), sin(x));
@@ -46,8 +57,27 @@ float noise(LatticeNoiseSample s) {
 }
 
 void main() {
-    o_FragColor = vec4(
-        vec3(to01(noise(latticeSample(uv.xy, LATTICE_MODE_RECT_2D)))), 1.0);
+    LatticeNoiseSample s = latticeSample(uv.xy, LATTICE_MODE_RECT_2D);
+    vec3 p = uv * PARAM_SCALE;
+    vec4 n;
+
+    // 3D and 4D gradient noises are scaled to the same [-1, 1] range
+    if (noise_dimensions == LATTICE_DIMENSIONS_3D) {
+        n = 1.2 * gradientNoised(p, PARAM_GLOBAL_SEED);
+    } else if (noise_dimensions == LATTICE_DIMENSIONS_4D) {
+        n = 1.2 * gradientNoised(vec4(p, time), PARAM_GLOBAL_SEED);
+    } else if (noise_output == LATTICE_OUTPUT_DERIVATIVES) {
+        n = vec4(gradientNoised(s), 0.0);
+    } else {
+        n = vec4(noise(s), 0.0, 0.0, 0.0);
+    }
+
+    if (noise_output == LATTICE_OUTPUT_DERIVATIVES) {
+        // Value and derivatives of the output with respect to lattice coordinates
+        o_FragColor = vec4(to01(n.x), .5 * n.yzw);
+    } else {
+        o_FragColor = vec4(vec3(to01(n.x)), 1.0);
+    }
 }
 
 // vim: ft=glsl.doxygen
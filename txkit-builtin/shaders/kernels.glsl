@@ -0,0 +1,89 @@
+/**
+ * @file kernels.glsl
+ * @brief Kernel generation for sparse convolution noises
+ * @author Vincent Tavernier <vince.tavernier@gmail.com>
+ *
+ * Declares the uniforms shared by sparse convolution noises (phasor, Gabor).
+ * Requires noise_stats.glsl, lcg.glsl and points.glsl.
+ */
+
+#ifndef _KERNELS_GLSL_
+#define _KERNELS_GLSL_
+
+layout(location = 20) uniform int noise_lookahead;
+layout(location = 21) uniform int kernel_count;
+layout(location = 23) uniform int noise_weights;
+layout(location = 24) uniform int noise_point_distribution;
+
+layout(location = 25) uniform float noise_frequency;
+layout(location = 26) uniform float noise_angle;
+
+layout(location = 27) uniform float jitter_amount;
+layout(location = 28) uniform int jitter_max;
+
+layout(location = 29, binding = 0) uniform sampler2D frequency_orientation_field;
+
+#define KERNEL_WEIGHTS_NONE 0
+#define KERNEL_WEIGHTS_BERNOULLI 1
+#define KERNEL_WEIGHTS_UNIFORM 2
+
+/// Sparse convolution kernel
+struct Kernel {
+    /// Position in cell coordinates
+    vec2 pos;
+    /// Weight of the kernel
+    float weight;
+    /// Frequency vector, in oscillations per pixel
+    vec2 angle;
+    /// Phase of the kernel
+    float phase;
+};
+
+/**
+ * @brief Initialize the set of kernels for a cell
+ * @param rng Random number generator seeded for the current cell
+ */
+PointSet kernelsInit(inout LCG rng) {
+    return pointsInit(rng, noise_point_distribution, kernel_count,
+                      jitter_amount, jitter_max);
+}
+
+/**
+ * @brief Sample the frequency and orientation field for a cell
+ * @param pos Position relative to the cell
+ * @param cell Cell coordinates
+ */
+vec4 kernelsField(vec2 pos, ivec2 cell) {
+    return texture(frequency_orientation_field, (pos + vec2(cell)) / scale);
+}
+
+/**
+ * @brief Generate the next kernel of a cell
+ * @param rng Random number generator used in kernelsInit
+ * @param ps Set of kernels for the cell
+ * @param i Index of the kernel to generate
+ * @param fo Frequency and orientation field sample
+ */
+Kernel kernelsNext(inout LCG rng, PointSet ps, int i, vec4 fo) {
+    Kernel k;
+    k.pos = pointsNext(rng, ps, i);
+
+    // Compute weighting: always step the generator so we can get the same
+    // image with and without weights
+    float v = lcgNext11(rng);
+    k.weight = 1.;
+    if (noise_weights == KERNEL_WEIGHTS_BERNOULLI) {
+        k.weight = v < 0. ? -1. : 1.;
+    } else if (noise_weights == KERNEL_WEIGHTS_UNIFORM) {
+        k.weight = v;
+    }
+
+    k.angle = noise_frequency / scale * (1. + fo.r) * vec2(cos(noise_angle + fo.g), sin(noise_angle + fo.g));
+    k.phase = 0.;
+
+    return k;
+}
+
+#endif /* _KERNELS_GLSL_ */
+
+// vim: ft=glsl.doxygen
@@ -0,0 +1,382 @@
+/**
+ * @file noise_derivatives.glsl
+ * @brief Lattice noises with analytic derivatives
+ * @author inigo quilez
+ * @author Vincent Tavernier <vince.tavernier@gmail.com>
+ * @see https://iquilezles.org/articles/gradientnoise/
+ *
+ * Lattice noises returning their value along with their partial derivatives
+ * with respect to the lattice coordinates. The 2D variants evaluate the same
+ * noise as value_noise.frag, gradient_noise.frag and simplex_noise.frag. The 4D
+ * variants only return the derivatives along the first three coordinates.
+ */
+
+#ifndef _NOISE_DERIVATIVES_GLSL_
+#define _NOISE_DERIVATIVES_GLSL_
+
+#include "shared.glsl"
+#include "noise_stats.glsl"
+
+/// Lattice noise output: noise value
+#define LATTICE_OUTPUT_VALUE 0
+/// Lattice noise output: noise value and partial derivatives
+#define LATTICE_OUTPUT_DERIVATIVES 1
+
+/// Lattice noise dimensions: 2D lattice, evaluated per layer
+#define LATTICE_DIMENSIONS_2D 0
+/// Lattice noise dimensions: 3D lattice
+#define LATTICE_DIMENSIONS_3D 1
+/// Lattice noise dimensions: 4D lattice, with time as the last coordinate
+#define LATTICE_DIMENSIONS_4D 2
+
+/// Simplex constant for 1/3
+const float SIMPLEX3_F = 0.333333333;
+/// Simplex constant for 1/6
+const float SIMPLEX3_G = 0.166666667;
+/// Simplex constant for (sqrt(5)-1)/4
+const float SIMPLEX4_F = 0.309016994;
+/// Simplex constant for (5-sqrt(5))/20
+const float SIMPLEX4_G = 0.138196601;
+
+float valueNoiseHash(uvec2 p, uint seed) { return tofloat(hash(p, seed)); }
+
+vec2 gradientNoiseHash(uvec2 p, uint seed) {
+    float x = 2. * M_PI * tofloat11(hash(p, seed));
+    return vec2(cos(x), sin(x));
+}
+
+vec2 simplexNoiseHash(uvec2 p, uint seed) { return tofloat11(hash2(p, seed)); }
+
+vec3 noiseHash(uvec3 p, uint seed) { return tofloat11(hash3(p, seed)); }
+
+vec4 noiseHash(uvec4 p, uint seed) { return tofloat11(hash4(p, seed)); }
+
+/**
+ * @brief 2D value noise with derivatives
+ * @param s Rectangular lattice sample
+ * @return (value, d/dx, d/dy)
+ */
+vec3 valueNoised(LatticeNoiseSample s) {
+    uvec2 i = uvec2(s.cell);
+    vec2 f = s.position;
+
+    vec2 u = f * f * (3. - 2. * f);
+    vec2 du = 6. * f * (1. - f);
+
+    float a = valueNoiseHash(latticeLoop(i + uvec2(0, 0)), s.seed);
+    float b = valueNoiseHash(latticeLoop(i + uvec2(1, 0)), s.seed);
+    float c = valueNoiseHash(latticeLoop(i + uvec2(0, 1)), s.seed);
+    float d = valueNoiseHash(latticeLoop(i + uvec2(1, 1)), s.seed);
+
+    float value = mix(mix(a, b, u.x), mix(c, d, u.x), u.y);
+    vec2 dv = du * (vec2(b - a, c - a) + (a - b - c + d) * u.yx);
+
+    return vec3(value, dv) * 0.73582062;
+}
+
+/**
+ * @brief 2D gradient noise with derivatives
+ * @param s Rectangular lattice sample
+ * @return (value, d/dx, d/dy)
+ */
+vec3 gradientNoised(LatticeNoiseSample s) {
+    uvec2 i = uvec2(s.cell);
+    vec2 f = s.position;
+
+    vec2 u = f * f * (3. - 2. * f);
+    vec2 du = 6. * f * (1. - f);
+
+    vec2 ga = gradientNoiseHash(latticeLoop(i + uvec2(0, 0)), s.seed);
+    vec2 gb = gradientNoiseHash(latticeLoop(i + uvec2(1, 0)), s.seed);
+    vec2 gc = gradientNoiseHash(latticeLoop(i + uvec2(0, 1)), s.seed);
+    vec2 gd = gradientNoiseHash(latticeLoop(i + uvec2(1, 1)), s.seed);
+
+    float va = dot(ga, f - vec2(0, 0));
+    float vb = dot(gb, f - vec2(1, 0));
+    float vc = dot(gc, f - vec2(0, 1));
+    float vd = dot(gd, f - vec2(1, 1));
+
+    float value = mix(mix(va, vb, u.x), mix(vc, vd, u.x), u.y);
+    vec2 d = ga + u.x * (gb - ga) + u.y * (gc - ga) +
+             u.x * u.y * (ga - gb - gc + gd) +
+             du * (u.yx * (va - vb - vc + vd) + vec2(vb, vc) - va);
+
+    return vec3(value, d) * 1.49315244;
+}
+
+/**
+ * @brief 2D simplex noise with derivatives
+ * @param s Simplex lattice sample
+ * @return (value, d/dx, d/dy)
+ */
+vec3 simplexNoised(LatticeNoiseSample s) {
+    vec2 a = s.position;
+    float m = step(a.y, a.x);
+    vec2 o = vec2(m, 1.0 - m);
+    vec2 b = a - o + SIMPLEX_K2;
+    vec2 c = a - 1.0 + 2.0 * SIMPLEX_K2;
+
+    vec2 ga = simplexNoiseHash(s.cell + 0, s.seed);
+    vec2 gb = simplexNoiseHash(s.cell + uvec2(o), s.seed);
+    vec2 gc = simplexNoiseHash(s.cell + 1, s.seed);
+
+    vec3 h = max(0.5 - vec3(dot(a, a), dot(b, b), dot(c, c)), 0.0);
+    vec3 h3 = h * h * h;
+    vec3 v = vec3(dot(a, ga), dot(b, gb), dot(c, gc));
+
+    float value = dot(h3 * h * v, vec3(71.2825901));
+    vec2 d = h3.x * (h.x * ga - 8. * v.x * a) + h3.y * (h.y * gb - 8. * v.y * b) +
+             h3.z * (h.z * gc - 8. * v.z * c);
+
+    return vec3(value, d * 71.2825901);
+}
+
+/**
+ * @brief 3D value noise with derivatives
+ * @param p Position in lattice coordinates
+ * @param seed Random seed
+ * @return (value, d/dx, d/dy, d/dz)
+ */
+vec4 valueNoised(vec3 p, uint seed) {
+    uvec3 i = uvec3(ivec3(floor(p)));
+    vec3 w = fract(p);
+
+    vec3 u = w * w * (3. - 2. * w);
+    vec3 du = 6. * w * (1. - w);
+
+    float a = tofloat(hash(latticeLoop(i + uvec3(0, 0, 0)), seed));
+    float b = tofloat(hash(latticeLoop(i + uvec3(1, 0, 0)), seed));
+    float c = tofloat(hash(latticeLoop(i + uvec3(0, 1, 0)), seed));
+    float d = tofloat(hash(latticeLoop(i + uvec3(1, 1, 0)), seed));
+    float e = tofloat(hash(latticeLoop(i + uvec3(0, 0, 1)), seed));
+    float f = tofloat(hash(latticeLoop(i + uvec3(1, 0, 1)), seed));
+    float g = tofloat(hash(latticeLoop(i + uvec3(0, 1, 1)), seed));
+    float h = tofloat(hash(latticeLoop(i + uvec3(1, 1, 1)), seed));
+
+    float k0 = b - a;
+    float k1 = c - a;
+    float k2 = e - a;
+    float k3 = a - b - c + d;
+    float k4 = a - c - e + g;
+    float k5 = a - b - e + f;
+    float k6 = -a + b + c - d + e - f - g + h;
+
+    float value = a + u.x * k0 + u.y * k1 + u.z * k2 + u.x * u.y * k3 +
+                  u.y * u.z * k4 + u.z * u.x * k5 + u.x * u.y * u.z * k6;
+    vec3 dv = du * (vec3(k0, k1, k2) + u.yzx * vec3(k3, k4, k5) +
+                    u.zxy * vec3(k5, k3, k4) + u.yzx * u.zxy * k6);
+
+    return vec4(value, dv) * 0.73582062;
+}
+
+/**
+ * @brief 3D gradient noise with derivatives
+ * @param p Position in lattice coordinates
+ * @param seed Random seed
+ * @return (value, d/dx, d/dy, d/dz)
+ */
+vec4 gradientNoised(vec3 p, uint seed) {
+    uvec3 i = uvec3(ivec3(floor(p)));
+    vec3 w = fract(p);
+
+    vec3 u = w * w * (3. - 2. * w);
+    vec3 du = 6. * w * (1. - w);
+
+    vec3 ga = noiseHash(latticeLoop(i + uvec3(0, 0, 0)), seed);
+    vec3 gb = noiseHash(latticeLoop(i + uvec3(1, 0, 0)), seed);
+    vec3 gc = noiseHash(latticeLoop(i + uvec3(0, 1, 0)), seed);
+    vec3 gd = noiseHash(latticeLoop(i + uvec3(1, 1, 0)), seed);
+    vec3 ge = noiseHash(latticeLoop(i + uvec3(0, 0, 1)), seed);
+    vec3 gf = noiseHash(latticeLoop(i + uvec3(1, 0, 1)), seed);
+    vec3 gg = noiseHash(latticeLoop(i + uvec3(0, 1, 1)), seed);
+    vec3 gh = noiseHash(latticeLoop(i + uvec3(1, 1, 1)), seed);
+
+    float va = dot(ga, w - vec3(0, 0, 0));
+    float vb = dot(gb, w - vec3(1, 0, 0));
+    float vc = dot(gc, w - vec3(0, 1, 0));
+    float vd = dot(gd, w - vec3(1, 1, 0));
+    float ve = dot(ge, w - vec3(0, 0, 1));
+    float vf = dot(gf, w - vec3(1, 0, 1));
+    float vg = dot(gg, w - vec3(0, 1, 1));
+    float vh = dot(gh, w - vec3(1, 1, 1));
+
+    float k0 = vb - va;
+    float k1 = vc - va;
+    float k2 = ve - va;
+    float k3 = va - vb - vc + vd;
+    float k4 = va - vc - ve + vg;
+    float k5 = va - vb - ve + vf;
+    float k6 = -va + vb + vc - vd + ve - vf - vg + vh;
+
+    float value = va + u.x * k0 + u.y * k1 + u.z * k2 + u.x * u.y * k3 +
+                  u.y * u.z * k4 + u.z * u.x * k5 + u.x * u.y * u.z * k6;
+    vec3 d = ga + u.x * (gb - ga) + u.y * (gc - ga) + u.z * (ge - ga) +
+             u.x * u.y * (ga - gb - gc + gd) + u.y * u.z * (ga - gc - ge + gg) +
+             u.z * u.x * (ga - gb - ge + gf) +
+             u.x * u.y * u.z * (-ga + gb + gc - gd + ge - gf - gg + gh) +
+             du * (vec3(k0, k1, k2) + u.yzx * vec3(k3, k4, k5) +
+                   u.zxy * vec3(k5, k3, k4) + u.yzx * u.zxy * k6);
+
+    return vec4(value, d);
+}
+
+/**
+ * @brief 3D simplex noise with derivatives
+ * @param p Position in lattice coordinates
+ * @param seed Random seed
+ * @return (value, d/dx, d/dy, d/dz)
+ */
+vec4 simplexNoised(vec3 p, uint seed) {
+    // Skew the position to find the simplex cell
+    vec3 s = floor(p + dot(p, vec3(SIMPLEX3_F)));
+    vec3 x = p - s + dot(s, vec3(SIMPLEX3_G));
+
+    // Find the simplex the position lies in. The last comparison is strict so
+    // that ties still describe an ordering of the coordinates.
+    vec3 e = vec3(step(x.y, x.x), step(x.z, x.y), 1. - step(x.z, x.x));
+    vec3 i1 = e * (1. - e.zxy);
+    vec3 i2 = 1. - e.zxy * (1. - e);
+
+    vec3 x0 = x;
+    vec3 x1 = x - i1 + SIMPLEX3_G;
+    vec3 x2 = x - i2 + 2. * SIMPLEX3_G;
+    vec3 x3 = x - 1. + 3. * SIMPLEX3_G;
+
+    uvec3 cell = uvec3(ivec3(s));
+    vec3 g0 = noiseHash(cell, seed);
+    vec3 g1 = noiseHash(cell + uvec3(i1), seed);
+    vec3 g2 = noiseHash(cell + uvec3(i2), seed);
+    vec3 g3 = noiseHash(cell + 1, seed);
+
+    vec4 h = max(0.5 - vec4(dot(x0, x0), dot(x1, x1), dot(x2, x2), dot(x3, x3)), 0.);
+    vec4 h3 = h * h * h;
+    vec4 v = vec4(dot(x0, g0), dot(x1, g1), dot(x2, g2), dot(x3, g3));
+
+    float value = dot(h3 * h * v, vec4(64.));
+    vec3 d = h3.x * (h.x * g0 - 8. * v.x * x0) + h3.y * (h.y * g1 - 8. * v.y * x1) +
+             h3.z * (h.z * g2 - 8. * v.z * x2) + h3.w * (h.w * g3 - 8. * v.w * x3);
+
+    return vec4(value, d * 64.);
+}
+
+/**
+ * @brief 4D value noise with derivatives
+ * @param p Position in lattice coordinates
+ * @param seed Random seed
+ * @return (value, d/dx, d/dy, d/dz)
+ */
+vec4 valueNoised(vec4 p, uint seed) {
+    uvec4 i = uvec4(ivec4(floor(p)));
+    vec4 w = fract(p);
+
+    vec4 u = w * w * (3. - 2. * w);
+    vec4 du = 6. * w * (1. - w);
+
+    float value = 0.;
+    vec3 dv = vec3(0.);
+
+    for (uint k = 0u; k < 16u; ++k) {
+        // Corner offset, with its interpolation weight along each axis
+        uvec4 o = (uvec4(k) >> uvec4(0, 1, 2, 3)) & 1u;
+        vec4 a = mix(1. - u, u, vec4(o));
+        vec4 da = mix(-du, du, vec4(o));
+
+        float n = tofloat(hash(latticeLoop(i + o), seed));
+
+        value += n * a.x * a.y * a.z * a.w;
+        dv += n * a.w *
+              vec3(da.x * a.y * a.z, a.x * da.y * a.z, a.x * a.y * da.z);
+    }
+
+    return vec4(value, dv) * 0.73582062;
+}
+
+/**
+ * @brief 4D gradient noise with derivatives
+ * @param p Position in lattice coordinates
+ * @param seed Random seed
+ * @return (value, d/dx, d/dy, d/dz)
+ */
+vec4 gradientNoised(vec4 p, uint seed) {
+    uvec4 i = uvec4(ivec4(floor(p)));
+    vec4 w = fract(p);
+
+    vec4 u = w * w * (3. - 2. * w);
+    vec4 du = 6. * w * (1. - w);
+
+    float value = 0.;
+    vec3 d = vec3(0.);
+
+    for (uint k = 0u; k < 16u; ++k) {
+        // Corner offset, with its interpolation weight along each axis
+        uvec4 o = (uvec4(k) >> uvec4(0, 1, 2, 3)) & 1u;
+        vec4 a = mix(1. - u, u, vec4(o));
+        vec4 da = mix(-du, du, vec4(o));
+
+        vec4 g = noiseHash(latticeLoop(i + o), seed);
+        float v = dot(g, w - vec4(o));
+        float weight = a.x * a.y * a.z * a.w;
+
+        value += v * weight;
+        d += g.xyz * weight +
+             v * a.w * vec3(da.x * a.y * a.z, a.x * da.y * a.z, a.x * a.y * da.z);
+    }
+
+    return vec4(value, d);
+}
+
+/**
+ * @brief 4D simplex noise with derivatives
+ * @param p Position in lattice coordinates
+ * @param seed Random seed
+ * @return (value, d/dx, d/dy, d/dz)
+ * @see https://github.com/ashima/webgl-noise
+ */
+vec4 simplexNoised(vec4 p, uint seed) {
+    // Skew the position to find the simplex cell
+    vec4 s = floor(p + dot(p, vec4(SIMPLEX4_F)));
+    vec4 x0 = p - s + dot(s, vec4(SIMPLEX4_G));
+
+    // Rank the coordinates to find the simplex the position lies in
+    vec3 isX = step(x0.yzw, x0.xxx);
+    vec3 isYZ = step(x0.zww, x0.yyz);
+    vec4 i0 = vec4(isX.x + isX.y + isX.z, 1. - isX);
+    i0.y += isYZ.x + isYZ.y;
+    i0.zw += 1. - isYZ.xy;
+    i0.z += isYZ.z;
+    i0.w += 1. - isYZ.z;
+
+    vec4 i3 = clamp(i0, 0., 1.);
+    vec4 i2 = clamp(i0 - 1., 0., 1.);
+    vec4 i1 = clamp(i0 - 2., 0., 1.);
+
+    vec4 x1 = x0 - i1 + SIMPLEX4_G;
+    vec4 x2 = x0 - i2 + 2. * SIMPLEX4_G;
+    vec4 x3 = x0 - i3 + 3. * SIMPLEX4_G;
+    vec4 x4 = x0 - 1. + 4. * SIMPLEX4_G;
+
+    uvec4 cell = uvec4(ivec4(s));
+    vec4 g0 = noiseHash(cell, seed);
+    vec4 g1 = noiseHash(cell + uvec4(i1), seed);
+    vec4 g2 = noiseHash(cell + uvec4(i2), seed);
+    vec4 g3 = noiseHash(cell + uvec4(i3), seed);
+    vec4 g4 = noiseHash(cell + 1, seed);
+
+    vec4 h = max(0.5 - vec4(dot(x0, x0), dot(x1, x1), dot(x2, x2), dot(x3, x3)), 0.);
+    float h4 = max(0.5 - dot(x4, x4), 0.);
+    vec4 hh = h * h * h;
+    float hh4 = h4 * h4 * h4;
+    vec4 v = vec4(dot(x0, g0), dot(x1, g1), dot(x2, g2), dot(x3, g3));
+    float v4 = dot(x4, g4);
+
+    float value = dot(hh * h * v, vec4(1.)) + hh4 * h4 * v4;
+    vec4 d = hh.x * (h.x * g0 - 8. * v.x * x0) + hh.y * (h.y * g1 - 8. * v.y * x1) +
+             hh.z * (h.z * g2 - 8. * v.z * x2) + hh.w * (h.w * g3 - 8. * v.w * x3) +
+             hh4 * (h4 * g4 - 8. * v4 * x4);
+
+    return vec4(value, d.xyz) * 62.;
+}
+
+#endif /* _NOISE_DERIVATIVES_GLSL_ */
+
+// vim: ft=glsl.doxygen
@@ -36,6 +36,12 @@
 layout(location = 52) uniform float scale;
 #endif /* PARAM_SCALE */
 
+#ifndef PARAM_PERIOD
+/// Explicit period of the lattice along each axis, in cells (0 to loop at the
+/// scale)
+#define PARAM_PERIOD vec4(0.)
+#endif /* PARAM_PERIOD */
+
 /// Current statistics computation mode
 layout(location = 50) uniform int statsMode;
 /// Location for local sampling
@@ -91,9 +97,9 @@ LatticeNoiseSample latticeSample(vec2 position, int mode) {
 
     if (mode == LATTICE_MODE_RECT_2D) {
         res.position = fract(position);
-        res.cell = ivec2(position);
+        res.cell = ivec2(floor(position));
     } else if (mode == LATTICE_MODE_SIMPLEX_2D) {
-        res.cell = ivec2(position + (position.x + position.y) * SIMPLEX_K1);
+        res.cell = ivec2(floor(position + (position.x + position.y) * SIMPLEX_K1));
         res.position =
             position - vec2(res.cell) + (res.cell.x + res.cell.y) * SIMPLEX_K2;
     }
@@ -102,32 +108,82 @@ LatticeNoiseSample latticeSample(vec2 position, int mode) {
 }
 
 /**
- * @brief Ensure 2D lattice cells loop around the texture borders
- * @param p Cell coordinates to loop
- * @todo Support looping simplex grids
+ * @brief Get the period of the lattice along each axis
+ * @return Period in cells along each axis, or 0 for axes which do not loop
+ *
+ * Axes without an explicit period loop at the scale, so that the lattice loops
+ * around the texture borders, unless a statistics mode is active.
  */
-ivec2 latticeLoop(ivec2 p) {
-    const int S = int(PARAM_SCALE);
+uvec4 latticePeriod() {
+    uvec4 period = uvec4(PARAM_PERIOD);
 
     if (statsMode == STATS_MODE_NORMAL) {
-        return ivec2(p.x >= 0 ? p.x % S : S - (-p.x % S),
-                     p.y >= 0 ? p.y % S : S - (-p.y % S));
+        period = mix(uvec4(int(PARAM_SCALE)), period, greaterThan(period, uvec4(0u)));
+    }
+
+    return period;
+}
+
+/**
+ * @brief Loop a cell coordinate over a period
+ * @param p Cell coordinate to loop
+ * @param period Period, or 0 to leave the coordinate unchanged
+ */
+int latticeLoop(int p, uint period) {
+    int S = int(period);
+
+    if (S > 0) {
+        return p >= 0 ? p % S : (S - (-p % S)) % S;
     }
 
     return p;
 }
 
 /**
- * @brief Ensure 2D lattice cells loop around the texture borders
+ * @brief Loop a cell coordinate over a period
+ * @param p Cell coordinate to loop, as a wrapped signed coordinate
+ * @param period Period, or 0 to leave the coordinate unchanged
+ */
+uint latticeLoop(uint p, uint period) { return uint(latticeLoop(int(p), period)); }
+
+/**
+ * @brief Ensure 2D lattice cells loop around the lattice period
+ * @param p Cell coordinates to loop
+ * @todo Support looping simplex grids
+ */
+ivec2 latticeLoop(ivec2 p) {
+    uvec2 period = latticePeriod().xy;
+    return ivec2(latticeLoop(p.x, period.x), latticeLoop(p.y, period.y));
+}
+
+/**
+ * @brief Ensure 2D lattice cells loop around the lattice period
  * @param p Cell coordinates to loop
  * @todo Support looping simplex grids
  */
 uvec2 latticeLoop(uvec2 p) {
-    if (statsMode == STATS_MODE_NORMAL) {
-        return p % int(PARAM_SCALE);
-    }
+    uvec2 period = latticePeriod().xy;
+    return uvec2(latticeLoop(p.x, period.x), latticeLoop(p.y, period.y));
+}
 
-    return p;
+/**
+ * @brief Ensure 3D lattice cells loop around the lattice period
+ * @param p Cell coordinates to loop
+ */
+uvec3 latticeLoop(uvec3 p) {
+    uvec3 period = latticePeriod().xyz;
+    return uvec3(latticeLoop(p.x, period.x), latticeLoop(p.y, period.y),
+                 latticeLoop(p.z, period.z));
+}
+
+/**
+ * @brief Ensure 4D lattice cells loop around the lattice period
+ * @param p Cell coordinates to loop
+ */
+uvec4 latticeLoop(uvec4 p) {
+    uvec4 period = latticePeriod();
+    return uvec4(latticeLoop(p.x, period.x), latticeLoop(p.y, period.y),
+                 latticeLoop(p.z, period.z), latticeLoop(p.w, period.w));
 }
 
 #endif /* _NOISE_STATS_GLSL_ */
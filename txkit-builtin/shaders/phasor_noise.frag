@@ -18,38 +18,26 @@ layout(location = 0) out vec4 o_FragColor;
 #include "noise.glsl"
 #include "shared.glsl"
 
+/// Lattice period along (x, y), in cells
+layout(location = 30) uniform vec2 period;
+#define PARAM_PERIOD vec4(period, 0., 0.)
+
 // Noise statistics helpers
 #include "noise_stats.glsl"
 
 // Fast rng
 #include "lcg.glsl"
 
-layout(location = 20) uniform int noise_lookahead;
-layout(location = 21) uniform int kernel_count;
-layout(location = 22) uniform int noise_profile;
-layout(location = 23) uniform int noise_weights;
-layout(location = 24) uniform int noise_point_distribution;
-
-layout(location = 25) uniform float noise_frequency;
-layout(location = 26) uniform float noise_angle;
+// Point distributions
+#include "points.glsl"
 
-layout(location = 27) uniform float jitter_amount;
-layout(location = 28) uniform int jitter_max;
+// Sparse convolution kernels
+#include "kernels.glsl"
 
-layout(location = 29, binding = 0) uniform sampler2D frequency_orientation_field;
+layout(location = 22) uniform int noise_profile;
 
 #define PHASOR_PROFILE_IMPULSES 5
 
-#define PHASOR_POINTS_RECT_JITTERED 2
-#define PHASOR_POINTS_HEX_JITTERED 3
-
-struct Kernel {
-    vec2 pos;
-    float weight;
-    vec2 angle;
-    float phase;
-};
-
 vec2 phasor(vec2 x, Kernel k) {
     if (noise_profile == PHASOR_PROFILE_IMPULSES) {
         return exp(-M_PI * (0.125 * iResolution.x / scale) * (0.125 * iResolution.x / scale) * dot(x, x)) * vec2(1., k.weight);
@@ -71,60 +59,15 @@ vec2 noiseCell(vec2 pos, ivec2 cell, uint seed) {
     LCG rng = lcgSeed(hash(cell, seed));
 
     // Compute impulse count
-    int ic = kernel_count, tc;
-    ivec2 bc;
-
-    if (noise_point_distribution == 1 /* PHASOR_POINTS_POISSON */) {
-        ic = lcgPoisson(rng, ic);
-    } else if (noise_point_distribution == PHASOR_POINTS_RECT_JITTERED ||
-            noise_point_distribution == PHASOR_POINTS_HEX_JITTERED) {
-        bc.x = int(sqrt(ic));
-        bc.x = jitter_max == 0 ? bc.x : min(jitter_max, bc.x);
-        bc.y = jitter_max == 0 ? ic / bc.x : min(jitter_max, ic / bc.x);
-        tc = bc.x * bc.y;
-        ic = (ic / tc) * tc;
-    }
+    PointSet ps = kernelsInit(rng);
 
     // Fixed number of impulses per cell
-    for (int i = 0; i < ic; ++i) {
+    for (int i = 0; i < ps.count; ++i) {
         // Sample kernel parameters
-        vec4 fo = texture(frequency_orientation_field, (pos + vec2(cell)) / scale);
+        vec4 fo = kernelsField(pos, cell);
 
         // Generate a kernel
-        Kernel k;
-        k.pos = .5 + (vec2(lcgNext01(rng), lcgNext01(rng)) - .5) * jitter_amount;
-
-        if (noise_point_distribution == PHASOR_POINTS_RECT_JITTERED ||
-                noise_point_distribution == PHASOR_POINTS_HEX_JITTERED)
-        {
-            int col = (i % tc) % bc.x;
-            int row = (i % tc) / bc.x;
-
-            if (noise_point_distribution == PHASOR_POINTS_HEX_JITTERED)
-            {
-                // Triangle transform
-                k.pos = vec2(.25 * (k.pos.x - k.pos.y), .5 * abs(k.pos.x + k.pos.y));
-
-                // Offset one every two triangles
-                if ((ic + row) % 2 == 0)
-                    k.pos = vec2(k.pos.x + .5, 1. - k.pos.y);
-            }
-
-            k.pos = (k.pos + vec2(col, row)) / vec2(bc);
-        }
-
-        // Compute weighting: always step the generator so we can get the same
-        // image with and without weights
-        float v = lcgNext11(rng);
-        k.weight = 1.;
-        if (noise_weights == 1 /* PHASOR_WEIGHTS_BERNOULLI */) {
-            k.weight = v < 0. ? -1. : 1.;
-        } else if (noise_weights == 2 /* PHASOR_WEIGHTS_UNIFORM */) {
-            k.weight = v;
-        }
-
-        k.angle = noise_frequency / scale * (1. + fo.r) * vec2(cos(noise_angle + fo.g), sin(noise_angle + fo.g));
-        k.phase = 0.;
+        Kernel k = kernelsNext(rng, ps, i, fo);
 
         // Compute contribution
         res += phasor(scale * (pos - k.pos), k);
@@ -0,0 +1,95 @@
+/**
+ * @file points.glsl
+ * @brief Point distributions for cell-based noises
+ * @author Vincent Tavernier <vince.tavernier@gmail.com>
+ *
+ * Requires lcg.glsl.
+ */
+
+#ifndef _POINTS_GLSL_
+#define _POINTS_GLSL_
+
+#define POINTS_STRAT_POISSON 0
+#define POINTS_POISSON 1
+#define POINTS_RECT_JITTERED 2
+#define POINTS_HEX_JITTERED 3
+
+/// Points to generate in a cell
+struct PointSet {
+    /// Point distribution
+    int distribution;
+    /// Number of points in the cell
+    int count;
+    /// Number of subcells for jittered distributions
+    int total;
+    /// Subcell grid size for jittered distributions
+    ivec2 grid;
+    /// Jittering amount, 0 = no random, 1 = full subcell random
+    float jitterAmount;
+};
+
+/**
+ * @brief Initialize the set of points for a cell
+ * @param rng Random number generator seeded for the current cell
+ * @param distribution Point distribution
+ * @param count Requested number of points per cell
+ * @param jitterAmount Jittering amount
+ * @param jitterMax Max jittering subcells, 0 = no limit
+ */
+PointSet pointsInit(inout LCG rng, int distribution, int count,
+                    float jitterAmount, int jitterMax) {
+    PointSet ps;
+    ps.distribution = distribution;
+    ps.count = count;
+    ps.total = 0;
+    ps.grid = ivec2(0);
+    ps.jitterAmount = jitterAmount;
+
+    if (distribution == POINTS_POISSON) {
+        ps.count = lcgPoisson(rng, count);
+    } else if (distribution == POINTS_RECT_JITTERED ||
+               distribution == POINTS_HEX_JITTERED) {
+        ps.grid.x = int(sqrt(count));
+        ps.grid.x = jitterMax == 0 ? ps.grid.x : min(jitterMax, ps.grid.x);
+        ps.grid.y = jitterMax == 0 ? count / ps.grid.x
+                                   : min(jitterMax, count / ps.grid.x);
+        ps.total = ps.grid.x * ps.grid.y;
+        ps.count = (count / ps.total) * ps.total;
+    }
+
+    return ps;
+}
+
+/**
+ * @brief Generate the next point of a cell
+ * @param rng Random number generator used in pointsInit
+ * @param ps Set of points for the cell
+ * @param i Index of the point to generate
+ * @return Position of the point in cell coordinates
+ */
+vec2 pointsNext(inout LCG rng, PointSet ps, int i) {
+    vec2 pos = .5 + (vec2(lcgNext01(rng), lcgNext01(rng)) - .5) * ps.jitterAmount;
+
+    if (ps.distribution == POINTS_RECT_JITTERED ||
+        ps.distribution == POINTS_HEX_JITTERED) {
+        int col = (i % ps.total) % ps.grid.x;
+        int row = (i % ps.total) / ps.grid.x;
+
+        if (ps.distribution == POINTS_HEX_JITTERED) {
+            // Triangle transform
+            pos = vec2(.25 * (pos.x - pos.y), .5 * abs(pos.x + pos.y));
+
+            // Offset one every two triangles
+            if ((ps.count + row) % 2 == 0)
+                pos = vec2(pos.x + .5, 1. - pos.y);
+        }
+
+        pos = (pos + vec2(col, row)) / vec2(ps.grid);
+    }
+
+    return pos;
+}
+
+#endif /* _POINTS_GLSL_ */
+
+// vim: ft=glsl.doxygen
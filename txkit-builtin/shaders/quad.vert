@@ -10,12 +10,23 @@
 
 layout(location = 0) out vec3 uv;
 
+/// Resolution of the full image
 layout(location = 0) uniform uvec3 iResolution;
+/// Layer of the output image being rendered
 layout(location = 1) uniform uint iLayer;
+/// Origin of the output image on the full image plane, in pixels
+layout(location = 2) uniform vec3 iOffset;
+/// Resolution of the output image
+layout(location = 3) uniform uvec3 iSize;
+/// Size of an output pixel, in full image pixels
+layout(location = 4) uniform float iScale;
 
 void main() {
-    uv = vec3((gl_VertexID << 1) & 2, gl_VertexID & 2, (float(iLayer) + .5) / iResolution.z);
-    gl_Position = vec4(uv.xy * 2. - 1., 0., 1.);
+    vec2 pos = vec2((gl_VertexID << 1) & 2, gl_VertexID & 2);
+
+    // Texture coordinates span the full image, of which the output is a region
+    uv = (iOffset + vec3(pos * vec2(iSize.xy), float(iLayer) + .5) * iScale) / vec3(iResolution);
+    gl_Position = vec4(pos * 2. - 1., 0., 1.);
 }
 
 // vim: ft=glsl.doxygen
@@ -11,7 +11,7 @@
 #define M_PI 3.14159265358979323846
 #define M_2PI (2. * M_PI)
 
-/// Output image resolution
+/// Resolution of the full image the output image is a region of
 layout(location = 0) uniform uvec3 iResolution;
 
 /**
@@ -213,6 +213,30 @@ uint hash(uvec2 x, uint seed) {
     return hash((seed << 16) | (0x0000ffffu & morton(x.x, x.y)));
 }
 
+/**
+ * @brief Hash a (coordinates, seed) pair
+ * @param x Coordinates
+ * @param seed Random seed
+ * @return Hashed value
+ *
+ * Note that the coordinates are enumerated in Morton order, thus they are
+ * limited to their low 10 bits.
+ */
+uint hash(uvec3 x, uint seed) { return hash(seed + morton(x.x, x.y, x.z)); }
+
+/**
+ * @brief Hash a (coordinates, seed) pair
+ * @param x Coordinates
+ * @param seed Random seed
+ * @return Hashed value
+ *
+ * Note that the coordinates are enumerated in Morton order two at a time, thus
+ * they are limited to their low 16 bits.
+ */
+uint hash(uvec4 x, uint seed) {
+    return hash(hash(seed + morton(x.x, x.y)) + morton(x.z, x.w));
+}
+
 /**
  * @brief Hash a (coordinates, seed) pair
  * @param x Coordinates
@@ -242,6 +266,38 @@ uvec2 hash2(uvec2 x, uint seed) {
     return uvec2(hash(2 * base), hash(2 * base + 1));
 }
 
+/**
+ * @brief Hash a (coordinates, seed) pair, return three values
+ * @param x Coordinates
+ * @param seed Random seed
+ * @return Hashed value
+ *
+ * Note that the coordinates are enumerated in Morton order, thus they are
+ * limited to their low 10 bits.
+ */
+uvec3 hash3(uvec3 x, uint seed) {
+    // Mix all coordinates into one seed value
+    uint base = hash(seed + morton(x.x, x.y, x.z));
+    // Hash all coordinates
+    return uvec3(hash(3 * base), hash(3 * base + 1), hash(3 * base + 2));
+}
+
+/**
+ * @brief Hash a (coordinates, seed) pair, return four values
+ * @param x Coordinates
+ * @param seed Random seed
+ * @return Hashed value
+ *
+ * See uint version for limitations.
+ */
+uvec4 hash4(uvec4 x, uint seed) {
+    // Mix all coordinates into one seed value
+    uint base = hash(x, seed);
+    // Hash all coordinates
+    return uvec4(hash(4 * base), hash(4 * base + 1), hash(4 * base + 2),
+                 hash(4 * base + 3));
+}
+
 /**
  * @brief Convert an unsigned int to a float in [0, 1]
  * @param u Unsigned int to convert
@@ -263,6 +319,22 @@ float tofloat(uint u) {
  */
 vec2 tofloat(uvec2 u) { return vec2(tofloat(u.x), tofloat(u.y)); }
 
+/**
+ * @brief Convert a vector of unsigned ints to floats
+ * @param u Vector to convert
+ * @return vec in [0, 1]
+ */
+vec3 tofloat(uvec3 u) { return vec3(tofloat(u.x), tofloat(u.y), tofloat(u.z)); }
+
+/**
+ * @brief Convert a vector of unsigned ints to floats
+ * @param u Vector to convert
+ * @return vec in [0, 1]
+ */
+vec4 tofloat(uvec4 u) {
+    return vec4(tofloat(u.x), tofloat(u.y), tofloat(u.z), tofloat(u.w));
+}
+
 
 /**
  * @brief Convert an unsigned int to a float in [-1, 1]
@@ -278,6 +350,20 @@ float tofloat11(uint u) { return 2. * tofloat(u) - 1.; }
  */
 vec2 tofloat11(uvec2 u) { return 2. * tofloat(u) - 1.; }
 
+/**
+ * @brief Convert a vector of unsigned ints to floats
+ * @param u Vector to convert
+ * @return vec in [-1, 1]
+ */
+vec3 tofloat11(uvec3 u) { return 2. * tofloat(u) - 1.; }
+
+/**
+ * @brief Convert a vector of unsigned ints to floats
+ * @param u Vector to convert
+ * @return vec in [-1, 1]
+ */
+vec4 tofloat11(uvec4 u) { return 2. * tofloat(u) - 1.; }
+
 /**
  * @brief Convert a float value from [-1, 1] to [0, 1]
  * @param x Value to convert
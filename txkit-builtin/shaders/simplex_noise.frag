@@ -2,13 +2,13 @@
 
 /**
  * @file simplex_noise.frag
- * @brief 2D simplex noise fragment shader
+ * @brief Simplex noise fragment shader
  * @author inigo quilez
  * @author Vincent Tavernier <vince.tavernier@gmail.com>
  * @see https://www.shadertoy.com/view/Msf3WH
  * @todo Support cyclic coordinates
  *
- * 2D Simplex noise, adapted for use in txkit.
+ * 2D, 3D and 4D simplex noise, adapted for use in txkit.
  */
 
 layout(location = 0) in vec3 uv;
@@ -21,6 +21,13 @@ layout(location = 0) out vec4 o_FragColor;
 // Noise statistics helpers
 #include "noise_stats.glsl"
 
+// Noises with derivatives
+#include "noise_derivatives.glsl"
+
+layout(location = 20) uniform int noise_output;
+layout(location = 21) uniform int noise_dimensions;
+layout(location = 22) uniform float time;
+
 vec2 noisehash(uvec2 p, uint seed) { return tofloat11(hash2(p, seed)); }
 
 float noise(LatticeNoiseSample s) {
@@ -38,8 +45,26 @@ float noise(LatticeNoiseSample s) {
 }
 
 void main() {
-    o_FragColor = vec4(
-        vec3(to01(noise(latticeSample(uv.xy, LATTICE_MODE_SIMPLEX_2D)))), 1.0);
+    LatticeNoiseSample s = latticeSample(uv.xy, LATTICE_MODE_SIMPLEX_2D);
+    vec3 p = uv * PARAM_SCALE;
+    vec4 n;
+
+    if (noise_dimensions == LATTICE_DIMENSIONS_3D) {
+        n = simplexNoised(p, PARAM_GLOBAL_SEED);
+    } else if (noise_dimensions == LATTICE_DIMENSIONS_4D) {
+        n = simplexNoised(vec4(p, time), PARAM_GLOBAL_SEED);
+    } else if (noise_output == LATTICE_OUTPUT_DERIVATIVES) {
+        n = vec4(simplexNoised(s), 0.0);
+    } else {
+        n = vec4(noise(s), 0.0, 0.0, 0.0);
+    }
+
+    if (noise_output == LATTICE_OUTPUT_DERIVATIVES) {
+        // Value and derivatives of the output with respect to lattice coordinates
+        o_FragColor = vec4(to01(n.x), .5 * n.yzw);
+    } else {
+        o_FragColor = vec4(vec3(to01(n.x)), 1.0);
+    }
 }
 
 // vim: ft=glsl.doxygen
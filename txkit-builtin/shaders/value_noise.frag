@@ -2,12 +2,12 @@
 
 /**
  * @file value_noise.frag
- * @brief 2D value noise fragment shader
+ * @brief Value noise fragment shader
  * @author inigo quilez
  * @author Vincent Tavernier <vince.tavernier@gmail.com>
  * @see https://www.shadertoy.com/view/lsf3WH
  *
- * 2D value noise, adapted for use in txkit.
+ * 2D, 3D and 4D value noise, adapted for use in txkit.
  */
 
 layout(location = 0) in vec3 uv;
@@ -17,9 +17,20 @@ layout(location = 0) out vec4 o_FragColor;
 #include "noise.glsl"
 #include "shared.glsl"
 
+/// Lattice period along (x, y, z, time), in cells
+layout(location = 23) uniform vec4 period;
+#define PARAM_PERIOD period
+
 // Noise statistics helpers
 #include "noise_stats.glsl"
 
+// Noises with derivatives
+#include "noise_derivatives.glsl"
+
+layout(location = 20) uniform int noise_output;
+layout(location = 21) uniform int noise_dimensions;
+layout(location = 22) uniform float time;
+
 float noisehash(uvec2 p, uint seed) { return tofloat(hash(p, seed)); }
 
 float noise(LatticeNoiseSample s) {
@@ -37,8 +48,26 @@ float noise(LatticeNoiseSample s) {
 }
 
 void main() {
-    o_FragColor =
-        vec4(vec3(noise(latticeSample(uv.xy, LATTICE_MODE_RECT_2D))), 1.0);
+    LatticeNoiseSample s = latticeSample(uv.xy, LATTICE_MODE_RECT_2D);
+    vec3 p = uv * PARAM_SCALE;
+    vec4 n;
+
+    if (noise_dimensions == LATTICE_DIMENSIONS_3D) {
+        n = valueNoised(p, PARAM_GLOBAL_SEED);
+    } else if (noise_dimensions == LATTICE_DIMENSIONS_4D) {
+        n = valueNoised(vec4(p, time), PARAM_GLOBAL_SEED);
+    } else if (noise_output == LATTICE_OUTPUT_DERIVATIVES) {
+        n = vec4(valueNoised(s), 0.0);
+    } else {
+        n = vec4(noise(s), 0.0, 0.0, 0.0);
+    }
+
+    if (noise_output == LATTICE_OUTPUT_DERIVATIVES) {
+        // Value and derivatives of the output with respect to lattice coordinates
+        o_FragColor = n;
+    } else {
+        o_FragColor = vec4(vec3(n.x), 1.0);
+    }
 }
 
 // vim: ft=glsl.doxygen
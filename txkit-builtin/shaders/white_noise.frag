@@ -15,7 +15,7 @@ layout(location = 0) out vec4 o_FragColor;
 #include "shared.glsl"
 
 void main() {
-    ivec3 px = ivec3(uv * vec3(iResolution));
+    ivec3 px = ivec3(floor(uv * vec3(iResolution)));
 
     uvec2 idx = shl64(
         mul64(add64(uvec2(0, px.x), uvec2(0, globalSeed)),
@@ -0,0 +1,113 @@
+#version 460 core
+
+/**
+ * @file worley_noise.frag
+ * @brief Worley noise fragment shader
+ * @author Vincent Tavernier <vince.tavernier@gmail.com>
+ * @see https://doi.org/10.1145/237170.237267
+ *
+ * Cellular noise: distances to the closest feature points, or identifier of
+ * the closest point.
+ */
+
+layout(location = 0) in vec3 uv;
+layout(location = 0) out vec4 o_FragColor;
+
+// Required built-ins
+#include "noise.glsl"
+#include "shared.glsl"
+
+// Noise statistics helpers
+#include "noise_stats.glsl"
+
+// Fast rng
+#include "lcg.glsl"
+
+// Point distributions
+#include "points.glsl"
+
+layout(location = 20) uniform int noise_lookahead;
+layout(location = 21) uniform int point_count;
+layout(location = 22) uniform int noise_output;
+layout(location = 23) uniform int distance_metric;
+layout(location = 24) uniform int noise_point_distribution;
+
+layout(location = 25) uniform float jitter_amount;
+layout(location = 26) uniform int jitter_max;
+
+#define WORLEY_OUTPUT_F1 0
+#define WORLEY_OUTPUT_F2 1
+#define WORLEY_OUTPUT_F2_MINUS_F1 2
+#define WORLEY_OUTPUT_CELL_ID 3
+
+#define WORLEY_METRIC_EUCLIDEAN 0
+#define WORLEY_METRIC_MANHATTAN 1
+#define WORLEY_METRIC_CHEBYSHEV 2
+
+float distanceMetric(vec2 d) {
+    if (distance_metric == WORLEY_METRIC_MANHATTAN) {
+        return abs(d.x) + abs(d.y);
+    } else if (distance_metric == WORLEY_METRIC_CHEBYSHEV) {
+        return max(abs(d.x), abs(d.y));
+    }
+
+    return length(d);
+}
+
+vec3 noise(LatticeNoiseSample s) {
+    // Distances to the closest and second closest points
+    vec2 f = vec2(1e20);
+    // Identifier of the closest point
+    uint id = 0;
+
+    ivec2 cell = s.cell;
+    for (cell.x = s.cell.x - noise_lookahead;
+         cell.x <= s.cell.x + noise_lookahead; ++cell.x) {
+        for (cell.y = s.cell.y - noise_lookahead;
+             cell.y <= s.cell.y + noise_lookahead; ++cell.y) {
+            // Make sure the noise tiles correctly
+            ivec2 looped_cell = latticeLoop(cell);
+
+            // Compute the position of the current point relative to the target
+            // cell
+            vec2 position =
+                s.position - vec2(cell.x - s.cell.x, cell.y - s.cell.y);
+
+            // Generate the points of the cell
+            uint cell_seed = hash(looped_cell, s.seed);
+            LCG rng = lcgSeed(cell_seed);
+            PointSet ps = pointsInit(rng, noise_point_distribution, point_count,
+                                     jitter_amount, jitter_max);
+
+            for (int i = 0; i < ps.count; ++i) {
+                float d = distanceMetric(position - pointsNext(rng, ps, i));
+
+                if (d < f.x) {
+                    f = vec2(d, f.x);
+                    id = hash(cell_seed + uint(i));
+                } else if (d < f.y) {
+                    f.y = d;
+                }
+            }
+        }
+    }
+
+    if (noise_output == WORLEY_OUTPUT_F1) {
+        return vec3(f.x);
+    } else if (noise_output == WORLEY_OUTPUT_F2) {
+        return vec3(f.y);
+    } else if (noise_output == WORLEY_OUTPUT_F2_MINUS_F1) {
+        return vec3(f.y - f.x);
+    } else if (noise_output == WORLEY_OUTPUT_CELL_ID) {
+        return vec3(tofloat(id));
+    }
+
+    // Invalid enum value
+    return vec3(1., 0., 1.);
+}
+
+void main() {
+    o_FragColor = vec4(noise(latticeSample(uv.xy, LATTICE_MODE_RECT_2D)), 1.);
+}
+
+// vim: ft=glsl.doxygen
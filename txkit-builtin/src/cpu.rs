@@ -0,0 +1,19 @@
+//! CPU ports of the shared GLSL code used by the built-in methods
+
+mod shared;
+pub use shared::*;
+
+mod noise_stats;
+pub use noise_stats::*;
+
+mod lcg;
+pub use lcg::*;
+
+mod points;
+pub use points::*;
+
+mod kernels;
+pub use kernels::*;
+
+mod noise_derivatives;
+pub use noise_derivatives::*;
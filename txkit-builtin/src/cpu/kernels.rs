@@ -0,0 +1,89 @@
+//! Port of `shaders/kernels.glsl`
+
+use cgmath::{Vector2, Vector4};
+use txkit_core::io::cpu::TextureSampler;
+
+use super::{Lcg, PointSet};
+
+/// No kernel weights
+pub const KERNEL_WEIGHTS_NONE: i32 = 0;
+/// Bernoulli kernel weights
+pub const KERNEL_WEIGHTS_BERNOULLI: i32 = 1;
+/// Uniform kernel weights
+pub const KERNEL_WEIGHTS_UNIFORM: i32 = 2;
+
+/// Sparse convolution kernel
+#[derive(Debug, Clone, Copy)]
+pub struct Kernel {
+    /// Position in cell coordinates
+    pub pos: Vector2<f32>,
+    /// Weight of the kernel
+    pub weight: f32,
+    /// Frequency vector, in oscillations per pixel
+    pub angle: Vector2<f32>,
+    /// Phase of the kernel
+    pub phase: f32,
+}
+
+/// Snapshot of the parameters shared by sparse convolution noises
+pub struct KernelParams {
+    pub scale: f32,
+    pub noise_lookahead: i32,
+    pub kernel_count: i32,
+    pub noise_weights: i32,
+    pub noise_point_distribution: i32,
+    pub noise_frequency: f32,
+    pub noise_angle: f32,
+    pub jitter_amount: f32,
+    pub jitter_max: i32,
+    pub frequency_orientation_field: TextureSampler,
+}
+
+impl KernelParams {
+    /// Initialize the set of kernels for a cell
+    pub fn init(&self, rng: &mut Lcg) -> PointSet {
+        PointSet::new(
+            rng,
+            self.noise_point_distribution,
+            self.kernel_count,
+            self.jitter_amount,
+            self.jitter_max,
+        )
+    }
+
+    /// Sample the frequency and orientation field for a cell
+    pub fn field(&self, pos: Vector2<f32>, cell: Vector2<i32>) -> Vector4<f32> {
+        self.frequency_orientation_field
+            .texture((pos + cgmath::vec2(cell.x as f32, cell.y as f32)) / self.scale)
+    }
+
+    /// Generate the next kernel of a cell
+    pub fn next(&self, rng: &mut Lcg, ps: &PointSet, i: i32, fo: Vector4<f32>) -> Kernel {
+        let pos = ps.next(rng, i);
+
+        // Compute weighting: always step the generator so we can get the same image with and
+        // without weights
+        let v = rng.next11();
+        let weight = if self.noise_weights == KERNEL_WEIGHTS_BERNOULLI {
+            if v < 0. {
+                -1.
+            } else {
+                1.
+            }
+        } else if self.noise_weights == KERNEL_WEIGHTS_UNIFORM {
+            v
+        } else {
+            1.
+        };
+
+        let angle = self.noise_angle + fo.y;
+        Kernel {
+            pos,
+            weight,
+            angle: self.noise_frequency / self.scale
+                * (1. + fo.x)
+                * cgmath::vec2(angle.cos(), angle.sin()),
+            phase: 0.,
+        }
+    }
+}
@@ -0,0 +1,42 @@
+//! Port of `shaders/lcg.glsl`
+
+/// Linear congruential pseudo-random number generator
+#[derive(Debug, Clone, Copy)]
+pub struct Lcg {
+    state: u32,
+}
+
+impl Lcg {
+    pub fn new(seed: u32) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next(&mut self) -> u32 {
+        let x = 1103515245u32.wrapping_mul(self.state).wrapping_add(12345) % (1 << 31);
+        self.state = x;
+        x >> 16
+    }
+
+    pub fn next01(&mut self) -> f32 {
+        self.next() as f32 / 32767.
+    }
+
+    pub fn next11(&mut self) -> f32 {
+        2. * self.next01() - 1.
+    }
+
+    pub fn poisson(&mut self, mean: f32) -> i32 {
+        // Note that the shader only ever uses Knuth's method, its Gaussian approximation branch
+        // for large means is never taken
+        let mut em = 0;
+
+        let g = (-mean).exp();
+        let mut t = self.next01();
+        while t > g {
+            em += 1;
+            t *= self.next01();
+        }
+
+        em
+    }
+}
@@ -0,0 +1,560 @@
+//! Port of `shaders/noise_derivatives.glsl`
+
+use cgmath::{ElementWise, InnerSpace, Vector2, Vector3, Vector4};
+
+use super::noise_stats::*;
+use super::shared::*;
+
+/// Simplex constant for 1/3
+pub const SIMPLEX3_F: f32 = 0.33333334;
+/// Simplex constant for 1/6
+pub const SIMPLEX3_G: f32 = 0.16666667;
+/// Simplex constant for (sqrt(5)-1)/4
+pub const SIMPLEX4_F: f32 = 0.309017;
+/// Simplex constant for (5-sqrt(5))/20
+pub const SIMPLEX4_G: f32 = 0.1381966;
+
+fn value_noise_hash(p: Vector2<u32>, seed: u32) -> f32 {
+    tofloat(hash_cell(p, seed))
+}
+
+fn gradient_noise_hash(p: Vector2<u32>, seed: u32) -> Vector2<f32> {
+    let x = 2. * M_PI * tofloat11(hash_cell(p, seed));
+    cgmath::vec2(x.cos(), x.sin())
+}
+
+fn simplex_noise_hash(p: Vector2<u32>, seed: u32) -> Vector2<f32> {
+    tofloat11_2(hash2(p, seed))
+}
+
+fn noise_hash(p: Vector3<u32>, seed: u32) -> Vector3<f32> {
+    tofloat11_3(hash3(p, seed))
+}
+
+fn noise_hash4(p: Vector4<u32>, seed: u32) -> Vector4<f32> {
+    tofloat11_4(hash4(p, seed))
+}
+
+/// GLSL `step` function
+fn step(edge: f32, x: f32) -> f32 {
+    if x < edge {
+        0.
+    } else {
+        1.
+    }
+}
+
+fn splat3(x: f32) -> Vector3<f32> {
+    cgmath::vec3(x, x, x)
+}
+
+fn splat4(x: f32) -> Vector4<f32> {
+    cgmath::vec4(x, x, x, x)
+}
+
+/// Wrapping addition of unsigned cell coordinates, as GLSL does
+fn add4(a: Vector4<u32>, b: Vector4<u32>) -> Vector4<u32> {
+    cgmath::vec4(
+        a.x.wrapping_add(b.x),
+        a.y.wrapping_add(b.y),
+        a.z.wrapping_add(b.z),
+        a.w.wrapping_add(b.w),
+    )
+}
+
+/// Integer cell and fractional position of a 4D point, as `uvec4(ivec4(floor(p)))` and `fract(p)`
+fn cell4(p: Vector4<f32>) -> (Vector4<u32>, Vector4<f32>) {
+    (
+        cgmath::vec4(
+            p.x.floor() as i32 as u32,
+            p.y.floor() as i32 as u32,
+            p.z.floor() as i32 as u32,
+            p.w.floor() as i32 as u32,
+        ),
+        cgmath::vec4(fract(p.x), fract(p.y), fract(p.z), fract(p.w)),
+    )
+}
+
+/// Interpolation weights of a 4D lattice corner along each axis, with their derivatives
+///
+/// # Parameters
+///
+/// * `k`: corner index, in [0, 16)
+/// * `u`: smoothed position in the cell
+/// * `du`: derivative of `u`
+fn corner4(
+    k: u32,
+    u: Vector4<f32>,
+    du: Vector4<f32>,
+) -> (Vector4<u32>, Vector4<f32>, Vector4<f32>) {
+    let o = cgmath::vec4(k & 1, (k >> 1) & 1, (k >> 2) & 1, (k >> 3) & 1);
+    let pick = |o: u32, x: f32, dx: f32| if o == 1 { (x, dx) } else { (1. - x, -dx) };
+
+    let (ax, dax) = pick(o.x, u.x, du.x);
+    let (ay, day) = pick(o.y, u.y, du.y);
+    let (az, daz) = pick(o.z, u.z, du.z);
+    let (aw, daw) = pick(o.w, u.w, du.w);
+
+    (
+        o,
+        cgmath::vec4(ax, ay, az, aw),
+        cgmath::vec4(dax, day, daz, daw),
+    )
+}
+
+/// 2D value noise with derivatives
+///
+/// Returns (value, d/dx, d/dy) for a rectangular lattice sample.
+pub fn value_noised(sampler: &LatticeSampler, s: LatticeNoiseSample) -> Vector3<f32> {
+    let i = to_uvec2(s.cell);
+    let f = s.position;
+
+    let u = cgmath::vec2(f.x * f.x * (3. - 2. * f.x), f.y * f.y * (3. - 2. * f.y));
+    let du = cgmath::vec2(6. * f.x * (1. - f.x), 6. * f.y * (1. - f.y));
+
+    let corner = |x: u32, y: u32| {
+        value_noise_hash(
+            sampler.lattice_loop(cgmath::vec2(i.x.wrapping_add(x), i.y.wrapping_add(y))),
+            s.seed,
+        )
+    };
+
+    let (a, b, c, d) = (corner(0, 0), corner(1, 0), corner(0, 1), corner(1, 1));
+
+    let value = mix(mix(a, b, u.x), mix(c, d, u.x), u.y);
+    let dv =
+        du.mul_element_wise(cgmath::vec2(b - a, c - a) + cgmath::vec2(u.y, u.x) * (a - b - c + d));
+
+    cgmath::vec3(value, dv.x, dv.y) * 0.7358206
+}
+
+/// 2D gradient noise with derivatives
+///
+/// Returns (value, d/dx, d/dy) for a rectangular lattice sample.
+pub fn gradient_noised(sampler: &LatticeSampler, s: LatticeNoiseSample) -> Vector3<f32> {
+    let i = to_uvec2(s.cell);
+    let f = s.position;
+
+    let u = cgmath::vec2(f.x * f.x * (3. - 2. * f.x), f.y * f.y * (3. - 2. * f.y));
+    let du = cgmath::vec2(6. * f.x * (1. - f.x), 6. * f.y * (1. - f.y));
+
+    let g = |x: u32, y: u32| {
+        gradient_noise_hash(
+            sampler.lattice_loop(cgmath::vec2(i.x.wrapping_add(x), i.y.wrapping_add(y))),
+            s.seed,
+        )
+    };
+
+    let (ga, gb, gc, gd) = (g(0, 0), g(1, 0), g(0, 1), g(1, 1));
+
+    let va = ga.dot(f - cgmath::vec2(0., 0.));
+    let vb = gb.dot(f - cgmath::vec2(1., 0.));
+    let vc = gc.dot(f - cgmath::vec2(0., 1.));
+    let vd = gd.dot(f - cgmath::vec2(1., 1.));
+
+    let value = mix(mix(va, vb, u.x), mix(vc, vd, u.x), u.y);
+    let d = ga
+        + (gb - ga) * u.x
+        + (gc - ga) * u.y
+        + (ga - gb - gc + gd) * (u.x * u.y)
+        + du.mul_element_wise(
+            cgmath::vec2(u.y, u.x) * (va - vb - vc + vd) + cgmath::vec2(vb, vc)
+                - cgmath::vec2(va, va),
+        );
+
+    cgmath::vec3(value, d.x, d.y) * 1.4931525
+}
+
+/// 2D simplex noise with derivatives
+///
+/// Returns (value, d/dx, d/dy) for a simplex lattice sample.
+pub fn simplex_noised(s: LatticeNoiseSample) -> Vector3<f32> {
+    let cell = to_uvec2(s.cell);
+    let offset = |x: u32, y: u32| cgmath::vec2(cell.x.wrapping_add(x), cell.y.wrapping_add(y));
+
+    let a = s.position;
+    let m = step(a.y, a.x);
+    let o = cgmath::vec2(m, 1.0 - m);
+    let b = a - o + cgmath::vec2(SIMPLEX_K2, SIMPLEX_K2);
+    let c = a - cgmath::vec2(1.0, 1.0) + cgmath::vec2(2.0 * SIMPLEX_K2, 2.0 * SIMPLEX_K2);
+
+    let ga = simplex_noise_hash(offset(0, 0), s.seed);
+    let gb = simplex_noise_hash(offset(o.x as u32, o.y as u32), s.seed);
+    let gc = simplex_noise_hash(offset(1, 1), s.seed);
+
+    let h = cgmath::vec3(
+        (0.5 - a.dot(a)).max(0.0),
+        (0.5 - b.dot(b)).max(0.0),
+        (0.5 - c.dot(c)).max(0.0),
+    );
+    let h3 = h.mul_element_wise(h).mul_element_wise(h);
+    let v = cgmath::vec3(a.dot(ga), b.dot(gb), c.dot(gc));
+
+    let value = h3
+        .mul_element_wise(h)
+        .mul_element_wise(v)
+        .dot(splat3(71.28259));
+    let d = (ga * h.x - a * (8. * v.x)) * h3.x
+        + (gb * h.y - b * (8. * v.y)) * h3.y
+        + (gc * h.z - c * (8. * v.z)) * h3.z;
+    let d = d * 71.28259;
+
+    cgmath::vec3(value, d.x, d.y)
+}
+
+/// 3D value noise with derivatives
+///
+/// Returns (value, d/dx, d/dy, d/dz).
+///
+/// # Parameters
+///
+/// * `sampler`: lattice sampler, for looping cells
+/// * `p`: position in lattice coordinates
+/// * `seed`: random seed
+pub fn value_noised3(sampler: &LatticeSampler, p: Vector3<f32>, seed: u32) -> Vector4<f32> {
+    let i = cgmath::vec3(
+        p.x.floor() as i32 as u32,
+        p.y.floor() as i32 as u32,
+        p.z.floor() as i32 as u32,
+    );
+    let w = cgmath::vec3(fract(p.x), fract(p.y), fract(p.z));
+
+    let u = w.mul_element_wise(w).mul_element_wise(splat3(3.) - w * 2.);
+    let du = (w * 6.).mul_element_wise(splat3(1.) - w);
+
+    let corner = |x: u32, y: u32, z: u32| {
+        tofloat(hash_cell3(
+            sampler.lattice_loop3(cgmath::vec3(
+                i.x.wrapping_add(x),
+                i.y.wrapping_add(y),
+                i.z.wrapping_add(z),
+            )),
+            seed,
+        ))
+    };
+
+    let a = corner(0, 0, 0);
+    let b = corner(1, 0, 0);
+    let c = corner(0, 1, 0);
+    let d = corner(1, 1, 0);
+    let e = corner(0, 0, 1);
+    let f = corner(1, 0, 1);
+    let g = corner(0, 1, 1);
+    let h = corner(1, 1, 1);
+
+    let k0 = b - a;
+    let k1 = c - a;
+    let k2 = e - a;
+    let k3 = a - b - c + d;
+    let k4 = a - c - e + g;
+    let k5 = a - b - e + f;
+    let k6 = -a + b + c - d + e - f - g + h;
+
+    let value = a
+        + u.x * k0
+        + u.y * k1
+        + u.z * k2
+        + u.x * u.y * k3
+        + u.y * u.z * k4
+        + u.z * u.x * k5
+        + u.x * u.y * u.z * k6;
+
+    let u_yzx = cgmath::vec3(u.y, u.z, u.x);
+    let u_zxy = cgmath::vec3(u.z, u.x, u.y);
+    let dv = du.mul_element_wise(
+        cgmath::vec3(k0, k1, k2)
+            + u_yzx.mul_element_wise(cgmath::vec3(k3, k4, k5))
+            + u_zxy.mul_element_wise(cgmath::vec3(k5, k3, k4))
+            + u_yzx.mul_element_wise(u_zxy) * k6,
+    );
+
+    cgmath::vec4(value, dv.x, dv.y, dv.z) * 0.7358206
+}
+
+/// 3D gradient noise with derivatives
+///
+/// Returns (value, d/dx, d/dy, d/dz).
+///
+/// # Parameters
+///
+/// * `sampler`: lattice sampler, for looping cells
+/// * `p`: position in lattice coordinates
+/// * `seed`: random seed
+pub fn gradient_noised3(sampler: &LatticeSampler, p: Vector3<f32>, seed: u32) -> Vector4<f32> {
+    let i = cgmath::vec3(
+        p.x.floor() as i32 as u32,
+        p.y.floor() as i32 as u32,
+        p.z.floor() as i32 as u32,
+    );
+    let w = cgmath::vec3(fract(p.x), fract(p.y), fract(p.z));
+
+    let u = w.mul_element_wise(w).mul_element_wise(splat3(3.) - w * 2.);
+    let du = (w * 6.).mul_element_wise(splat3(1.) - w);
+
+    let corner = |x: u32, y: u32, z: u32| {
+        let g = noise_hash(
+            sampler.lattice_loop3(cgmath::vec3(
+                i.x.wrapping_add(x),
+                i.y.wrapping_add(y),
+                i.z.wrapping_add(z),
+            )),
+            seed,
+        );
+        (g, g.dot(w - cgmath::vec3(x as f32, y as f32, z as f32)))
+    };
+
+    let (ga, va) = corner(0, 0, 0);
+    let (gb, vb) = corner(1, 0, 0);
+    let (gc, vc) = corner(0, 1, 0);
+    let (gd, vd) = corner(1, 1, 0);
+    let (ge, ve) = corner(0, 0, 1);
+    let (gf, vf) = corner(1, 0, 1);
+    let (gg, vg) = corner(0, 1, 1);
+    let (gh, vh) = corner(1, 1, 1);
+
+    let k0 = vb - va;
+    let k1 = vc - va;
+    let k2 = ve - va;
+    let k3 = va - vb - vc + vd;
+    let k4 = va - vc - ve + vg;
+    let k5 = va - vb - ve + vf;
+    let k6 = -va + vb + vc - vd + ve - vf - vg + vh;
+
+    let value = va
+        + u.x * k0
+        + u.y * k1
+        + u.z * k2
+        + u.x * u.y * k3
+        + u.y * u.z * k4
+        + u.z * u.x * k5
+        + u.x * u.y * u.z * k6;
+
+    let u_yzx = cgmath::vec3(u.y, u.z, u.x);
+    let u_zxy = cgmath::vec3(u.z, u.x, u.y);
+    let d = ga
+        + (gb - ga) * u.x
+        + (gc - ga) * u.y
+        + (ge - ga) * u.z
+        + (ga - gb - gc + gd) * (u.x * u.y)
+        + (ga - gc - ge + gg) * (u.y * u.z)
+        + (ga - gb - ge + gf) * (u.z * u.x)
+        + (-ga + gb + gc - gd + ge - gf - gg + gh) * (u.x * u.y * u.z)
+        + du.mul_element_wise(
+            cgmath::vec3(k0, k1, k2)
+                + u_yzx.mul_element_wise(cgmath::vec3(k3, k4, k5))
+                + u_zxy.mul_element_wise(cgmath::vec3(k5, k3, k4))
+                + u_yzx.mul_element_wise(u_zxy) * k6,
+        );
+
+    cgmath::vec4(value, d.x, d.y, d.z)
+}
+
+/// 3D simplex noise with derivatives
+///
+/// Returns (value, d/dx, d/dy, d/dz).
+///
+/// # Parameters
+///
+/// * `p`: position in lattice coordinates
+/// * `seed`: random seed
+pub fn simplex_noised3(p: Vector3<f32>, seed: u32) -> Vector4<f32> {
+    // Skew the position to find the simplex cell
+    let s = p + splat3(p.dot(splat3(SIMPLEX3_F)));
+    let s = cgmath::vec3(s.x.floor(), s.y.floor(), s.z.floor());
+    let x = p - s + splat3(s.dot(splat3(SIMPLEX3_G)));
+
+    // Find the simplex the position lies in. The last comparison is strict so that ties still
+    // describe an ordering of the coordinates.
+    let e = cgmath::vec3(step(x.y, x.x), step(x.z, x.y), 1. - step(x.z, x.x));
+    let e_zxy = cgmath::vec3(e.z, e.x, e.y);
+    let i1 = e.mul_element_wise(splat3(1.) - e_zxy);
+    let i2 = splat3(1.) - e_zxy.mul_element_wise(splat3(1.) - e);
+
+    let x0 = x;
+    let x1 = x - i1 + splat3(SIMPLEX3_G);
+    let x2 = x - i2 + splat3(2. * SIMPLEX3_G);
+    let x3 = x - splat3(1.) + splat3(3. * SIMPLEX3_G);
+
+    let cell = cgmath::vec3(s.x as i32 as u32, s.y as i32 as u32, s.z as i32 as u32);
+    let offset = |o: Vector3<f32>| {
+        cgmath::vec3(
+            cell.x.wrapping_add(o.x as u32),
+            cell.y.wrapping_add(o.y as u32),
+            cell.z.wrapping_add(o.z as u32),
+        )
+    };
+
+    let g0 = noise_hash(cell, seed);
+    let g1 = noise_hash(offset(i1), seed);
+    let g2 = noise_hash(offset(i2), seed);
+    let g3 = noise_hash(offset(splat3(1.)), seed);
+
+    let h = cgmath::vec4(
+        (0.5 - x0.dot(x0)).max(0.),
+        (0.5 - x1.dot(x1)).max(0.),
+        (0.5 - x2.dot(x2)).max(0.),
+        (0.5 - x3.dot(x3)).max(0.),
+    );
+    let h3 = h.mul_element_wise(h).mul_element_wise(h);
+    let v = cgmath::vec4(x0.dot(g0), x1.dot(g1), x2.dot(g2), x3.dot(g3));
+
+    let value = h3
+        .mul_element_wise(h)
+        .mul_element_wise(v)
+        .dot(cgmath::vec4(64., 64., 64., 64.));
+    let d = (g0 * h.x - x0 * (8. * v.x)) * h3.x
+        + (g1 * h.y - x1 * (8. * v.y)) * h3.y
+        + (g2 * h.z - x2 * (8. * v.z)) * h3.z
+        + (g3 * h.w - x3 * (8. * v.w)) * h3.w;
+    let d = d * 64.;
+
+    cgmath::vec4(value, d.x, d.y, d.z)
+}
+
+/// 4D value noise with derivatives
+///
+/// Returns (value, d/dx, d/dy, d/dz).
+///
+/// # Parameters
+///
+/// * `sampler`: lattice sampler, for looping cells
+/// * `p`: position in lattice coordinates
+/// * `seed`: random seed
+pub fn value_noised4(sampler: &LatticeSampler, p: Vector4<f32>, seed: u32) -> Vector4<f32> {
+    let (i, w) = cell4(p);
+
+    let u = w.mul_element_wise(w).mul_element_wise(splat4(3.) - w * 2.);
+    let du = (w * 6.).mul_element_wise(splat4(1.) - w);
+
+    let mut value = 0.;
+    let mut dv = splat3(0.);
+
+    for k in 0..16 {
+        // Corner offset, with its interpolation weight along each axis
+        let (o, a, da) = corner4(k, u, du);
+
+        let n = tofloat(hash_cell4(sampler.lattice_loop4(add4(i, o)), seed));
+
+        value += n * a.x * a.y * a.z * a.w;
+        dv += cgmath::vec3(da.x * a.y * a.z, a.x * da.y * a.z, a.x * a.y * da.z) * (n * a.w);
+    }
+
+    cgmath::vec4(value, dv.x, dv.y, dv.z) * 0.7358206
+}
+
+/// 4D gradient noise with derivatives
+///
+/// Returns (value, d/dx, d/dy, d/dz).
+///
+/// # Parameters
+///
+/// * `sampler`: lattice sampler, for looping cells
+/// * `p`: position in lattice coordinates
+/// * `seed`: random seed
+pub fn gradient_noised4(sampler: &LatticeSampler, p: Vector4<f32>, seed: u32) -> Vector4<f32> {
+    let (i, w) = cell4(p);
+
+    let u = w.mul_element_wise(w).mul_element_wise(splat4(3.) - w * 2.);
+    let du = (w * 6.).mul_element_wise(splat4(1.) - w);
+
+    let mut value = 0.;
+    let mut d = splat3(0.);
+
+    for k in 0..16 {
+        // Corner offset, with its interpolation weight along each axis
+        let (o, a, da) = corner4(k, u, du);
+
+        let g = noise_hash4(sampler.lattice_loop4(add4(i, o)), seed);
+        let v = g.dot(w - cgmath::vec4(o.x as f32, o.y as f32, o.z as f32, o.w as f32));
+        let weight = a.x * a.y * a.z * a.w;
+
+        value += v * weight;
+        d += g.truncate() * weight
+            + cgmath::vec3(da.x * a.y * a.z, a.x * da.y * a.z, a.x * a.y * da.z) * (v * a.w);
+    }
+
+    cgmath::vec4(value, d.x, d.y, d.z)
+}
+
+/// 4D simplex noise with derivatives
+///
+/// Returns (value, d/dx, d/dy, d/dz).
+///
+/// # Parameters
+///
+/// * `p`: position in lattice coordinates
+/// * `seed`: random seed
+pub fn simplex_noised4(p: Vector4<f32>, seed: u32) -> Vector4<f32> {
+    // Skew the position to find the simplex cell
+    let s = p + splat4(p.dot(splat4(SIMPLEX4_F)));
+    let s = cgmath::vec4(s.x.floor(), s.y.floor(), s.z.floor(), s.w.floor());
+    let x0 = p - s + splat4(s.dot(splat4(SIMPLEX4_G)));
+
+    // Rank the coordinates to find the simplex the position lies in
+    let is_x = cgmath::vec3(step(x0.y, x0.x), step(x0.z, x0.x), step(x0.w, x0.x));
+    let is_yz = cgmath::vec3(step(x0.z, x0.y), step(x0.w, x0.y), step(x0.w, x0.z));
+    let mut i0 = cgmath::vec4(
+        is_x.x + is_x.y + is_x.z,
+        1. - is_x.x,
+        1. - is_x.y,
+        1. - is_x.z,
+    );
+    i0.y += is_yz.x + is_yz.y;
+    i0.z += 1. - is_yz.x;
+    i0.w += 1. - is_yz.y;
+    i0.z += is_yz.z;
+    i0.w += 1. - is_yz.z;
+
+    let clamp01 = |v: Vector4<f32>| {
+        cgmath::vec4(
+            v.x.clamp(0., 1.),
+            v.y.clamp(0., 1.),
+            v.z.clamp(0., 1.),
+            v.w.clamp(0., 1.),
+        )
+    };
+
+    let i3 = clamp01(i0);
+    let i2 = clamp01(i0 - splat4(1.));
+    let i1 = clamp01(i0 - splat4(2.));
+
+    let x1 = x0 - i1 + splat4(SIMPLEX4_G);
+    let x2 = x0 - i2 + splat4(2. * SIMPLEX4_G);
+    let x3 = x0 - i3 + splat4(3. * SIMPLEX4_G);
+    let x4 = x0 - splat4(1.) + splat4(4. * SIMPLEX4_G);
+
+    let cell = cgmath::vec4(
+        s.x as i32 as u32,
+        s.y as i32 as u32,
+        s.z as i32 as u32,
+        s.w as i32 as u32,
+    );
+    let offset = |o: Vector4<f32>| {
+        add4(
+            cell,
+            cgmath::vec4(o.x as u32, o.y as u32, o.z as u32, o.w as u32),
+        )
+    };
+
+    let corners = [
+        (x0, noise_hash4(cell, seed)),
+        (x1, noise_hash4(offset(i1), seed)),
+        (x2, noise_hash4(offset(i2), seed)),
+        (x3, noise_hash4(offset(i3), seed)),
+        (x4, noise_hash4(offset(splat4(1.)), seed)),
+    ];
+
+    let mut value = 0.;
+    let mut d = splat4(0.);
+
+    for (x, g) in corners.iter() {
+        let h = (0.5 - x.dot(*x)).max(0.);
+        let h3 = h * h * h;
+        let v = x.dot(*g);
+
+        value += h3 * h * v;
+        d += (g * h - x * (8. * v)) * h3;
+    }
+
+    cgmath::vec4(value, d.x, d.y, d.z) * 62.
+}
@@ -0,0 +1,233 @@
+//! Port of `shaders/noise_stats.glsl`
+
+use cgmath::{Vector2, Vector3, Vector4};
+use txkit_core::image::ImageDim;
+
+use super::shared::*;
+
+/// Compute a regular procedural texture
+pub const STATS_MODE_NORMAL: i32 = 0;
+/// Compute a process sample: each pixel is seeded differently, at random offset
+pub const STATS_MODE_PROCESS: i32 = 1;
+/// Compute a local sample: each pixel is seeded differently, at a fixed offset
+pub const STATS_MODE_LOOKAT: i32 = 2;
+
+/// Sample a rectangular lattice
+pub const LATTICE_MODE_RECT_2D: i32 = 0;
+/// Sample a simplex lattice
+pub const LATTICE_MODE_SIMPLEX_2D: i32 = 1;
+
+/// Sample in a 2D lattice
+#[derive(Debug, Clone, Copy)]
+pub struct LatticeNoiseSample {
+    /// Position in the current cell (in [0, 1])
+    pub position: Vector2<f32>,
+    /// Cell number
+    pub cell: Vector2<i32>,
+    /// Seed for the current sample
+    pub seed: u32,
+}
+
+/// Simplex constant for (sqrt(3)-1)/2
+pub const SIMPLEX_K1: f32 = 0.36602542;
+/// Simplex constant for (3-sqrt(3))/6
+pub const SIMPLEX_K2: f32 = 0.21132487;
+
+/// Values of the uniforms the lattice sampling functions depend on
+#[derive(Debug, Clone, Copy)]
+pub struct LatticeSampler {
+    /// Output image resolution
+    pub resolution: Vector3<u32>,
+    /// Pseudo-random seed
+    pub global_seed: u32,
+    /// Scale of the noise: how many lattice cells are visible in the current viewport
+    pub scale: f32,
+    /// Current statistics computation mode
+    pub stats_mode: i32,
+    /// Location for local sampling
+    pub stats_look_at: Vector2<f32>,
+    /// Explicit period of the lattice along each axis, in cells (0 to loop at the scale)
+    pub period: Vector4<f32>,
+}
+
+impl LatticeSampler {
+    pub fn new(
+        dim: ImageDim,
+        global_seed: u32,
+        scale: f32,
+        stats_mode: i32,
+        stats_look_at: Vector2<f32>,
+    ) -> Self {
+        Self {
+            resolution: cgmath::vec3(dim.width as u32, dim.height as u32, dim.depth as u32),
+            global_seed,
+            scale,
+            stats_mode,
+            stats_look_at,
+            period: cgmath::vec4(0., 0., 0., 0.),
+        }
+    }
+
+    /// Set the explicit period of the lattice along each axis
+    ///
+    /// # Parameters
+    ///
+    /// * `period`: period in cells, or 0 to loop at the scale
+    pub fn with_period(self, period: Vector4<f32>) -> Self {
+        Self { period, ..self }
+    }
+
+    /// Sample a 2D lattice
+    ///
+    /// # Parameters
+    ///
+    /// * `position`: input position in the viewport
+    /// * `mode`: sampling mode, one of the `LATTICE_MODE_*` constants
+    pub fn sample(&self, mut position: Vector2<f32>, mode: i32) -> LatticeNoiseSample {
+        // Regular sampling mode inside the lattice
+        let mut seed = self.global_seed;
+
+        if self.stats_mode == STATS_MODE_NORMAL {
+            // Regular sampling mode is the default
+            position *= self.scale;
+        } else {
+            // For process and look-at mode: each pixel being evaluated is seeded differently
+            let px = cgmath::vec2(
+                (position.x * self.resolution.x as f32) as u32,
+                (position.y * self.resolution.y as f32) as u32,
+            );
+
+            // Generate one seed per pixel, mix it with the base seed, then hash it
+            seed = hash(morton(morton(px.x, px.y), seed));
+
+            if self.stats_mode == STATS_MODE_PROCESS {
+                // In process mode, evaluate at random positions for each pixel
+                position = tofloat2(hash2(px, seed.wrapping_mul(32165431))) * self.scale;
+            } else if self.stats_mode == STATS_MODE_LOOKAT {
+                // Look-at mode: all pixels describe the same position in a cell, but are seeded
+                // differently
+                position = self.stats_look_at;
+            }
+        }
+
+        match mode {
+            LATTICE_MODE_RECT_2D => LatticeNoiseSample {
+                position: cgmath::vec2(fract(position.x), fract(position.y)),
+                cell: cgmath::vec2(position.x.floor() as i32, position.y.floor() as i32),
+                seed,
+            },
+            LATTICE_MODE_SIMPLEX_2D => {
+                let cell = cgmath::vec2(
+                    (position.x + (position.x + position.y) * SIMPLEX_K1).floor() as i32,
+                    (position.y + (position.x + position.y) * SIMPLEX_K1).floor() as i32,
+                );
+
+                let k = cell.x.wrapping_add(cell.y) as f32 * SIMPLEX_K2;
+
+                LatticeNoiseSample {
+                    position: cgmath::vec2(
+                        position.x - cell.x as f32 + k,
+                        position.y - cell.y as f32 + k,
+                    ),
+                    cell,
+                    seed,
+                }
+            }
+            _ => unreachable!("unknown lattice mode"),
+        }
+    }
+
+    /// Get the period of the lattice along each axis
+    ///
+    /// Returns the period in cells along each axis, or 0 for axes which do not loop. Axes without
+    /// an explicit period loop at the scale, so that the lattice loops around the texture borders,
+    /// unless a statistics mode is active.
+    pub fn lattice_period(&self) -> Vector4<u32> {
+        let period = cgmath::vec4(
+            self.period.x as u32,
+            self.period.y as u32,
+            self.period.z as u32,
+            self.period.w as u32,
+        );
+
+        if self.stats_mode == STATS_MODE_NORMAL {
+            // Note that GLSL leaves the result undefined for scales below 1
+            let s = (self.scale as i32).max(1) as u32;
+            let or_scale = |p: u32| if p > 0 { p } else { s };
+            return cgmath::vec4(
+                or_scale(period.x),
+                or_scale(period.y),
+                or_scale(period.z),
+                or_scale(period.w),
+            );
+        }
+
+        period
+    }
+
+    /// Ensure 2D lattice cells loop around the lattice period
+    ///
+    /// # Parameters
+    ///
+    /// * `p`: cell coordinates to loop
+    pub fn lattice_loop_signed(&self, p: Vector2<i32>) -> Vector2<i32> {
+        let period = self.lattice_period();
+        cgmath::vec2(loop_signed(p.x, period.x), loop_signed(p.y, period.y))
+    }
+
+    /// Ensure 2D lattice cells loop around the lattice period
+    ///
+    /// # Parameters
+    ///
+    /// * `p`: cell coordinates to loop
+    pub fn lattice_loop(&self, p: Vector2<u32>) -> Vector2<u32> {
+        let period = self.lattice_period();
+        cgmath::vec2(loop_unsigned(p.x, period.x), loop_unsigned(p.y, period.y))
+    }
+
+    /// Ensure 3D lattice cells loop around the lattice period
+    ///
+    /// # Parameters
+    ///
+    /// * `p`: cell coordinates to loop
+    pub fn lattice_loop3(&self, p: Vector3<u32>) -> Vector3<u32> {
+        let period = self.lattice_period();
+        cgmath::vec3(
+            loop_unsigned(p.x, period.x),
+            loop_unsigned(p.y, period.y),
+            loop_unsigned(p.z, period.z),
+        )
+    }
+
+    /// Ensure 4D lattice cells loop around the lattice period
+    ///
+    /// # Parameters
+    ///
+    /// * `p`: cell coordinates to loop
+    pub fn lattice_loop4(&self, p: Vector4<u32>) -> Vector4<u32> {
+        let period = self.lattice_period();
+        cgmath::vec4(
+            loop_unsigned(p.x, period.x),
+            loop_unsigned(p.y, period.y),
+            loop_unsigned(p.z, period.z),
+            loop_unsigned(p.w, period.w),
+        )
+    }
+}
+
+/// Loop a cell coordinate over a period, or leave it unchanged if the period is 0
+fn loop_signed(p: i32, period: u32) -> i32 {
+    if period > 0 {
+        p.rem_euclid(period as i32)
+    } else {
+        p
+    }
+}
+
+/// Loop a cell coordinate over a period, or leave it unchanged if the period is 0
+///
+/// Unsigned cell coordinates are wrapped signed ones, so cells left of the origin loop like
+/// [`loop_signed`] cells.
+fn loop_unsigned(p: u32, period: u32) -> u32 {
+    loop_signed(p as i32, period) as u32
+}
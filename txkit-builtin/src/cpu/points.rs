@@ -0,0 +1,107 @@
+//! Port of `shaders/points.glsl`
+
+use cgmath::Vector2;
+
+use super::Lcg;
+
+/// Constant number of points per cell
+pub const POINTS_STRAT_POISSON: i32 = 0;
+/// Poisson number of points per cell
+pub const POINTS_POISSON: i32 = 1;
+/// Rectangular jittered grid
+pub const POINTS_RECT_JITTERED: i32 = 2;
+/// Hexagonal jittered grid
+pub const POINTS_HEX_JITTERED: i32 = 3;
+
+/// Points to generate in a cell
+#[derive(Debug, Clone, Copy)]
+pub struct PointSet {
+    /// Point distribution
+    pub distribution: i32,
+    /// Number of points in the cell
+    pub count: i32,
+    /// Number of subcells for jittered distributions
+    pub total: i32,
+    /// Subcell grid size for jittered distributions
+    pub grid: Vector2<i32>,
+    /// Jittering amount, 0 = no random, 1 = full subcell random
+    pub jitter_amount: f32,
+}
+
+impl PointSet {
+    /// Initialize the set of points for a cell
+    ///
+    /// `rng` must be seeded for the current cell, and then used to generate the points with
+    /// [`PointSet::next`].
+    pub fn new(
+        rng: &mut Lcg,
+        distribution: i32,
+        count: i32,
+        jitter_amount: f32,
+        jitter_max: i32,
+    ) -> Self {
+        let mut ps = Self {
+            distribution,
+            count,
+            total: 0,
+            grid: cgmath::vec2(0, 0),
+            jitter_amount,
+        };
+
+        if distribution == POINTS_POISSON {
+            ps.count = rng.poisson(count as f32);
+        } else if ps.jittered() {
+            ps.grid.x = (count as f32).sqrt() as i32;
+            ps.grid.x = if jitter_max == 0 {
+                ps.grid.x
+            } else {
+                jitter_max.min(ps.grid.x)
+            };
+            ps.grid.y = if jitter_max == 0 {
+                count.checked_div(ps.grid.x).unwrap_or(0)
+            } else {
+                jitter_max.min(count.checked_div(ps.grid.x).unwrap_or(0))
+            };
+            ps.total = ps.grid.x * ps.grid.y;
+            ps.count = count.checked_div(ps.total).unwrap_or(0) * ps.total;
+        }
+
+        ps
+    }
+
+    fn jittered(&self) -> bool {
+        self.distribution == POINTS_RECT_JITTERED || self.distribution == POINTS_HEX_JITTERED
+    }
+
+    /// Generate the `i`-th point of the cell, in cell coordinates
+    pub fn next(&self, rng: &mut Lcg, i: i32) -> Vector2<f32> {
+        let x = rng.next01();
+        let y = rng.next01();
+        let mut pos = cgmath::vec2(
+            0.5 + (x - 0.5) * self.jitter_amount,
+            0.5 + (y - 0.5) * self.jitter_amount,
+        );
+
+        if self.jittered() {
+            let col = (i % self.total) % self.grid.x;
+            let row = (i % self.total) / self.grid.x;
+
+            if self.distribution == POINTS_HEX_JITTERED {
+                // Triangle transform
+                pos = cgmath::vec2(0.25 * (pos.x - pos.y), 0.5 * (pos.x + pos.y).abs());
+
+                // Offset one every two triangles
+                if (self.count + row) % 2 == 0 {
+                    pos = cgmath::vec2(pos.x + 0.5, 1. - pos.y);
+                }
+            }
+
+            pos = cgmath::vec2(
+                (pos.x + col as f32) / self.grid.x as f32,
+                (pos.y + row as f32) / self.grid.y as f32,
+            );
+        }
+
+        pos
+    }
+}
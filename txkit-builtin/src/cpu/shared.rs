@@ -0,0 +1,279 @@
+//! Port of `shaders/shared.glsl`
+//!
+//! All integer arithmetic wraps like GLSL `uint` arithmetic, and all floating-point computations
+//! are performed in single precision so the CPU methods match their GPU counterparts as closely
+//! as possible.
+
+use cgmath::{Vector2, Vector3, Vector4};
+use txkit_core::image::{Fragment, ImageDim};
+
+/// The pi mathematical constant
+pub const M_PI: f32 = std::f32::consts::PI;
+/// Two times the pi mathematical constant
+pub const M_2PI: f32 = 2. * M_PI;
+
+/// Compute the interpolated `uv` value `quad.vert` yields for the given fragment
+///
+/// # Parameters
+///
+/// * `frag`: fragment being computed
+/// * `dim`: dimensions of the full image
+pub fn frag_uv(frag: Fragment, dim: ImageDim) -> Vector3<f32> {
+    let [x, y, z] = frag.position;
+    cgmath::vec3(
+        x / dim.width as f32,
+        y / dim.height as f32,
+        z / dim.depth as f32,
+    )
+}
+
+/// Low-bias 32 bit hash function
+///
+/// See <https://github.com/skeeto/hash-prospector#three-round-functions>
+pub fn hash(x: u32) -> u32 {
+    let mut x = x.wrapping_add(1);
+    x = ((x >> 17) ^ x).wrapping_mul(0xed5ad4bb);
+    x = ((x >> 11) ^ x).wrapping_mul(0xac4c1b51);
+    x = ((x >> 15) ^ x).wrapping_mul(0x31848bab);
+    (x >> 14) ^ x
+}
+
+/// 64-bit addition
+///
+/// 64-bit values are represented as (high word, low word) pairs.
+pub fn add64(mut a: Vector2<u32>, b: Vector2<u32>) -> Vector2<u32> {
+    // Low word
+    let naw = a.y.wrapping_add(b.y);
+    let cc = (naw < a.y) as u32;
+    a.y = naw;
+
+    // High word
+    a.x = a.x.wrapping_add(b.x).wrapping_add(cc);
+
+    a
+}
+
+/// 64-bit left shift
+pub fn shl64(mut a: Vector2<u32>, b: u32) -> Vector2<u32> {
+    a.x = (a.x << b) | (a.y >> (32 - b));
+    a.y <<= b;
+    a
+}
+
+/// 64-bit right shift
+pub fn shr64(mut a: Vector2<u32>, b: u32) -> Vector2<u32> {
+    a.y = (a.y >> b) | (a.x << (32 - b));
+    a.x >>= b;
+    a
+}
+
+/// 64-bit multiplication and accumulation (d + a * b)
+///
+/// This reproduces the shader version exactly, including the fact that the 32-bit partial
+/// products are truncated.
+pub fn mul64(mut d: Vector4<u32>, a: Vector2<u32>, b: Vector2<u32>) -> Vector4<u32> {
+    let lo = |x: u32| cgmath::vec2(0, x);
+
+    {
+        let f = b.y;
+
+        let z = add64(add64(lo(d.w), lo(f.wrapping_mul(a.y))), lo(0));
+        let cc = lo(z.x);
+        d.w = z.y;
+
+        let z = add64(add64(lo(d.z), lo(f.wrapping_mul(a.x))), cc);
+        let cc = lo(z.x);
+        d.z = z.y;
+
+        d.y = cc.y;
+    }
+
+    {
+        let f = b.x;
+
+        let z = add64(add64(lo(d.z), lo(f.wrapping_mul(a.y))), lo(0));
+        let cc = lo(z.x);
+        d.z = z.y;
+
+        let z = add64(add64(lo(d.y), lo(f.wrapping_mul(a.x))), cc);
+        let cc = lo(z.x);
+        d.y = z.y;
+
+        d.x = cc.y;
+    }
+
+    d
+}
+
+/// 64-bit hash function
+///
+/// See <http://xoshiro.di.unimi.it/splitmix64.c>
+pub fn hash64(x: Vector2<u32>) -> Vector2<u32> {
+    let xor = |a: Vector2<u32>, b: Vector2<u32>| cgmath::vec2(a.x ^ b.x, a.y ^ b.y);
+    let zw = |v: Vector4<u32>| cgmath::vec2(v.z, v.w);
+
+    let x = add64(x, cgmath::vec2(0x9e3779b9, 0x7f4a7c15));
+    let x = zw(mul64(
+        cgmath::vec4(0, 0, 0, 0),
+        xor(x, shr64(x, 30)),
+        cgmath::vec2(0xbf58476d, 0x1ce4e5b9),
+    ));
+    let x = zw(mul64(
+        cgmath::vec4(0, 0, 0, 0),
+        xor(x, shr64(x, 27)),
+        cgmath::vec2(0x94d049bb, 0x133111eb),
+    ));
+    xor(x, shr64(x, 31))
+}
+
+/// Insert a 0 bit after each of the 16 low bits of x
+pub fn morton_part1_by1(x: u32) -> u32 {
+    let mut x = x & 0x0000ffff;
+    x = (x ^ (x << 8)) & 0x00ff00ff;
+    x = (x ^ (x << 4)) & 0x0f0f0f0f;
+    x = (x ^ (x << 2)) & 0x33333333;
+    (x ^ (x << 1)) & 0x55555555
+}
+
+/// Insert two 0 bits after each of the 10 low bits of x
+pub fn morton_part1_by2(x: u32) -> u32 {
+    let mut x = x & 0x000003ff;
+    x = (x ^ (x << 16)) & 0xff0000ff;
+    x = (x ^ (x << 8)) & 0x0300f00f;
+    x = (x ^ (x << 4)) & 0x030c30c3;
+    (x ^ (x << 2)) & 0x09249249
+}
+
+/// Encode two coordinates in Morton order
+pub fn morton(x: u32, y: u32) -> u32 {
+    (morton_part1_by1(y) << 1) | morton_part1_by1(x)
+}
+
+/// Encode three coordinates in Morton order
+pub fn morton3(x: u32, y: u32, z: u32) -> u32 {
+    (morton_part1_by2(z) << 2) | (morton_part1_by2(y) << 1) | morton_part1_by2(x)
+}
+
+/// Hash a (coordinates, seed) pair
+///
+/// Equivalent to the `hash(uvec2, uint)` overload: cell coordinates are limited to their low 8
+/// bits, and the seed to its low 16 bits.
+pub fn hash_cell(x: Vector2<u32>, seed: u32) -> u32 {
+    hash((seed << 16) | (0x0000ffff & morton(x.x, x.y)))
+}
+
+/// Hash a (coordinates, seed) pair
+///
+/// Equivalent to the `hash(uvec3, uint)` overload: cell coordinates are limited to their low 10
+/// bits.
+pub fn hash_cell3(x: Vector3<u32>, seed: u32) -> u32 {
+    hash(seed.wrapping_add(morton3(x.x, x.y, x.z)))
+}
+
+/// Hash a (coordinates, seed) pair
+///
+/// Equivalent to the `hash(uvec4, uint)` overload: cell coordinates are limited to their low 16
+/// bits.
+pub fn hash_cell4(x: Vector4<u32>, seed: u32) -> u32 {
+    hash(hash(seed.wrapping_add(morton(x.x, x.y))).wrapping_add(morton(x.z, x.w)))
+}
+
+/// Hash a (coordinates, seed) pair, return two values
+pub fn hash2(x: Vector2<u32>, seed: u32) -> Vector2<u32> {
+    // Mix both coordinates into one seed value
+    let base = hash(seed.wrapping_add(morton(x.x, x.y)));
+    // Hash both coordinates
+    cgmath::vec2(
+        hash(base.wrapping_mul(2)),
+        hash(base.wrapping_mul(2).wrapping_add(1)),
+    )
+}
+
+/// Hash a (coordinates, seed) pair, return three values
+///
+/// Cell coordinates are limited to their low 10 bits.
+pub fn hash3(x: Vector3<u32>, seed: u32) -> Vector3<u32> {
+    // Mix all coordinates into one seed value
+    let base = hash(seed.wrapping_add(morton3(x.x, x.y, x.z)));
+    // Hash all coordinates
+    cgmath::vec3(
+        hash(base.wrapping_mul(3)),
+        hash(base.wrapping_mul(3).wrapping_add(1)),
+        hash(base.wrapping_mul(3).wrapping_add(2)),
+    )
+}
+
+/// Hash a (coordinates, seed) pair, return four values
+///
+/// Cell coordinates are limited to their low 16 bits.
+pub fn hash4(x: Vector4<u32>, seed: u32) -> Vector4<u32> {
+    // Mix all coordinates into one seed value
+    let base = hash_cell4(x, seed);
+    // Hash all coordinates
+    cgmath::vec4(
+        hash(base.wrapping_mul(4)),
+        hash(base.wrapping_mul(4).wrapping_add(1)),
+        hash(base.wrapping_mul(4).wrapping_add(2)),
+        hash(base.wrapping_mul(4).wrapping_add(3)),
+    )
+}
+
+/// Convert an unsigned int to a float in [0, 1]
+pub fn tofloat(u: u32) -> f32 {
+    f32::from_bits(0x7f << 23 | u >> 9) - 1.
+}
+
+/// Convert a vector of unsigned ints to floats in [0, 1]
+pub fn tofloat2(u: Vector2<u32>) -> Vector2<f32> {
+    cgmath::vec2(tofloat(u.x), tofloat(u.y))
+}
+
+/// Convert an unsigned int to a float in [-1, 1]
+pub fn tofloat11(u: u32) -> f32 {
+    2. * tofloat(u) - 1.
+}
+
+/// Convert a vector of unsigned ints to floats in [-1, 1]
+pub fn tofloat11_2(u: Vector2<u32>) -> Vector2<f32> {
+    cgmath::vec2(tofloat11(u.x), tofloat11(u.y))
+}
+
+/// Convert a vector of unsigned ints to floats in [-1, 1]
+pub fn tofloat11_3(u: Vector3<u32>) -> Vector3<f32> {
+    cgmath::vec3(tofloat11(u.x), tofloat11(u.y), tofloat11(u.z))
+}
+
+/// Convert a vector of unsigned ints to floats in [-1, 1]
+pub fn tofloat11_4(u: Vector4<u32>) -> Vector4<f32> {
+    cgmath::vec4(
+        tofloat11(u.x),
+        tofloat11(u.y),
+        tofloat11(u.z),
+        tofloat11(u.w),
+    )
+}
+
+/// Convert a float value from [-1, 1] to [0, 1]
+pub fn to01(x: f32) -> f32 {
+    0.5 * x + 0.5
+}
+
+/// GLSL `fract` function
+pub fn fract(x: f32) -> f32 {
+    x - x.floor()
+}
+
+/// GLSL `mod` function
+pub fn modulo(x: f32, y: f32) -> f32 {
+    x - y * (x / y).floor()
+}
+
+/// GLSL `mix` function
+pub fn mix(x: f32, y: f32, a: f32) -> f32 {
+    x * (1. - a) + y * a
+}
+
+/// Convert signed cell coordinates to unsigned ones, as GLSL `uvec2(ivec2)` does
+pub fn to_uvec2(p: Vector2<i32>) -> Vector2<u32> {
+    cgmath::vec2(p.x as u32, p.y as u32)
+}
@@ -1 +1,7 @@
+mod cpu;
 pub mod methods;
+
+#[cfg(feature = "serde")]
+mod serde_io;
+#[cfg(feature = "serde")]
+mod serde_vector;
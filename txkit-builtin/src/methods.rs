@@ -1,9 +1,31 @@
+//! Built-in texturing methods
+//!
+//! With the `serde` feature, the parameter structs of these methods implement `Serialize` and
+//! `Deserialize`, so presets can be saved and reloaded. Vectors are written as arrays of
+//! components, and missing fields take their default value. Texture inputs are skipped, since
+//! they refer to images that only exist at runtime: they have to be bound again after loading a
+//! preset.
+
 mod debug;
 pub use debug::*;
 
 mod white_noise;
 pub use white_noise::*;
 
+mod scratch;
+
+mod fractal;
+pub use fractal::*;
+
+mod blue_noise;
+pub use blue_noise::*;
+
+mod domain_warp;
+pub use domain_warp::*;
+
+mod lattice;
+pub use lattice::*;
+
 mod value_noise;
 pub use value_noise::*;
 
@@ -13,9 +35,18 @@ pub use gradient_noise::*;
 mod simplex_noise;
 pub use simplex_noise::*;
 
+mod curl_noise;
+pub use curl_noise::*;
+
 mod phasor_noise;
 pub use phasor_noise::*;
 
+mod gabor_noise;
+pub use gabor_noise::*;
+
+mod worley_noise;
+pub use worley_noise::*;
+
 use txkit_core::method::MethodRegistry;
 pub fn new_registry() -> MethodRegistry {
     let mut registry = MethodRegistry::new();
@@ -27,6 +58,31 @@ pub fn new_registry() -> MethodRegistry {
         Box::new(|| Box::new(GradientNoise::new())),
     );
     registry.register("simplex_noise", Box::new(|| Box::new(SimplexNoise::new())));
+    registry.register("curl_noise", Box::new(|| Box::new(CurlNoise::new())));
     registry.register("phasor_noise", Box::new(|| Box::new(PhasorNoise::new())));
+    registry.register("gabor_noise", Box::new(|| Box::new(GaborNoise::new())));
+    registry.register("blue_noise", Box::new(|| Box::new(BlueNoise::new())));
+    registry.register("worley_noise", Box::new(|| Box::new(WorleyNoise::new())));
+    registry.register(
+        "fractal_value_noise",
+        Box::new(|| Box::new(Fractal::new(Box::new(ValueNoise::new())))),
+    );
+    registry.register(
+        "fractal_gradient_noise",
+        Box::new(|| Box::new(Fractal::new(Box::new(GradientNoise::new())))),
+    );
+    registry.register(
+        "fractal_simplex_noise",
+        Box::new(|| Box::new(Fractal::new(Box::new(SimplexNoise::new())))),
+    );
+    registry.register(
+        "domain_warp",
+        Box::new(|| {
+            Box::new(DomainWarp::with_methods(
+                Box::new(GradientNoise::new()),
+                Box::new(ValueNoise::new()),
+            ))
+        }),
+    );
     registry
 }
@@ -0,0 +1,339 @@
+use std::sync::Arc;
+
+use txkit_core::image::{Fragment, ImageDataType, ImageDim, IntoElementType};
+use txkit_core::{Error, Result};
+use txkit_impl::{Method, ParamsFor};
+
+use crate::cpu::hash;
+
+#[derive(Clone, Copy, PartialEq, ParamsFor)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+#[repr(C)]
+pub struct BlueNoiseParams {
+    /// pseudo-random seed
+    pub global_seed: u32,
+    /// standard deviation of the energy filter, in pixels
+    pub sigma: f32,
+}
+
+impl Default for BlueNoiseParams {
+    fn default() -> Self {
+        Self {
+            global_seed: 0,
+            sigma: 1.5,
+        }
+    }
+}
+
+/// Blue noise threshold map, computed using the void-and-cluster method
+///
+/// Every pixel of the output holds its rank in the void-and-cluster ordering, normalized to
+/// [0, 1), so that thresholding the map at `t` selects a blue noise pattern of density `t`. The
+/// map wraps around the edges of the image (and its layers, for 3D images), and all channels hold
+/// the same values.
+///
+/// See Ulichney, R. A. (1993). Void-and-cluster method for dither array generation.
+#[derive(Default, Method)]
+#[txkit(
+    cpu(method(
+        iter = "Self::compute_idx",
+        init = "Self::init_cpu",
+        params = "BlueNoiseParams"
+    )),
+    method()
+)]
+pub struct BlueNoise {
+    /// Last computed threshold map
+    ///
+    /// Regions of the same image (e.g. tiles of a large render) index the same full threshold
+    /// map, which would otherwise be computed again for every region.
+    ranks: Option<(RanksKey, Arc<Vec<u32>>)>,
+}
+
+/// Rank of a pixel in a threshold map of `count` pixels
+#[derive(Clone, Copy)]
+struct Rank {
+    rank: u32,
+    count: u32,
+}
+
+impl IntoElementType for Rank {
+    const INTEGER: bool = true;
+
+    fn into_element_type() -> ImageDataType {
+        ImageDataType::Float32
+    }
+
+    fn into_u8(&self) -> u8 {
+        // Spread the ranks evenly over all 256 levels
+        ((self.rank as u64 * 256) / self.count as u64) as u8
+    }
+
+    fn into_f32(&self) -> f32 {
+        (self.rank as f32 + 0.5) / self.count as f32
+    }
+
+    fn into_u16(&self) -> u16 {
+        ((self.rank as u64 * 65536) / self.count as u64) as u16
+    }
+
+    fn into_i32(&self) -> i32 {
+        self.rank as i32
+    }
+}
+
+/// Binary pattern with the filtered energy of its set pixels
+struct Pattern {
+    dim: [usize; 3],
+    radius: [usize; 3],
+    kernel: Vec<f32>,
+    bits: Vec<bool>,
+    energy: Vec<f32>,
+}
+
+impl Pattern {
+    fn new(dim: ImageDim, sigma: f32) -> Self {
+        let dim = [dim.width, dim.height, dim.depth];
+
+        // Truncate the Gaussian at 3 sigma, without wrapping over more than one period
+        let r = (3. * sigma).ceil() as usize;
+        let radius = [
+            r.min((dim[0] - 1) / 2),
+            r.min((dim[1] - 1) / 2),
+            r.min((dim[2] - 1) / 2),
+        ];
+
+        let mut kernel = Vec::new();
+        for z in 0..=2 * radius[2] {
+            for y in 0..=2 * radius[1] {
+                for x in 0..=2 * radius[0] {
+                    let d2 = (x as f32 - radius[0] as f32).powi(2)
+                        + (y as f32 - radius[1] as f32).powi(2)
+                        + (z as f32 - radius[2] as f32).powi(2);
+                    kernel.push((-d2 / (2. * sigma * sigma)).exp());
+                }
+            }
+        }
+
+        let count = dim[0] * dim[1] * dim[2];
+        Self {
+            dim,
+            radius,
+            kernel,
+            bits: vec![false; count],
+            energy: vec![0.; count],
+        }
+    }
+
+    fn set(&mut self, idx: usize, value: bool) {
+        self.bits[idx] = value;
+
+        let sign = if value { 1. } else { -1. };
+        let [w, h, d] = self.dim;
+        let [rx, ry, rz] = self.radius;
+        let (i, j, k) = (idx % w, (idx / w) % h, idx / (w * h));
+
+        let mut kernel = self.kernel.iter();
+        for z in 0..=2 * rz {
+            let k = (k + d + z - rz) % d;
+            for y in 0..=2 * ry {
+                let j = (j + h + y - ry) % h;
+                for x in 0..=2 * rx {
+                    let i = (i + w + x - rx) % w;
+                    self.energy[i + w * (j + h * k)] += sign * kernel.next().unwrap();
+                }
+            }
+        }
+    }
+
+    /// Index of the set pixel with the highest energy
+    fn tightest_cluster(&self) -> usize {
+        self.find(true, |a, b| a > b)
+    }
+
+    /// Index of the unset pixel with the lowest energy
+    fn largest_void(&self) -> usize {
+        self.find(false, |a, b| a < b)
+    }
+
+    fn find(&self, value: bool, better: impl Fn(f32, f32) -> bool) -> usize {
+        let mut best: Option<usize> = None;
+
+        for (idx, (&bit, &energy)) in self.bits.iter().zip(self.energy.iter()).enumerate() {
+            if bit == value && best.map(|b| better(energy, self.energy[b])).unwrap_or(true) {
+                best = Some(idx);
+            }
+        }
+
+        best.expect("no pixel to pick in binary pattern")
+    }
+}
+
+/// Snapshot of the blue noise threshold map for CPU evaluation
+struct BlueNoiseCpuParams {
+    ranks: Arc<Vec<u32>>,
+}
+
+/// Dimensions and parameters a threshold map was computed for
+#[derive(Clone, Copy, PartialEq)]
+struct RanksKey {
+    dim: [usize; 3],
+    global_seed: u32,
+    sigma: f32,
+}
+
+impl BlueNoise {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn init_cpu(&mut self, dim: ImageDim, params: &BlueNoiseParams) -> Result<BlueNoiseCpuParams> {
+        // NaN is not finite, so it is rejected too
+        if !params.sigma.is_finite() || params.sigma <= 0. {
+            return Err(Error::InvalidParameters);
+        }
+
+        let key = RanksKey {
+            dim: [dim.width, dim.height, dim.depth],
+            global_seed: params.global_seed,
+            sigma: params.sigma,
+        };
+
+        let ranks = match &self.ranks {
+            Some((cached, ranks)) if *cached == key => ranks.clone(),
+            _ => {
+                let ranks = Arc::new(Self::ranks(dim, params));
+                self.ranks = Some((key, ranks.clone()));
+                ranks
+            }
+        };
+
+        Ok(BlueNoiseCpuParams { ranks })
+    }
+
+    /// Rank every pixel of a `dim` image in the void-and-cluster ordering
+    fn ranks(dim: ImageDim, params: &BlueNoiseParams) -> Vec<u32> {
+        let count = dim.width * dim.height * dim.depth;
+
+        // Empty images have nothing to rank
+        if count == 0 {
+            return Vec::new();
+        }
+
+        let mut pattern = Pattern::new(dim, params.sigma);
+
+        // Initial binary pattern: 10% of the pixels, picked using a partial Fisher-Yates shuffle
+        let ones = (count / 10).max(1);
+        let mut order: Vec<usize> = (0..count).collect();
+        let mut state = hash(params.global_seed);
+        for n in 0..ones {
+            state = hash(state);
+            order.swap(n, n + state as usize % (count - n));
+            pattern.set(order[n], true);
+        }
+
+        // Move pixels from the tightest clusters to the largest voids until this is a no-op
+        if ones < count {
+            loop {
+                let cluster = pattern.tightest_cluster();
+                pattern.set(cluster, false);
+
+                let void = pattern.largest_void();
+                pattern.set(void, true);
+
+                if void == cluster {
+                    break;
+                }
+            }
+        }
+
+        let mut ranks = vec![0; count];
+
+        // Phase 1: rank the initial pixels by removing the tightest clusters
+        let initial_bits = pattern.bits.clone();
+        let initial_energy = pattern.energy.clone();
+        for rank in (0..ones).rev() {
+            let cluster = pattern.tightest_cluster();
+            pattern.set(cluster, false);
+            ranks[cluster] = rank as u32;
+        }
+
+        // Phases 2 and 3: rank the remaining pixels by filling the largest voids. The tightest
+        // cluster of unset pixels that phase 3 looks for is the largest void of set pixels, since
+        // the energy filter sums to the same value everywhere.
+        pattern.bits = initial_bits;
+        pattern.energy = initial_energy;
+        for rank in ones..count {
+            let void = pattern.largest_void();
+            pattern.set(void, true);
+            ranks[void] = rank as u32;
+        }
+
+        ranks
+    }
+
+    fn compute_idx(frag: Fragment, dim: ImageDim, params: &BlueNoiseCpuParams) -> Rank {
+        // The threshold map tiles the plane of the full image
+        let [i, j, k] = frag.pixel();
+        let (i, j, k) = (
+            i.rem_euclid(dim.width as i64) as usize,
+            j.rem_euclid(dim.height as i64) as usize,
+            k.rem_euclid(dim.depth as i64) as usize,
+        );
+
+        Rank {
+            rank: params.ranks[i + dim.width * (j + dim.height * k)],
+            count: params.ranks.len() as u32,
+        }
+    }
+}
+
+#[cfg(all(test, feature = "cpu"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranks_are_cached() {
+        let mut method = BlueNoise::new();
+        let dim = ImageDim::new(16, 16, 1);
+        let params = BlueNoiseParams::default();
+
+        let first = method.init_cpu(dim, &params).unwrap().ranks;
+        let second = method.init_cpu(dim, &params).unwrap().ranks;
+        assert!(Arc::ptr_eq(&first, &second));
+
+        let other_params = BlueNoiseParams {
+            global_seed: 1,
+            ..params
+        };
+        let other = method.init_cpu(dim, &other_params).unwrap().ranks;
+        assert!(!Arc::ptr_eq(&first, &other));
+
+        // Other instances do not share the map
+        let fresh = BlueNoise::new().init_cpu(dim, &params).unwrap().ranks;
+        assert!(!Arc::ptr_eq(&first, &fresh));
+        assert_eq!(first, fresh);
+    }
+
+    #[test]
+    fn invalid_sigma() {
+        let mut method = BlueNoise::new();
+        let dim = ImageDim::new(16, 16, 1);
+
+        for sigma in [0., -1., f32::NAN, f32::INFINITY] {
+            let params = BlueNoiseParams {
+                sigma,
+                ..Default::default()
+            };
+
+            assert!(matches!(
+                method.init_cpu(dim, &params),
+                Err(Error::InvalidParameters)
+            ));
+        }
+    }
+}
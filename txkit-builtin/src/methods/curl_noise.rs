@@ -0,0 +1,126 @@
+use txkit_core::image::{Fragment, ImageDim};
+use txkit_impl::{Method, ParamsFor};
+
+use crate::cpu::*;
+
+/// Curl: potential built from gradient noise
+pub const CURL_POTENTIAL_GRADIENT: i32 = 0;
+/// Curl: potential built from simplex noise, which does not tile
+pub const CURL_POTENTIAL_SIMPLEX: i32 = 1;
+
+#[derive(Clone, Copy, PartialEq, ParamsFor)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+#[repr(C)]
+#[txkit(program = "CurlNoiseProgram")]
+pub struct CurlNoiseParams {
+    /// pseudo-random seed
+    pub global_seed: u32,
+    /// lattice scale (size in pixels)
+    pub scale: f32,
+    /// noise the potential is built from
+    pub noise_potential: i32,
+}
+
+impl Default for CurlNoiseParams {
+    fn default() -> Self {
+        Self {
+            global_seed: 0,
+            scale: 32.,
+            noise_potential: CURL_POTENTIAL_GRADIENT,
+        }
+    }
+}
+
+/// Divergence-free vector field, computed as the curl of a noise potential
+///
+/// On 2D images, the potential is the scalar gradient or simplex noise with the same parameters,
+/// and the (x, y) components of the field are written to the R and G channels. On 3D images, the
+/// potential is made of three decorrelated 3D noises, and the (x, y, z) components are written to
+/// the R, G and B channels.
+///
+/// Components are signed and expressed per lattice cell, so a `Float32` target is required to
+/// use the field as-is.
+///
+/// The gradient potential loops at the scale like gradient noise, so the field tiles over the
+/// image. The simplex potential does not: its skewed lattice cannot loop along the image axes, as
+/// for simplex noise.
+#[derive(Default, Method)]
+#[txkit(
+    gpu(
+        name = "CurlNoiseGpu",
+        program("shaders/quad.vert", "shaders/curl_noise.frag"),
+        method(run = "program", params = "CurlNoiseParams")
+    ),
+    cpu(method(iter = "Self::compute_idx", params = "CurlNoiseParams")),
+    method()
+)]
+pub struct CurlNoise {
+    #[cfg(feature = "gpu")]
+    gpu: Option<CurlNoiseGpu>,
+}
+
+impl CurlNoise {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Gradient of one component of the 3D vector potential
+    fn potential3(
+        sampler: &LatticeSampler,
+        params: &CurlNoiseParams,
+        p: cgmath::Vector3<f32>,
+        i: u32,
+    ) -> cgmath::Vector3<f32> {
+        let seed = hash(params.global_seed.wrapping_add(i));
+
+        let n = if params.noise_potential == CURL_POTENTIAL_SIMPLEX {
+            simplex_noised3(p, seed)
+        } else {
+            gradient_noised3(sampler, p, seed)
+        };
+
+        cgmath::vec3(n.y, n.z, n.w)
+    }
+
+    fn compute_idx(frag: Fragment, dim: ImageDim, params: &CurlNoiseParams) -> f32 {
+        if frag.channel == 3 {
+            return 1.0;
+        }
+
+        let sampler = LatticeSampler::new(
+            dim,
+            params.global_seed,
+            params.scale,
+            STATS_MODE_NORMAL,
+            cgmath::vec2(0., 0.),
+        );
+
+        let uv = frag_uv(frag, dim);
+
+        let curl = if dim.depth > 1 {
+            let p = uv * params.scale;
+            let a = Self::potential3(&sampler, params, p, 0);
+            let b = Self::potential3(&sampler, params, p, 1);
+            let c = Self::potential3(&sampler, params, p, 2);
+
+            cgmath::vec3(c.y - b.z, a.z - c.x, b.x - a.y)
+        } else {
+            let n = if params.noise_potential == CURL_POTENTIAL_SIMPLEX {
+                simplex_noised(sampler.sample(uv.truncate(), LATTICE_MODE_SIMPLEX_2D))
+            } else {
+                gradient_noised(
+                    &sampler,
+                    sampler.sample(uv.truncate(), LATTICE_MODE_RECT_2D),
+                )
+            };
+
+            cgmath::vec3(n.z, -n.y, 0.)
+        };
+
+        curl[frag.channel]
+    }
+}
@@ -1,7 +1,12 @@
-use txkit_core::image::ImageDim;
+use txkit_core::image::{Fragment, ImageDim};
 use txkit_impl::{Method, ParamsFor};
 
 #[derive(Clone, Copy, PartialEq, ParamsFor)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
 #[repr(C)]
 #[txkit(program = "DebugProgram")]
 pub struct DebugParams {
@@ -14,6 +19,7 @@ impl Default for DebugParams {
     }
 }
 
+/// Debug pattern, with the pixel coordinates in the color channels
 #[derive(Default, Method)]
 #[txkit(
     gpu(
@@ -34,17 +40,15 @@ impl Debug {
         Self::default()
     }
 
-    fn compute_idx(
-        (k, j, i, l): (usize, usize, usize, usize),
-        _dim: ImageDim,
-        params: &DebugParams,
-    ) -> f32 {
-        match l {
+    fn compute_idx(frag: Fragment, _dim: ImageDim, params: &DebugParams) -> f32 {
+        let [i, j, k] = frag.pixel();
+
+        match frag.channel {
             0 => i as f32,
             1 => j as f32,
             2 => k as f32,
             3 => params.alpha_value,
-            _ => unreachable!(),
+            _ => 0.,
         }
     }
 }
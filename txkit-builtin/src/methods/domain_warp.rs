@@ -0,0 +1,369 @@
+use std::any::Any;
+
+use ndarray::{Array4, Axis};
+
+use txkit_core::context::{Context, CpuContext};
+use txkit_core::image::{Fragment, Image, ImageDim};
+use txkit_core::io::cpu::{CpuImageIoExt, TextureSampler};
+use txkit_core::io::{ImageBinding, ImageIo};
+use txkit_core::method::{
+    downcast_params, Method, MethodInfo, MethodParams, MethodRegistry, OUTPUT_CHANNELS,
+};
+use txkit_core::{Error, Result};
+use txkit_impl::ParamsFor;
+
+use super::scratch::*;
+
+/// Domain warp: texture unit of the base image
+pub const DOMAIN_WARP_BASE_UNIT: usize = 0;
+/// Domain warp: texture unit of the X warp image (or of both components, in R and G)
+pub const DOMAIN_WARP_X_UNIT: usize = 1;
+/// Domain warp: texture unit of the Y warp image
+pub const DOMAIN_WARP_Y_UNIT: usize = 2;
+
+#[derive(Clone, PartialEq, ParamsFor)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+#[repr(C)]
+pub struct DomainWarpParams {
+    /// warp amplitude, in normalized texture coordinates
+    pub amplitude: f32,
+    /// number of times the warp is applied
+    pub warp_depth: i32,
+
+    /// texture inputs, overriding the corresponding methods when bound
+    #[texture_io(base_texture, warp_x_texture, warp_y_texture)]
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            skip_deserializing,
+            skip_serializing_if = "crate::serde_io::is_unbound",
+            serialize_with = "crate::serde_io::serialize"
+        )
+    )]
+    pub io: Box<ImageIo>,
+}
+
+impl Default for DomainWarpParams {
+    fn default() -> Self {
+        Self {
+            amplitude: 0.1,
+            warp_depth: 1,
+            io: Default::default(),
+        }
+    }
+}
+
+/// Method evaluated as an input of a domain warp
+struct Source {
+    method: Box<dyn Method>,
+    params: Option<Box<dyn MethodParams>>,
+}
+
+impl Source {
+    fn params(&self) -> Result<Box<dyn MethodParams>> {
+        match &self.params {
+            Some(params) => Ok(params.clone_params()),
+            None => self.method.default_params().ok_or(Error::InvalidParameters),
+        }
+    }
+}
+
+/// Input of a domain warp
+enum Input<'s> {
+    /// Image bound to the texture unit of the input
+    Image(TextureSampler),
+    /// Method evaluated at the looked up positions, with its parameters
+    Method(&'s mut dyn Method, Box<dyn MethodParams>),
+}
+
+impl<'s> Input<'s> {
+    /// Get the input on `unit`, preferring the bound image over the method
+    fn new(io: &ImageIo, unit: usize, source: Option<&'s mut Source>) -> Result<Option<Input<'s>>> {
+        if !matches!(io.get_texture_binding(unit), ImageBinding::None) {
+            return io
+                .texture_sampler(unit)
+                .map(|sampler| Some(Input::Image(sampler)));
+        }
+
+        match source {
+            Some(source) => {
+                let params = source.params()?;
+                Ok(Some(Input::Method(source.method.as_mut(), params)))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Evaluate a channel of this input
+    ///
+    /// # Parameters
+    ///
+    /// * `ctx`: CPU context to evaluate methods in
+    /// * `dim`: dimensions of the full image
+    /// * `points`: positions to evaluate, in texture coordinates of the full image
+    /// * `channel`: channel to evaluate
+    /// * `params`: parameters overriding those of the input method
+    fn evaluate(
+        &mut self,
+        ctx: &mut CpuContext,
+        dim: ImageDim,
+        points: &[cgmath::Vector3<f32>],
+        channel: usize,
+        params: Option<&dyn MethodParams>,
+    ) -> Result<Vec<f32>> {
+        match self {
+            // Bound images only hold one layer
+            Self::Image(sampler) => Ok(points
+                .iter()
+                .map(|p| sampler.texture(p.truncate())[channel])
+                .collect()),
+            Self::Method(method, method_params) => {
+                let params = params.unwrap_or_else(|| method_params.as_ref());
+                let fragments: Vec<_> = points
+                    .iter()
+                    .map(|p| Fragment {
+                        position: [
+                            p.x * dim.width as f32,
+                            p.y * dim.height as f32,
+                            p.z * dim.depth as f32,
+                        ],
+                        channel,
+                    })
+                    .collect();
+
+                let mut values = vec![0.; fragments.len()];
+                method.compute_fragments(
+                    ctx,
+                    &fragments,
+                    dim,
+                    Some(params.as_any()),
+                    &mut values,
+                )?;
+                Ok(values)
+            }
+        }
+    }
+}
+
+/// Source of the Y component of the warp
+enum WarpY<'s> {
+    /// Separate input
+    Input(Input<'s>),
+    /// X warp method, with the given parameters
+    Method(Box<dyn MethodParams>),
+    /// G channel of the X warp image
+    Green,
+}
+
+/// Domain warping of a base texture by one or two warp textures
+///
+/// Evaluates `base(p + amplitude * warp(p))`, where `warp` maps the [0, 1] output of the warp
+/// textures to [-1, 1]. With a warp depth of `n`, the warp is applied `n` times to its own
+/// output. Each input is either a method, evaluated exactly at the warped positions, or the image
+/// bound to its texture unit in the parameters, sampled bilinearly.
+///
+/// If a single warp method is set, its Y component is evaluated with the next `global_seed`. If
+/// a single warp image is bound, its Y component is read from the G channel.
+///
+/// The warp displaces the X and Y coordinates, so the layers of volumes are warped independently.
+/// Bound images only provide their first layer. Input methods are evaluated on the host, with
+/// [`Method::compute_fragments`], and the result is uploaded to GPU targets.
+#[derive(Default)]
+pub struct DomainWarp {
+    base: Option<Source>,
+    warp_x: Option<Source>,
+    warp_y: Option<Source>,
+    /// Context evaluating the inputs when rendering on a GPU context
+    host: Option<CpuContext>,
+}
+
+impl DomainWarp {
+    /// Create a domain warp without input methods
+    ///
+    /// Inputs must be either set as methods, or bound as images in the parameters.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a domain warp of `base` by `warp`
+    pub fn with_methods(base: Box<dyn Method>, warp: Box<dyn Method>) -> Self {
+        let mut result = Self::new();
+        result.set_base(base, None);
+        result.set_warp_x(warp, None);
+        result
+    }
+
+    /// Create a domain warp of the method named `base` by the method named `warp` in `registry`
+    pub fn from_registry(registry: &MethodRegistry, base: &str, warp: &str) -> Result<Self> {
+        Ok(Self::with_methods(
+            registry.build(base).ok_or(Error::MethodNotFound)?,
+            registry.build(warp).ok_or(Error::MethodNotFound)?,
+        ))
+    }
+
+    /// Set the base method, with its parameters or `None` for its defaults
+    pub fn set_base(&mut self, method: Box<dyn Method>, params: Option<Box<dyn MethodParams>>) {
+        self.base = Some(Source { method, params });
+    }
+
+    /// Set the X warp method, with its parameters or `None` for its defaults
+    pub fn set_warp_x(&mut self, method: Box<dyn Method>, params: Option<Box<dyn MethodParams>>) {
+        self.warp_x = Some(Source { method, params });
+    }
+
+    /// Set the Y warp method, with its parameters or `None` for its defaults
+    pub fn set_warp_y(&mut self, method: Box<dyn Method>, params: Option<Box<dyn MethodParams>>) {
+        self.warp_y = Some(Source { method, params });
+    }
+}
+
+/// Get the context to evaluate inputs in, creating `host` for GPU contexts
+#[cfg(feature = "cpu")]
+fn host_context<'c>(
+    ctx: &'c mut Context,
+    host: &'c mut Option<CpuContext>,
+) -> Result<&'c mut CpuContext> {
+    match ctx {
+        Context::Cpu(ctx) => Ok(ctx),
+        Context::Gpu(_) => {
+            if host.is_none() {
+                *host = Some(CpuContext::new()?);
+            }
+
+            host.as_mut().ok_or(Error::ContextNotSupported)
+        }
+    }
+}
+
+#[cfg(not(feature = "cpu"))]
+fn host_context<'c>(
+    ctx: &'c mut Context,
+    _host: &'c mut Option<CpuContext>,
+) -> Result<&'c mut CpuContext> {
+    ctx.cpu_mut().ok_or(Error::ContextNotSupported)
+}
+
+impl Method for DomainWarp {
+    fn compute(
+        &mut self,
+        ctx: &mut Context,
+        tgt: &mut Image,
+        params: Option<&dyn Any>,
+    ) -> Result<()> {
+        let mut default_params: Option<DomainWarpParams> = None;
+        let params = downcast_params(params, &mut default_params)?;
+
+        if params.warp_depth < 0 {
+            return Err(Error::InvalidParameters);
+        }
+
+        check_float_target(tgt)?;
+
+        let region = tgt.region();
+        let dim = tgt.dim();
+        let full_dim = region.full_dim(dim.channels);
+
+        let DomainWarp {
+            base,
+            warp_x,
+            warp_y,
+            host,
+        } = self;
+        let ctx = host_context(ctx, host)?;
+
+        let io = params.io.as_ref();
+        let mut base = Input::new(io, DOMAIN_WARP_BASE_UNIT, base.as_mut())?
+            .ok_or(Error::InvalidParameters)?;
+        let mut warp_x =
+            Input::new(io, DOMAIN_WARP_X_UNIT, warp_x.as_mut())?.ok_or(Error::InvalidParameters)?;
+        let mut warp_y = match Input::new(io, DOMAIN_WARP_Y_UNIT, warp_y.as_mut())? {
+            Some(input) => WarpY::Input(input),
+            // A single warp method provides the Y component using the next seed
+            None => match &warp_x {
+                Input::Method(_, params) => {
+                    let mut y_params = params.clone_params();
+                    let seed = y_params.global_seed().ok_or(Error::InvalidParameters)?;
+                    y_params.set_global_seed(seed.wrapping_add(1));
+                    WarpY::Method(y_params)
+                }
+                Input::Image(_) => WarpY::Green,
+            },
+        };
+
+        // Pixel centers of the target, in texture coordinates of the full image
+        let points: Vec<_> = ndarray::indices((dim.depth, dim.height, dim.width))
+            .into_iter()
+            .map(|(k, j, i)| {
+                let [x, y, z] = region.fragment((k, j, i, 0)).position;
+                cgmath::vec3(
+                    x / full_dim.width as f32,
+                    y / full_dim.height as f32,
+                    z / full_dim.depth as f32,
+                )
+            })
+            .collect();
+
+        let mut warped = points.clone();
+        for _ in 0..params.warp_depth {
+            let x = warp_x.evaluate(ctx, full_dim, &warped, 0, None)?;
+            let y = match &mut warp_y {
+                WarpY::Input(input) => input.evaluate(ctx, full_dim, &warped, 0, None)?,
+                WarpY::Method(params) => {
+                    warp_x.evaluate(ctx, full_dim, &warped, 0, Some(params.as_ref()))?
+                }
+                WarpY::Green => warp_x.evaluate(ctx, full_dim, &warped, 1, None)?,
+            };
+
+            for (((q, p), x), y) in warped.iter_mut().zip(&points).zip(x).zip(y) {
+                q.x = p.x + params.amplitude * (2. * x - 1.);
+                q.y = p.y + params.amplitude * (2. * y - 1.);
+            }
+        }
+
+        // The channels the base does not compute are left at zero
+        let mut result = Array4::zeros((dim.depth, dim.height, dim.width, dim.channels));
+        for l in 0..dim.channels.min(OUTPUT_CHANNELS) {
+            let values = base.evaluate(ctx, full_dim, &warped, l, None)?;
+            for (o, value) in result.index_axis_mut(Axis(3), l).iter_mut().zip(values) {
+                *o = value;
+            }
+        }
+
+        write_result(tgt, &result)
+    }
+
+    fn default_params(&self) -> Option<Box<dyn MethodParams>> {
+        Some(Box::new(DomainWarpParams::default()))
+    }
+
+    fn info(&self) -> Option<MethodInfo> {
+        // Input methods are evaluated on the host, bound images can be on any backend
+        let cpu = [&self.base, &self.warp_x, &self.warp_y]
+            .iter()
+            .copied()
+            .flatten()
+            .all(|source| source.method.info().map(|info| info.cpu).unwrap_or(false));
+
+        // The warp only displaces X and Y, so volumes vary along z as much as the base does. Bound
+        // base images only provide their first layer.
+        let max_dimensions = self
+            .base
+            .as_ref()
+            .and_then(|source| source.method.info())
+            .map(|info| info.max_dimensions)
+            .unwrap_or(2);
+
+        Some(MethodInfo {
+            description: "Domain warping of a base texture by one or two warp textures",
+            cpu,
+            gpu: cpu && cfg!(feature = "gpu"),
+            element_types: float_element_types(),
+            max_dimensions,
+            params_type: "DomainWarpParams",
+        })
+    }
+}
@@ -0,0 +1,316 @@
+use std::any::Any;
+
+use ndarray::{Array4, Axis, Slice, Zip};
+
+use txkit_core::context::{Context, CpuContext};
+use txkit_core::image::{Fragment, Image, ImageDim};
+use txkit_core::method::{
+    downcast_params, Method, MethodInfo, MethodParams, MethodRegistry, OUTPUT_CHANNELS,
+};
+use txkit_core::{Error, Result};
+use txkit_impl::ParamsFor;
+
+use super::scratch::*;
+
+/// Fractal: fractional brownian motion, weighted sum of octaves
+pub const FRACTAL_MODE_FBM: i32 = 0;
+/// Fractal: turbulence, weighted sum of the absolute value of octaves
+pub const FRACTAL_MODE_TURBULENCE: i32 = 1;
+/// Fractal: ridged multifractal
+pub const FRACTAL_MODE_RIDGED: i32 = 2;
+/// Fractal: hybrid multifractal
+pub const FRACTAL_MODE_HYBRID: i32 = 3;
+
+#[derive(Clone, Copy, PartialEq, ParamsFor)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+#[repr(C)]
+pub struct FractalParams {
+    /// number of octaves to sum
+    pub octaves: i32,
+    /// frequency multiplier between successive octaves
+    pub lacunarity: f32,
+    /// amplitude multiplier between successive octaves
+    pub gain: f32,
+    /// fractal mode
+    pub fractal_mode: i32,
+    /// offset for the ridged and hybrid multifractal modes
+    pub offset: f32,
+}
+
+impl Default for FractalParams {
+    fn default() -> Self {
+        Self {
+            octaves: 4,
+            lacunarity: 2.,
+            gain: 0.5,
+            fractal_mode: FRACTAL_MODE_FBM,
+            offset: 1.,
+        }
+    }
+}
+
+/// Fractal sum of octaves of another method
+///
+/// Each octave renders the wrapped method with its `scale` multiplied by the lacunarity and its
+/// `global_seed` incremented. The wrapped method is expected to output values in [0, 1], and
+/// the alpha channel of 4-channel images is taken from the first octave.
+pub struct Fractal {
+    method: Box<dyn Method>,
+    method_params: Option<Box<dyn MethodParams>>,
+}
+
+impl Fractal {
+    pub fn new(method: Box<dyn Method>) -> Self {
+        Self {
+            method,
+            method_params: None,
+        }
+    }
+
+    /// Wrap the method named `name` in `registry`
+    pub fn from_registry(registry: &MethodRegistry, name: &str) -> Result<Self> {
+        registry
+            .build(name)
+            .map(Self::new)
+            .ok_or(Error::MethodNotFound)
+    }
+
+    /// Set the parameters of the first octave of the wrapped method
+    ///
+    /// If not set, the default parameters of the wrapped method are used.
+    pub fn set_method_params(&mut self, params: Box<dyn MethodParams>) {
+        self.method_params = Some(params);
+    }
+}
+
+impl Fractal {
+    /// Parameters of the wrapped method for each octave
+    fn octave_params(&self, params: &FractalParams) -> Result<Vec<Box<dyn MethodParams>>> {
+        if params.octaves < 1 {
+            return Err(Error::InvalidParameters);
+        }
+
+        let base_params = match &self.method_params {
+            Some(method_params) => method_params.clone_params(),
+            None => self
+                .method
+                .default_params()
+                .ok_or(Error::InvalidParameters)?,
+        };
+
+        let base_scale = base_params.scale().ok_or(Error::InvalidParameters)?;
+        let base_seed = base_params.global_seed().ok_or(Error::InvalidParameters)?;
+
+        Ok((0..params.octaves)
+            .map(|octave| {
+                let mut octave_params = base_params.clone_params();
+                octave_params.set_scale(base_scale * params.lacunarity.powi(octave));
+                octave_params.set_global_seed(base_seed.wrapping_add(octave as u32));
+                octave_params
+            })
+            .collect())
+    }
+}
+
+/// Running state of a fractal sum
+struct Accumulator {
+    sum: Array4<f32>,
+    weight: Array4<f32>,
+    amplitude: f32,
+    norm: f32,
+}
+
+impl Accumulator {
+    fn new(sum: Array4<f32>) -> Self {
+        let weight = Array4::ones(sum.raw_dim());
+        Self {
+            sum,
+            weight,
+            amplitude: 1.,
+            norm: 0.,
+        }
+    }
+
+    fn add(&mut self, params: &FractalParams, octave: usize, values: ndarray::ArrayView4<f32>) {
+        let amplitude = self.amplitude;
+        let offset = params.offset;
+
+        let sum = Zip::from(&mut self.sum).and(&mut self.weight).and(&values);
+
+        match params.fractal_mode {
+            FRACTAL_MODE_TURBULENCE => sum.for_each(|s, _, n| {
+                *s += amplitude * (2. * n - 1.).abs();
+            }),
+            FRACTAL_MODE_RIDGED => sum.for_each(|s, w, n| {
+                // Sharpen the ridges, and weight successive octaves by the previous ones
+                let signal = offset - (2. * n - 1.).abs();
+                let signal = signal * signal * *w;
+                *w = (signal * 2.).clamp(0., 1.);
+                *s += amplitude * signal;
+            }),
+            FRACTAL_MODE_HYBRID => sum.for_each(|s, w, n| {
+                let signal = (2. * n - 1. + offset) * amplitude;
+                if octave == 0 {
+                    *s = signal;
+                    *w = signal;
+                } else {
+                    // The weight is clamped before it is carried to the next octave
+                    let weight = w.min(1.);
+                    *s += weight * signal;
+                    *w = weight * signal;
+                }
+            }),
+            _ => sum.for_each(|s, _, n| {
+                *s += amplitude * n;
+            }),
+        }
+
+        self.norm += amplitude;
+        self.amplitude *= params.gain;
+    }
+
+    fn finish(mut self, params: &FractalParams) -> Array4<f32> {
+        let norm = match params.fractal_mode {
+            FRACTAL_MODE_RIDGED => self.norm * params.offset * params.offset,
+            FRACTAL_MODE_HYBRID => self.norm * (1. + params.offset),
+            _ => self.norm,
+        };
+
+        self.sum.mapv_inplace(|x| x / norm);
+        self.sum
+    }
+}
+
+impl Method for Fractal {
+    fn compute(
+        &mut self,
+        ctx: &mut Context,
+        tgt: &mut Image,
+        params: Option<&dyn Any>,
+    ) -> Result<()> {
+        let mut default_params: Option<FractalParams> = None;
+        let params = downcast_params(params, &mut default_params)?;
+
+        check_float_target(tgt)?;
+
+        let octaves = self.octave_params(params)?;
+
+        let dim = tgt.dim();
+        let mut octave_image = new_scratch_image(ctx, tgt)?;
+        let mut acc = Accumulator::new(Array4::zeros((
+            dim.depth,
+            dim.height,
+            dim.width,
+            dim.channels,
+        )));
+        let mut alpha = None;
+
+        for (octave, octave_params) in octaves.iter().enumerate() {
+            self.method
+                .compute(ctx, &mut octave_image, Some(octave_params.as_any()))?;
+            octave_image.download()?;
+
+            let data = octave_image.data()?;
+            let values = data.as_f32_nd_array().ok_or(Error::FormatNotSupported)?;
+
+            if octave == 0 && dim.channels >= 4 {
+                alpha = Some(values.index_axis(Axis(3), 3).to_owned());
+            }
+
+            acc.add(params, octave, values);
+        }
+
+        let mut result = acc.finish(params);
+        if let Some(alpha) = alpha {
+            result.index_axis_mut(Axis(3), 3).assign(&alpha);
+        }
+
+        // Keep the channels the octaves do not compute at zero
+        if dim.channels > OUTPUT_CHANNELS {
+            result
+                .slice_axis_mut(Axis(3), Slice::from(OUTPUT_CHANNELS..))
+                .fill(0.);
+        }
+
+        write_result(tgt, &result)
+    }
+
+    fn compute_fragments(
+        &mut self,
+        ctx: &mut CpuContext,
+        fragments: &[Fragment],
+        dim: ImageDim,
+        params: Option<&dyn Any>,
+        values: &mut [f32],
+    ) -> Result<()> {
+        let mut default_params: Option<FractalParams> = None;
+        let params = downcast_params(params, &mut default_params)?;
+        let octaves = self.octave_params(params)?;
+
+        if fragments.len() != values.len() {
+            return Err(Error::ImageDimensionMismatch);
+        }
+
+        // Fragments are summed as a single row of single-channel pixels
+        let shape = (1, 1, fragments.len(), 1);
+        let mut octave_values = Array4::zeros(shape);
+        let mut acc = Accumulator::new(Array4::zeros(shape));
+        let mut alpha = None;
+
+        for (octave, octave_params) in octaves.iter().enumerate() {
+            self.method.compute_fragments(
+                ctx,
+                fragments,
+                dim,
+                Some(octave_params.as_any()),
+                octave_values
+                    .as_slice_mut()
+                    .ok_or(Error::FormatNotSupported)?,
+            )?;
+
+            if octave == 0 {
+                alpha = Some(octave_values.clone());
+            }
+
+            acc.add(params, octave, octave_values.view());
+        }
+
+        let result = acc.finish(params);
+        let alpha = alpha.ok_or(Error::InvalidParameters)?;
+
+        for (((value, frag), sum), alpha) in values
+            .iter_mut()
+            .zip(fragments)
+            .zip(result.iter())
+            .zip(alpha.iter())
+        {
+            *value = match frag.channel {
+                3 => *alpha,
+                channel if channel >= OUTPUT_CHANNELS => 0.,
+                _ => *sum,
+            };
+        }
+
+        Ok(())
+    }
+
+    fn default_params(&self) -> Option<Box<dyn MethodParams>> {
+        Some(Box::new(FractalParams::default()))
+    }
+
+    fn info(&self) -> Option<MethodInfo> {
+        // Octaves are rendered by the wrapped method, on the backend of the target
+        let method_info = self.method.info()?;
+
+        Some(MethodInfo {
+            description: "Fractal sum of octaves of another method",
+            element_types: float_element_types(),
+            params_type: "FractalParams",
+            ..method_info
+        })
+    }
+}
@@ -0,0 +1,235 @@
+use cgmath::InnerSpace;
+use txkit_core::image::{Fragment, ImageDim};
+use txkit_core::io::ImageIo;
+use txkit_impl::{Method, ParamsFor};
+
+use crate::cpu::*;
+
+/// Gabor: random kernel orientations
+pub const GABOR_ORIENTATION_ISOTROPIC: i32 = 0;
+/// Gabor: all kernels oriented along noise_angle
+pub const GABOR_ORIENTATION_ANISOTROPIC: i32 = 1;
+/// Gabor: kernel frequency and orientation driven by the frequency_orientation_field input
+pub const GABOR_ORIENTATION_FIELD: i32 = 2;
+
+#[derive(Clone, PartialEq, ParamsFor)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+#[repr(C)]
+#[txkit(program = "GaborNoiseProgram")]
+pub struct GaborNoiseParams {
+    /// pseudo-random seed
+    pub global_seed: u32,
+    /// lattice scale (size in pixels)
+    pub scale: f32,
+    /// stats mode (0: normal, 1: process, 2: lookat)
+    pub stats_mode: i32,
+    /// look-at parameter (if stats_mode == lookat) in [0, 1]^2
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_vector"))]
+    pub stats_look_at: cgmath::Vector2<f32>,
+
+    /// cell lookahead: number of cells to check for contributions
+    pub noise_lookahead: i32,
+    /// kernel count per cell
+    pub kernel_count: i32,
+    /// kernel orientation mode
+    pub noise_orientation_mode: i32,
+    /// noise weights (same values as the phasor noise weights)
+    pub noise_weights: i32,
+    /// point distribution (same values as the phasor noise point distribution)
+    pub noise_point_distribution: i32,
+
+    /// noise frequency (in oscillations / noise cell)
+    pub noise_frequency: f32,
+    /// noise angle (in radians)
+    pub noise_angle: f32,
+
+    /// jittering amount, 0 = no random, 1 = full subcell random
+    pub jitter_amount: f32,
+    /// max jittering subcells, 0 = no limit
+    pub jitter_max: i32,
+
+    /// texture inputs
+    #[texture_io(frequency_orientation_field)]
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            skip_deserializing,
+            skip_serializing_if = "crate::serde_io::is_unbound",
+            serialize_with = "crate::serde_io::serialize"
+        )
+    )]
+    pub io: Box<ImageIo>,
+}
+
+impl Default for GaborNoiseParams {
+    fn default() -> Self {
+        Self {
+            global_seed: 0,
+            scale: 32.,
+            stats_mode: 0,
+            stats_look_at: cgmath::vec2(0., 0.),
+            noise_lookahead: 1,
+            kernel_count: 8,
+            noise_orientation_mode: GABOR_ORIENTATION_ANISOTROPIC,
+            noise_weights: KERNEL_WEIGHTS_NONE,
+            noise_point_distribution: POINTS_STRAT_POISSON,
+            noise_frequency: 4.,
+            noise_angle: 0.,
+            jitter_amount: 1.,
+            jitter_max: 0,
+            io: Default::default(),
+        }
+    }
+}
+
+/// Gabor noise, a sparse convolution of Gabor kernels
+#[derive(Default, Method)]
+#[txkit(
+    gpu(
+        name = "GaborNoiseGpu",
+        program("shaders/quad.vert", "shaders/gabor_noise.frag"),
+        method(run = "program", params = "GaborNoiseParams")
+    ),
+    cpu(method(
+        iter = "Self::compute_idx",
+        init = "Self::init_cpu",
+        params = "GaborNoiseParams"
+    )),
+    method(max_dimensions = 2)
+)]
+pub struct GaborNoise {
+    #[cfg(feature = "gpu")]
+    gpu: Option<GaborNoiseGpu>,
+}
+
+/// Snapshot of the Gabor noise parameters for CPU evaluation
+struct GaborNoiseCpuParams {
+    sampler: LatticeSampler,
+    noise_orientation_mode: i32,
+    kernels: KernelParams,
+}
+
+impl GaborNoise {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn init_cpu(
+        &self,
+        dim: ImageDim,
+        params: &GaborNoiseParams,
+    ) -> txkit_core::Result<GaborNoiseCpuParams> {
+        use txkit_core::io::cpu::CpuImageIoExt;
+
+        Ok(GaborNoiseCpuParams {
+            sampler: LatticeSampler::new(
+                dim,
+                params.global_seed,
+                params.scale,
+                params.stats_mode,
+                params.stats_look_at,
+            ),
+            noise_orientation_mode: params.noise_orientation_mode,
+            kernels: KernelParams {
+                scale: params.scale,
+                noise_lookahead: params.noise_lookahead,
+                kernel_count: params.kernel_count,
+                noise_weights: params.noise_weights,
+                noise_point_distribution: params.noise_point_distribution,
+                noise_frequency: params.noise_frequency,
+                noise_angle: params.noise_angle,
+                jitter_amount: params.jitter_amount,
+                jitter_max: params.jitter_max,
+                // frequency_orientation_field is bound to texture unit 0 in kernels.glsl
+                frequency_orientation_field: params.io.texture_sampler(0)?,
+            },
+        })
+    }
+
+    fn gabor(params: &GaborNoiseCpuParams, x: cgmath::Vector2<f32>, k: &Kernel) -> f32 {
+        let scale = params.sampler.scale;
+        let b = (1. / scale) * (1. / scale) * M_PI;
+
+        let gaus = (-b * x.dot(x)).exp();
+        let osc = 2. * M_PI * x.dot(k.angle) + k.phase;
+
+        k.weight * gaus * osc.cos()
+    }
+
+    fn noise_cell(
+        params: &GaborNoiseCpuParams,
+        pos: cgmath::Vector2<f32>,
+        cell: cgmath::Vector2<i32>,
+        seed: u32,
+    ) -> f32 {
+        let scale = params.sampler.scale;
+        let mut res = 0.;
+
+        // Seed the random number generator
+        let mut rng = Lcg::new(hash_cell(to_uvec2(cell), seed));
+
+        // Compute impulse count
+        let ps = params.kernels.init(&mut rng);
+
+        // Fixed number of impulses per cell
+        for i in 0..ps.count {
+            // Sample kernel parameters
+            let fo = if params.noise_orientation_mode == GABOR_ORIENTATION_FIELD {
+                params.kernels.field(pos, cell)
+            } else {
+                cgmath::vec4(0., 0., 0., 0.)
+            };
+
+            // Generate a kernel
+            let mut k = params.kernels.next(&mut rng, &ps, i, fo);
+
+            if params.noise_orientation_mode == GABOR_ORIENTATION_ISOTROPIC {
+                let w = M_2PI * rng.next01();
+                k.angle = params.kernels.noise_frequency / scale * cgmath::vec2(w.cos(), w.sin());
+            }
+
+            // Compute contribution
+            res += Self::gabor(params, scale * (pos - k.pos), &k);
+        }
+
+        res
+    }
+
+    fn noise(params: &GaborNoiseCpuParams, s: LatticeNoiseSample) -> f32 {
+        // Compute Gabor noise value
+        let mut res = 0.;
+        let lookahead = params.kernels.noise_lookahead;
+
+        for cx in s.cell.x - lookahead..=s.cell.x + lookahead {
+            for cy in s.cell.y - lookahead..=s.cell.y + lookahead {
+                // Make sure the noise tiles correctly
+                let looped_cell = params.sampler.lattice_loop_signed(cgmath::vec2(cx, cy));
+
+                // Compute the position of the current point relative to the target cell
+                let position =
+                    s.position - cgmath::vec2((cx - s.cell.x) as f32, (cy - s.cell.y) as f32);
+
+                // Add contribution of target noise cell
+                res += Self::noise_cell(params, position, looped_cell, s.seed);
+            }
+        }
+
+        to01(res / params.kernels.kernel_count as f32)
+    }
+
+    fn compute_idx(frag: Fragment, dim: ImageDim, params: &GaborNoiseCpuParams) -> f32 {
+        if frag.channel == 3 {
+            return 1.0;
+        }
+
+        let uv = frag_uv(frag, dim);
+        Self::noise(
+            params,
+            params.sampler.sample(uv.truncate(), LATTICE_MODE_RECT_2D),
+        )
+    }
+}
@@ -0,0 +1,40 @@
+//! Shared definitions for the lattice noises (value, gradient and simplex)
+
+use txkit_core::{Error, Result};
+
+/// Lattice noise output: noise value in the color channels
+pub const LATTICE_OUTPUT_VALUE: i32 = 0;
+/// Lattice noise output: noise value in R, and its partial derivatives in G, B and A
+///
+/// The derivatives are those of the value written to R, with respect to the lattice coordinates
+/// (x, y, and z for 3D and 4D lattices). They are signed, so a `Float32` target is required to
+/// use them as-is.
+pub const LATTICE_OUTPUT_DERIVATIVES: i32 = 1;
+
+/// Lattice noise dimensions: 2D lattice, evaluated independently on every layer
+pub const LATTICE_DIMENSIONS_2D: i32 = 0;
+/// Lattice noise dimensions: 3D lattice, spanning the layers of the image
+///
+/// Like the 2D lattice, the lattice is `scale` cells wide along z, so that volumes are
+/// sampled with the same resolution along every axis. Single-layer images are a slice through
+/// the middle of the volume.
+pub const LATTICE_DIMENSIONS_3D: i32 = 1;
+/// Lattice noise dimensions: 4D lattice, with the `time` parameter as the last coordinate
+///
+/// Value and gradient noises loop over `scale` lattice cells along every axis, including time,
+/// so that animations with non-negative times loop with a period of `scale`.
+pub const LATTICE_DIMENSIONS_4D: i32 = 2;
+
+/// Check that every component of a lattice period is a whole number of cells
+///
+/// Both backends convert periods to unsigned integers, which they would disagree on for negative,
+/// fractional or out of range periods.
+pub(crate) fn check_period(period: &[f32]) -> Result<()> {
+    let valid = |p: f32| p.is_finite() && p >= 0. && p.fract() == 0. && p as f64 <= u32::MAX as f64;
+
+    if period.iter().copied().all(valid) {
+        Ok(())
+    } else {
+        Err(Error::InvalidParameters)
+    }
+}
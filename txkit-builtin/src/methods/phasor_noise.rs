@@ -1,6 +1,11 @@
+use cgmath::{InnerSpace, Vector2, Vector3};
+use txkit_core::image::{Fragment, ImageDim};
 use txkit_core::io::ImageIo;
 use txkit_impl::{Method, ParamsFor};
 
+use super::lattice::check_period;
+use crate::cpu::*;
+
 /// Phasor: complex sum divided by the kernel count in R and G
 pub const PHASOR_PROFILE_COMPLEX: i32 = 0;
 /// Phasor: real part of the complex sum divided by the kernel count in R
@@ -31,6 +36,11 @@ pub const PHASOR_POINTS_RECT_JITTERED: i32 = 2;
 pub const PHASOR_POINTS_HEX_JITTERED: i32 = 3;
 
 #[derive(Clone, PartialEq, ParamsFor)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
 #[repr(C)]
 #[txkit(program = "PhasorNoiseProgram")]
 pub struct PhasorNoiseParams {
@@ -41,7 +51,11 @@ pub struct PhasorNoiseParams {
     /// stats mode (0: normal, 1: process, 2: lookat)
     pub stats_mode: i32,
     /// look-at parameter (if stats_mode == lookat) in [0, 1]^2
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_vector"))]
     pub stats_look_at: cgmath::Vector2<f32>,
+    /// lattice period along (x, y), in whole lattice cells (0: loop at the scale)
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_vector"))]
+    pub period: cgmath::Vector2<f32>,
 
     /// cell lookahead: number of cells to check for contributions
     pub noise_lookahead: i32,
@@ -66,6 +80,14 @@ pub struct PhasorNoiseParams {
 
     /// texture inputs
     #[texture_io(frequency_orientation_field)]
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            skip_deserializing,
+            skip_serializing_if = "crate::serde_io::is_unbound",
+            serialize_with = "crate::serde_io::serialize"
+        )
+    )]
     pub io: Box<ImageIo>,
 }
 
@@ -76,6 +98,7 @@ impl Default for PhasorNoiseParams {
             scale: 32.,
             stats_mode: 0,
             stats_look_at: cgmath::vec2(0., 0.),
+            period: cgmath::vec2(0., 0.),
             noise_lookahead: 1,
             kernel_count: 8,
             noise_profile: PHASOR_PROFILE_SIN,
@@ -90,6 +113,7 @@ impl Default for PhasorNoiseParams {
     }
 }
 
+/// Phasor noise, the oscillating phase field of a sum of complex Gabor kernels
 #[derive(Default, Method)]
 #[txkit(
     gpu(
@@ -97,15 +121,186 @@ impl Default for PhasorNoiseParams {
         program("shaders/quad.vert", "shaders/phasor_noise.frag"),
         method(run = "program", params = "PhasorNoiseParams")
     ),
-    method()
+    cpu(method(
+        iter = "Self::compute_idx",
+        init = "Self::init_cpu",
+        params = "PhasorNoiseParams"
+    )),
+    method(check = "Self::check_params", max_dimensions = 2)
 )]
 pub struct PhasorNoise {
     #[cfg(feature = "gpu")]
     gpu: Option<PhasorNoiseGpu>,
 }
 
+/// Snapshot of the phasor noise parameters for CPU evaluation
+struct PhasorNoiseCpuParams {
+    sampler: LatticeSampler,
+    noise_profile: i32,
+    kernels: KernelParams,
+}
+
 impl PhasorNoise {
     pub fn new() -> Self {
         Self::default()
     }
+
+    fn check_params(params: &PhasorNoiseParams) -> txkit_core::Result<()> {
+        let period: [f32; 2] = params.period.into();
+        check_period(&period)
+    }
+
+    fn init_cpu(
+        &self,
+        dim: ImageDim,
+        params: &PhasorNoiseParams,
+    ) -> txkit_core::Result<PhasorNoiseCpuParams> {
+        use txkit_core::io::cpu::CpuImageIoExt;
+
+        Ok(PhasorNoiseCpuParams {
+            sampler: LatticeSampler::new(
+                dim,
+                params.global_seed,
+                params.scale,
+                params.stats_mode,
+                params.stats_look_at,
+            )
+            .with_period(params.period.extend(0.).extend(0.)),
+            noise_profile: params.noise_profile,
+            kernels: KernelParams {
+                scale: params.scale,
+                noise_lookahead: params.noise_lookahead,
+                kernel_count: params.kernel_count,
+                noise_weights: params.noise_weights,
+                noise_point_distribution: params.noise_point_distribution,
+                noise_frequency: params.noise_frequency,
+                noise_angle: params.noise_angle,
+                jitter_amount: params.jitter_amount,
+                jitter_max: params.jitter_max,
+                // frequency_orientation_field is bound to texture unit 0 in kernels.glsl
+                frequency_orientation_field: params.io.texture_sampler(0)?,
+            },
+        })
+    }
+
+    fn phasor(params: &PhasorNoiseCpuParams, x: Vector2<f32>, k: &Kernel) -> Vector2<f32> {
+        let scale = params.sampler.scale;
+
+        if params.noise_profile == PHASOR_PROFILE_IMPULSES {
+            let a = 0.125 * params.sampler.resolution.x as f32 / scale;
+            return (-M_PI * a * a * x.dot(x)).exp() * cgmath::vec2(1., k.weight);
+        }
+
+        let b = (1. / scale) * (1. / scale) * M_PI;
+
+        let gaus = (-b * x.dot(x)).exp();
+        let osc = 2. * M_PI * x.dot(k.angle) + k.phase;
+
+        k.weight * gaus * cgmath::vec2(osc.cos(), osc.sin())
+    }
+
+    fn noise_cell(
+        params: &PhasorNoiseCpuParams,
+        pos: Vector2<f32>,
+        cell: Vector2<i32>,
+        seed: u32,
+    ) -> Vector2<f32> {
+        let scale = params.sampler.scale;
+        let mut res = cgmath::vec2(0., 0.);
+
+        // Seed the random number generator
+        let mut rng = Lcg::new(hash_cell(to_uvec2(cell), seed));
+
+        // Compute impulse count
+        let ps = params.kernels.init(&mut rng);
+
+        // Fixed number of impulses per cell
+        for i in 0..ps.count {
+            // Sample kernel parameters
+            let fo = params.kernels.field(pos, cell);
+
+            // Generate a kernel
+            let k = params.kernels.next(&mut rng, &ps, i, fo);
+
+            // Compute contribution
+            res += Self::phasor(params, scale * (pos - k.pos), &k);
+        }
+
+        res
+    }
+
+    fn noise(params: &PhasorNoiseCpuParams, s: LatticeNoiseSample) -> Vector3<f32> {
+        // Compute complex phasor value
+        let mut res = cgmath::vec2(0., 0.);
+
+        for cx in
+            s.cell.x - params.kernels.noise_lookahead..=s.cell.x + params.kernels.noise_lookahead
+        {
+            for cy in s.cell.y - params.kernels.noise_lookahead
+                ..=s.cell.y + params.kernels.noise_lookahead
+            {
+                // Make sure the noise tiles correctly
+                let looped_cell = params.sampler.lattice_loop_signed(cgmath::vec2(cx, cy));
+
+                // Compute the position of the current point relative to the target cell
+                let position =
+                    s.position - cgmath::vec2((cx - s.cell.x) as f32, (cy - s.cell.y) as f32);
+
+                // Add contribution of target noise cell
+                res += Self::noise_cell(params, position, looped_cell, s.seed);
+            }
+        }
+
+        // Apply profile
+        let kernel_count = params.kernels.kernel_count as f32;
+        match params.noise_profile {
+            PHASOR_PROFILE_COMPLEX => {
+                cgmath::vec3(to01(res.x / kernel_count), to01(res.y / kernel_count), 0.)
+            }
+            PHASOR_PROFILE_REAL => {
+                let v = to01(res.x / kernel_count);
+                cgmath::vec3(v, v, v)
+            }
+            PHASOR_PROFILE_IMAG => {
+                let v = to01(res.y / kernel_count);
+                cgmath::vec3(v, v, v)
+            }
+            PHASOR_PROFILE_IMPULSES => cgmath::vec3(
+                res.x,
+                res.y,
+                if s.position.x.min(s.position.y)
+                    < params.sampler.scale / params.sampler.resolution.x as f32
+                {
+                    1.
+                } else {
+                    0.
+                },
+            ),
+            PHASOR_PROFILE_SIN | PHASOR_PROFILE_SAW => {
+                let ph = res.x.atan2(res.y);
+
+                let v = if params.noise_profile == PHASOR_PROFILE_SIN {
+                    to01(ph.sin())
+                } else {
+                    modulo(ph + M_PI, M_2PI) / M_2PI
+                };
+
+                cgmath::vec3(v, v, v)
+            }
+            // Invalid enum value
+            _ => cgmath::vec3(1., 0., 1.),
+        }
+    }
+
+    fn compute_idx(frag: Fragment, dim: ImageDim, params: &PhasorNoiseCpuParams) -> f32 {
+        if frag.channel == 3 {
+            return 1.0;
+        }
+
+        let uv = frag_uv(frag, dim);
+        Self::noise(
+            params,
+            params.sampler.sample(uv.truncate(), LATTICE_MODE_RECT_2D),
+        )[frag.channel]
+    }
 }
@@ -0,0 +1,78 @@
+//! Intermediate images for methods built on top of other methods
+
+use ndarray::{Array4, Zip};
+
+use txkit_core::context::Context;
+use txkit_core::image::{Image, ImageDataType, IntoElementType};
+use txkit_core::{Error, Result};
+
+/// Create a Float32 image on the same backend and with the same shape and region as `tgt`
+pub(crate) fn new_scratch_image(ctx: &Context, tgt: &Image) -> Result<Image> {
+    let mut image = new_image_like(ctx, tgt)?;
+    image.set_region(tgt.region())?;
+    Ok(image)
+}
+
+/// Create a Float32 image on the same backend and with the same shape as `tgt`
+#[cfg_attr(not(feature = "gpu"), allow(unused_variables))]
+fn new_image_like(ctx: &Context, tgt: &Image) -> Result<Image> {
+    let dim = tgt.dim();
+
+    #[cfg(feature = "gpu")]
+    if let Some(gpu_tgt) = tgt.as_gpu_image() {
+        return Ok(match gpu_tgt.target() {
+            tinygl::gl::TEXTURE_1D => Image::new_gpu_1d(dim, ImageDataType::Float32, ctx)?,
+            tinygl::gl::TEXTURE_3D => Image::new_gpu_3d(dim, ImageDataType::Float32, ctx)?,
+            _ => Image::new_gpu_2d(dim, ImageDataType::Float32, ctx)?,
+        });
+    }
+
+    Ok(Image::new_cpu(dim, ImageDataType::Float32))
+}
+
+/// Element types of the targets of methods computing normalized values on the host
+///
+/// `Int32` images hold unnormalized values, see [`IntoElementType`].
+pub(crate) fn float_element_types() -> Vec<ImageDataType> {
+    ImageDataType::ALL
+        .iter()
+        .copied()
+        .filter(|t| !t.is_integer())
+        .collect()
+}
+
+/// Check that `tgt` can hold the normalized values computed by a method
+pub(crate) fn check_float_target(tgt: &Image) -> Result<()> {
+    if tgt.element_type().is_integer() {
+        return Err(Error::FormatNotSupported);
+    }
+
+    Ok(())
+}
+
+/// Write values computed on the host to `tgt`, and upload them if needed
+pub(crate) fn write_result(tgt: &mut Image, result: &Array4<f32>) -> Result<()> {
+    {
+        let mut data = tgt.data_mut()?;
+
+        if let Some(mut tgt) = data.as_f32_nd_array_mut() {
+            tgt.assign(result);
+        } else if let Some(mut tgt) = data.as_u8_nd_array_mut() {
+            Zip::from(&mut tgt)
+                .and(result)
+                .for_each(|d, s| *d = s.into_u8());
+        } else if let Some(mut tgt) = data.as_u16_nd_array_mut() {
+            Zip::from(&mut tgt)
+                .and(result)
+                .for_each(|d, s| *d = s.into_u16());
+        } else if let Some(mut tgt) = data.as_f16_nd_array_mut() {
+            Zip::from(&mut tgt)
+                .and(result)
+                .for_each(|d, s| *d = s.into_f16());
+        } else {
+            return Err(Error::FormatNotSupported);
+        }
+    }
+
+    tgt.upload()
+}
@@ -1,6 +1,16 @@
+use cgmath::InnerSpace;
+use txkit_core::image::{Fragment, ImageDim};
 use txkit_impl::{Method, ParamsFor};
 
+use super::lattice::*;
+use crate::cpu::*;
+
 #[derive(Clone, Copy, PartialEq, ParamsFor)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
 #[repr(C)]
 #[txkit(program = "SimplexNoiseProgram")]
 pub struct SimplexNoiseParams {
@@ -11,7 +21,14 @@ pub struct SimplexNoiseParams {
     /// stats mode (0: normal, 1: process, 2: lookat)
     pub stats_mode: i32,
     /// look-at parameter (if stats_mode == lookat) in [0, 1]^2
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_vector"))]
     pub stats_look_at: cgmath::Vector2<f32>,
+    /// output mode (0: value, 1: value and derivatives)
+    pub noise_output: i32,
+    /// lattice dimensions (0: 2D, 1: 3D, 2: 4D)
+    pub noise_dimensions: i32,
+    /// time coordinate of the 4D lattice, in lattice cells
+    pub time: f32,
 }
 
 impl Default for SimplexNoiseParams {
@@ -21,10 +38,17 @@ impl Default for SimplexNoiseParams {
             scale: 32.,
             stats_mode: 0,
             stats_look_at: cgmath::vec2(0., 0.),
+            noise_output: LATTICE_OUTPUT_VALUE,
+            noise_dimensions: LATTICE_DIMENSIONS_2D,
+            time: 0.,
         }
     }
 }
 
+/// Simplex noise, summing random gradients on a simplex lattice
+///
+/// Unlike value and gradient noise, simplex noise does not tile: its skewed lattice cannot loop
+/// along the image axes.
 #[derive(Default, Method)]
 #[txkit(
     gpu(
@@ -32,6 +56,7 @@ impl Default for SimplexNoiseParams {
         program("shaders/quad.vert", "shaders/simplex_noise.frag"),
         method(run = "program", params = "SimplexNoiseParams")
     ),
+    cpu(method(iter = "Self::compute_idx", params = "SimplexNoiseParams")),
     method()
 )]
 pub struct SimplexNoise {
@@ -43,4 +68,62 @@ impl SimplexNoise {
     pub fn new() -> Self {
         Self::default()
     }
+
+    fn noisehash(p: cgmath::Vector2<u32>, seed: u32) -> cgmath::Vector2<f32> {
+        tofloat11_2(hash2(p, seed))
+    }
+
+    fn noise(s: LatticeNoiseSample) -> f32 {
+        let cell = to_uvec2(s.cell);
+        let offset = |x: u32, y: u32| cgmath::vec2(cell.x.wrapping_add(x), cell.y.wrapping_add(y));
+
+        let a = s.position;
+        let m = if a.x < a.y { 0. } else { 1. };
+        let o = cgmath::vec2(m, 1.0 - m);
+        let b = a - o + cgmath::vec2(SIMPLEX_K2, SIMPLEX_K2);
+        let c = a - cgmath::vec2(1.0, 1.0) + cgmath::vec2(2.0 * SIMPLEX_K2, 2.0 * SIMPLEX_K2);
+
+        let h = |x: cgmath::Vector2<f32>| {
+            let h = (0.5 - x.dot(x)).max(0.0);
+            h * h * h * h
+        };
+
+        let n = cgmath::vec3(
+            h(a) * a.dot(Self::noisehash(offset(0, 0), s.seed)),
+            h(b) * b.dot(Self::noisehash(offset(o.x as u32, o.y as u32), s.seed)),
+            h(c) * c.dot(Self::noisehash(offset(1, 1), s.seed)),
+        );
+
+        n.dot(cgmath::vec3(71.28259, 71.28259, 71.28259))
+    }
+
+    fn compute_idx(frag: Fragment, dim: ImageDim, params: &SimplexNoiseParams) -> f32 {
+        let sampler = LatticeSampler::new(
+            dim,
+            params.global_seed,
+            params.scale,
+            params.stats_mode,
+            params.stats_look_at,
+        );
+
+        let uv = frag_uv(frag, dim);
+        let s = sampler.sample(uv.truncate(), LATTICE_MODE_SIMPLEX_2D);
+        let p = uv * params.scale;
+
+        let n = match params.noise_dimensions {
+            LATTICE_DIMENSIONS_3D => simplex_noised3(p, params.global_seed),
+            LATTICE_DIMENSIONS_4D => simplex_noised4(p.extend(params.time), params.global_seed),
+            _ if params.noise_output == LATTICE_OUTPUT_DERIVATIVES => simplex_noised(s).extend(0.),
+            _ => cgmath::vec4(Self::noise(s), 0., 0., 0.),
+        };
+
+        if params.noise_output == LATTICE_OUTPUT_DERIVATIVES {
+            // Value and derivatives of the output with respect to lattice coordinates
+            [to01(n.x), 0.5 * n.y, 0.5 * n.z, 0.5 * n.w][frag.channel]
+        } else if frag.channel == 3 {
+            1.0
+        } else {
+            to01(n.x)
+        }
+    }
 }
@@ -1,6 +1,15 @@
+use txkit_core::image::{Fragment, ImageDim};
 use txkit_impl::{Method, ParamsFor};
 
+use super::lattice::*;
+use crate::cpu::*;
+
 #[derive(Clone, Copy, PartialEq, ParamsFor)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
 #[repr(C)]
 #[txkit(program = "ValueNoiseProgram")]
 pub struct ValueNoiseParams {
@@ -11,7 +20,17 @@ pub struct ValueNoiseParams {
     /// stats mode (0: normal, 1: process, 2: lookat)
     pub stats_mode: i32,
     /// look-at parameter (if stats_mode == lookat) in [0, 1]^2
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_vector"))]
     pub stats_look_at: cgmath::Vector2<f32>,
+    /// output mode (0: value, 1: value and derivatives)
+    pub noise_output: i32,
+    /// lattice dimensions (0: 2D, 1: 3D, 2: 4D)
+    pub noise_dimensions: i32,
+    /// time coordinate of the 4D lattice, in lattice cells
+    pub time: f32,
+    /// lattice period along (x, y, z, time), in whole lattice cells (0: loop at the scale)
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_vector"))]
+    pub period: cgmath::Vector4<f32>,
 }
 
 impl Default for ValueNoiseParams {
@@ -21,10 +40,15 @@ impl Default for ValueNoiseParams {
             scale: 32.,
             stats_mode: 0,
             stats_look_at: cgmath::vec2(0., 0.),
+            noise_output: LATTICE_OUTPUT_VALUE,
+            noise_dimensions: LATTICE_DIMENSIONS_2D,
+            time: 0.,
+            period: cgmath::vec4(0., 0., 0., 0.),
         }
     }
 }
 
+/// Value noise, interpolating random values on a lattice
 #[derive(Default, Method)]
 #[txkit(
     gpu(
@@ -32,7 +56,8 @@ impl Default for ValueNoiseParams {
         program("shaders/quad.vert", "shaders/value_noise.frag"),
         method(run = "program", params = "ValueNoiseParams")
     ),
-    method()
+    cpu(method(iter = "Self::compute_idx", params = "ValueNoiseParams")),
+    method(check = "Self::check_params")
 )]
 pub struct ValueNoise {
     #[cfg(feature = "gpu")]
@@ -43,4 +68,68 @@ impl ValueNoise {
     pub fn new() -> Self {
         Self::default()
     }
+
+    fn check_params(params: &ValueNoiseParams) -> txkit_core::Result<()> {
+        let period: [f32; 4] = params.period.into();
+        check_period(&period)
+    }
+
+    fn noisehash(p: cgmath::Vector2<u32>, seed: u32) -> f32 {
+        tofloat(hash_cell(p, seed))
+    }
+
+    fn noise(sampler: &LatticeSampler, s: LatticeNoiseSample) -> f32 {
+        let i = to_uvec2(s.cell);
+        let f = s.position;
+
+        let u = cgmath::vec2(f.x * f.x * (3. - 2. * f.x), f.y * f.y * (3. - 2. * f.y));
+
+        let corner = |x: u32, y: u32| {
+            Self::noisehash(
+                sampler.lattice_loop(cgmath::vec2(i.x.wrapping_add(x), i.y.wrapping_add(y))),
+                s.seed,
+            )
+        };
+
+        mix(
+            mix(corner(0, 0), corner(1, 0), u.x),
+            mix(corner(0, 1), corner(1, 1), u.x),
+            u.y,
+        ) * 0.7358206
+    }
+
+    fn compute_idx(frag: Fragment, dim: ImageDim, params: &ValueNoiseParams) -> f32 {
+        let sampler = LatticeSampler::new(
+            dim,
+            params.global_seed,
+            params.scale,
+            params.stats_mode,
+            params.stats_look_at,
+        )
+        .with_period(params.period);
+
+        let uv = frag_uv(frag, dim);
+        let s = sampler.sample(uv.truncate(), LATTICE_MODE_RECT_2D);
+        let p = uv * params.scale;
+
+        let n = match params.noise_dimensions {
+            LATTICE_DIMENSIONS_3D => value_noised3(&sampler, p, params.global_seed),
+            LATTICE_DIMENSIONS_4D => {
+                value_noised4(&sampler, p.extend(params.time), params.global_seed)
+            }
+            _ if params.noise_output == LATTICE_OUTPUT_DERIVATIVES => {
+                value_noised(&sampler, s).extend(0.)
+            }
+            _ => cgmath::vec4(Self::noise(&sampler, s), 0., 0., 0.),
+        };
+
+        if params.noise_output == LATTICE_OUTPUT_DERIVATIVES {
+            // Value and derivatives of the output with respect to lattice coordinates
+            [n.x, n.y, n.z, n.w][frag.channel]
+        } else if frag.channel == 3 {
+            1.0
+        } else {
+            n.x
+        }
+    }
 }
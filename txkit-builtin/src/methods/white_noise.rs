@@ -1,7 +1,14 @@
-use txkit_core::image::ImageDim;
+use txkit_core::image::{Fragment, ImageDim};
 use txkit_impl::{Method, ParamsFor};
 
+use crate::cpu::*;
+
 #[derive(Default, Clone, Copy, PartialEq, ParamsFor)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
 #[repr(C)]
 #[txkit(program = "WhiteNoiseProgram")]
 pub struct WhiteNoiseParams {
@@ -9,6 +16,7 @@ pub struct WhiteNoiseParams {
     pub global_seed: u32,
 }
 
+/// White noise, independent for every pixel and channel
 #[derive(Default, Method)]
 #[txkit(
     gpu(
@@ -29,21 +37,19 @@ impl WhiteNoise {
         Self::default()
     }
 
-    fn compute_idx(
-        (k, j, i, l): (usize, usize, usize, usize),
-        sz: ImageDim,
-        params: &WhiteNoiseParams,
-    ) -> f32 {
-        let mut x = ((i + j * sz.width + k * sz.width * sz.height) * sz.channels
-            + l
-            + params.global_seed as usize) as u32;
-
-        // Hash
-        x = ((x >> 16) ^ x).wrapping_mul(0x45d9f3bu32);
-        x = ((x >> 16) ^ x).wrapping_mul(0x45d9f3bu32);
-        x = (x >> 16) ^ x;
+    fn compute_idx(frag: Fragment, sz: ImageDim, params: &WhiteNoiseParams) -> f32 {
+        let l = frag.channel;
+        // Same 64-bit pixel index as white_noise.frag, so both backends agree. Pixels outside of
+        // the full image wrap like GLSL integer conversions.
+        let [i, j, k] = frag.pixel().map(|p| p as i32 as u32);
+        let d = add64(cgmath::vec2(0, i), cgmath::vec2(0, params.global_seed));
+        let idx = mul64(
+            cgmath::vec4(0, 0, d.x, d.y),
+            cgmath::vec2(0, sz.width as u32),
+            cgmath::vec2(0, j.wrapping_add(k.wrapping_mul(sz.height as u32))),
+        );
+        let idx = shl64(cgmath::vec2(idx.z, idx.w), 2);
 
-        // Convert to float
-        f32::from_bits(0x7fu32 << 23 | x >> 9) - 1.0f32
+        tofloat(hash64(cgmath::vec2(idx.x, idx.y | l as u32)).x)
     }
 }
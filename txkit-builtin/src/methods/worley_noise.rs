@@ -0,0 +1,230 @@
+use cgmath::{InnerSpace, Vector2};
+use txkit_core::image::{Fragment, Image, ImageDataType, ImageDim, IntoElementType};
+use txkit_core::{Error, Result};
+use txkit_impl::{Method, ParamsFor};
+
+use crate::cpu::*;
+
+/// Worley: distance to the closest point
+pub const WORLEY_OUTPUT_F1: i32 = 0;
+/// Worley: distance to the second closest point
+pub const WORLEY_OUTPUT_F2: i32 = 1;
+/// Worley: difference between the second and first closest distances
+pub const WORLEY_OUTPUT_F2_MINUS_F1: i32 = 2;
+/// Worley: random identifier of the closest point, in [0, 1], or as-is in `Int32` images
+pub const WORLEY_OUTPUT_CELL_ID: i32 = 3;
+
+/// Worley: euclidean distance
+pub const WORLEY_METRIC_EUCLIDEAN: i32 = 0;
+/// Worley: manhattan distance
+pub const WORLEY_METRIC_MANHATTAN: i32 = 1;
+/// Worley: chebyshev distance
+pub const WORLEY_METRIC_CHEBYSHEV: i32 = 2;
+
+#[derive(Clone, Copy, PartialEq, ParamsFor)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+#[repr(C)]
+#[txkit(program = "WorleyNoiseProgram")]
+pub struct WorleyNoiseParams {
+    /// pseudo-random seed
+    pub global_seed: u32,
+    /// lattice scale (size in pixels)
+    pub scale: f32,
+    /// stats mode (0: normal, 1: process, 2: lookat)
+    pub stats_mode: i32,
+    /// look-at parameter (if stats_mode == lookat) in [0, 1]^2
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_vector"))]
+    pub stats_look_at: cgmath::Vector2<f32>,
+
+    /// cell lookahead: number of cells to check for points
+    pub noise_lookahead: i32,
+    /// point count per cell
+    pub point_count: i32,
+    /// noise output function
+    pub noise_output: i32,
+    /// distance metric
+    pub distance_metric: i32,
+    /// point distribution (same values as the phasor noise point distribution)
+    pub noise_point_distribution: i32,
+
+    /// jittering amount, 0 = no random, 1 = full subcell random
+    pub jitter_amount: f32,
+    /// max jittering subcells, 0 = no limit
+    pub jitter_max: i32,
+}
+
+impl Default for WorleyNoiseParams {
+    fn default() -> Self {
+        Self {
+            global_seed: 0,
+            scale: 32.,
+            stats_mode: 0,
+            stats_look_at: cgmath::vec2(0., 0.),
+            noise_lookahead: 1,
+            point_count: 1,
+            noise_output: WORLEY_OUTPUT_F1,
+            distance_metric: WORLEY_METRIC_EUCLIDEAN,
+            noise_point_distribution: POINTS_STRAT_POISSON,
+            jitter_amount: 1.,
+            jitter_max: 0,
+        }
+    }
+}
+
+/// Worley (cellular) noise, from the distances to random feature points
+#[derive(Default, Method)]
+#[txkit(
+    gpu(
+        name = "WorleyNoiseGpu",
+        program("shaders/quad.vert", "shaders/worley_noise.frag"),
+        method(run = "program", params = "WorleyNoiseParams")
+    ),
+    cpu(method(iter = "Self::compute_idx", params = "WorleyNoiseParams")),
+    method(check_target = "Self::check_target", max_dimensions = 2)
+)]
+pub struct WorleyNoise {
+    #[cfg(feature = "gpu")]
+    gpu: Option<WorleyNoiseGpu>,
+}
+
+/// Value of a channel of Worley noise
+#[derive(Clone, Copy)]
+enum WorleyValue {
+    /// Normalized value, such as a distance
+    Normalized(f32),
+    /// Random identifier of the closest point
+    CellId(u32),
+}
+
+impl IntoElementType for WorleyValue {
+    const INTEGER: bool = true;
+
+    fn into_element_type() -> ImageDataType {
+        ImageDataType::Float32
+    }
+
+    fn into_u8(&self) -> u8 {
+        self.into_f32().into_u8()
+    }
+
+    fn into_f32(&self) -> f32 {
+        match self {
+            Self::Normalized(v) => *v,
+            Self::CellId(id) => tofloat(*id),
+        }
+    }
+
+    fn into_i32(&self) -> i32 {
+        match self {
+            Self::Normalized(v) => *v as i32,
+            // Keep all the bits of the identifier
+            Self::CellId(id) => *id as i32,
+        }
+    }
+}
+
+impl WorleyNoise {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Check that `tgt` can hold the output selected by `params`
+    ///
+    /// Only cell identifiers are integers, other outputs are normalized values which `Int32`
+    /// targets would truncate.
+    fn check_target(tgt: &Image, params: &WorleyNoiseParams) -> Result<()> {
+        if tgt.element_type().is_integer() && params.noise_output != WORLEY_OUTPUT_CELL_ID {
+            return Err(Error::FormatNotSupported);
+        }
+
+        Ok(())
+    }
+
+    fn distance_metric(params: &WorleyNoiseParams, d: Vector2<f32>) -> f32 {
+        match params.distance_metric {
+            WORLEY_METRIC_MANHATTAN => d.x.abs() + d.y.abs(),
+            WORLEY_METRIC_CHEBYSHEV => d.x.abs().max(d.y.abs()),
+            _ => d.magnitude(),
+        }
+    }
+
+    fn noise(
+        sampler: &LatticeSampler,
+        params: &WorleyNoiseParams,
+        s: LatticeNoiseSample,
+        channel: usize,
+    ) -> WorleyValue {
+        // Distances to the closest and second closest points
+        let mut f = cgmath::vec2(1e20f32, 1e20);
+        // Identifier of the closest point
+        let mut id = 0u32;
+
+        for cx in s.cell.x - params.noise_lookahead..=s.cell.x + params.noise_lookahead {
+            for cy in s.cell.y - params.noise_lookahead..=s.cell.y + params.noise_lookahead {
+                // Make sure the noise tiles correctly
+                let looped_cell = sampler.lattice_loop_signed(cgmath::vec2(cx, cy));
+
+                // Compute the position of the current point relative to the target cell
+                let position =
+                    s.position - cgmath::vec2((cx - s.cell.x) as f32, (cy - s.cell.y) as f32);
+
+                // Generate the points of the cell
+                let cell_seed = hash_cell(to_uvec2(looped_cell), s.seed);
+                let mut rng = Lcg::new(cell_seed);
+                let ps = PointSet::new(
+                    &mut rng,
+                    params.noise_point_distribution,
+                    params.point_count,
+                    params.jitter_amount,
+                    params.jitter_max,
+                );
+
+                for i in 0..ps.count {
+                    let d = Self::distance_metric(params, position - ps.next(&mut rng, i));
+
+                    if d < f.x {
+                        f = cgmath::vec2(d, f.x);
+                        id = hash(cell_seed.wrapping_add(i as u32));
+                    } else if d < f.y {
+                        f.y = d;
+                    }
+                }
+            }
+        }
+
+        WorleyValue::Normalized(match params.noise_output {
+            WORLEY_OUTPUT_F1 => f.x,
+            WORLEY_OUTPUT_F2 => f.y,
+            WORLEY_OUTPUT_F2_MINUS_F1 => f.y - f.x,
+            WORLEY_OUTPUT_CELL_ID => return WorleyValue::CellId(id),
+            // Invalid enum value, shown in magenta
+            _ => return WorleyValue::Normalized(if channel == 1 { 0. } else { 1. }),
+        })
+    }
+
+    fn compute_idx(frag: Fragment, dim: ImageDim, params: &WorleyNoiseParams) -> WorleyValue {
+        if frag.channel == 3 {
+            return WorleyValue::Normalized(1.0);
+        }
+
+        let sampler = LatticeSampler::new(
+            dim,
+            params.global_seed,
+            params.scale,
+            params.stats_mode,
+            params.stats_look_at,
+        );
+
+        let uv = frag_uv(frag, dim);
+        Self::noise(
+            &sampler,
+            params,
+            sampler.sample(uv.truncate(), LATTICE_MODE_RECT_2D),
+            frag.channel,
+        )
+    }
+}
@@ -0,0 +1,21 @@
+//! Serialization of the texture inputs of method parameters
+//!
+//! Images bound to the inputs only exist at runtime, so they cannot be written to presets. Used
+//! on `ImageIo` fields with `skip_serializing_if = "crate::serde_io::is_unbound"` and
+//! `serialize_with = "crate::serde_io::serialize"`, unbound inputs are left out of presets and
+//! serializing parameters with bound images fails, instead of silently dropping the bindings.
+//! Deserialized parameters always have unbound inputs.
+
+use serde::{ser::Error, Serializer};
+
+use txkit_core::io::ImageIo;
+
+pub fn is_unbound(io: &ImageIo) -> bool {
+    io.is_unbound()
+}
+
+pub fn serialize<S: Serializer>(_io: &ImageIo, _serializer: S) -> Result<S::Ok, S::Error> {
+    Err(S::Error::custom(
+        "parameters with images bound to their texture inputs cannot be serialized, unbind them first",
+    ))
+}
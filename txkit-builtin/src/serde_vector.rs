@@ -0,0 +1,47 @@
+//! Serialization of `cgmath` vector parameters as arrays of components
+//!
+//! Used with `#[serde(with = "crate::serde_vector")]`, so vectors are written as `[x, y]` instead of
+//! `{ x = .., y = .. }` tables, which TOML does not allow before the other fields of a struct.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Vector type with a fixed-size array of components
+pub trait VectorArray: Copy {
+    type Array: Serialize + for<'de> Deserialize<'de>;
+
+    fn to_array(self) -> Self::Array;
+    fn from_array(array: Self::Array) -> Self;
+}
+
+macro_rules! impl_vector_array {
+    ($t:ident, $n:literal) => {
+        impl VectorArray for cgmath::$t<f32> {
+            type Array = [f32; $n];
+
+            fn to_array(self) -> Self::Array {
+                self.into()
+            }
+
+            fn from_array(array: Self::Array) -> Self {
+                array.into()
+            }
+        }
+    };
+}
+
+impl_vector_array!(Vector2, 2);
+impl_vector_array!(Vector3, 3);
+impl_vector_array!(Vector4, 4);
+
+pub fn serialize<V: VectorArray, S: Serializer>(
+    vector: &V,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    vector.to_array().serialize(serializer)
+}
+
+pub fn deserialize<'de, V: VectorArray, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<V, D::Error> {
+    V::Array::deserialize(deserializer).map(V::from_array)
+}
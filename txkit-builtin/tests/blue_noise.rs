@@ -0,0 +1,33 @@
+//! Blue noise threshold maps
+
+use txkit_core::image::{ImageDataType, ImageDim};
+
+mod common;
+use common::*;
+
+#[test]
+fn empty_images() {
+    for dim in [
+        ImageDim::new(0, 0, 4),
+        ImageDim::new(0, 16, 4),
+        ImageDim::new(16, 0, 4),
+        ImageDim::new(16, 16, 0),
+    ] {
+        assert_eq!(
+            render_cpu_dim("blue_noise", dim, ImageDataType::Float32).len(),
+            0
+        );
+    }
+}
+
+#[test]
+fn ranks_are_a_permutation() {
+    let mut ranks: Vec<_> = render_cpu("blue_noise")
+        .index_axis(ndarray::Axis(3), 0)
+        .iter()
+        .map(|x| (x * (SIZE * SIZE) as f32) as usize)
+        .collect();
+    ranks.sort_unstable();
+
+    assert!(ranks.into_iter().eq(0..SIZE * SIZE));
+}
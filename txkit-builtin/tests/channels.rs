@@ -0,0 +1,71 @@
+//! Renders of the built-in methods with more channels than their shaders output
+
+use ndarray::{s, Array4};
+
+use txkit_core::image::{ImageDataType, ImageDim};
+
+mod common;
+use common::*;
+
+/// Number of channels of the wide renders
+const CHANNELS: usize = 8;
+
+/// Check that `wide` extends the four-channel render `reference` with zeros
+fn check_extends(reference: &Array4<f32>, wide: &Array4<f32>) -> Result<(), String> {
+    if wide.slice(s![.., .., .., ..4]) != reference {
+        return Err("first four channels differ".to_owned());
+    }
+
+    if wide.slice(s![.., .., .., 4..]).iter().any(|x| *x != 0.) {
+        return Err("extra channels are not zero".to_owned());
+    }
+
+    Ok(())
+}
+
+#[test]
+fn cpu_extra_channels_are_zero() {
+    check_methods(|name| {
+        let reference = render_cpu(name);
+        let wide = render_cpu_dim(
+            name,
+            ImageDim::new(SIZE, SIZE, CHANNELS),
+            ImageDataType::Float32,
+        );
+
+        check_extends(&reference, &wide)
+    });
+}
+
+#[cfg(feature = "gpu")]
+#[test]
+fn gpu_extra_channels_are_zero() {
+    use txkit_core::image::Image;
+
+    let mut ctx = match gpu_context() {
+        Some(ctx) => ctx,
+        None => return,
+    };
+
+    let registry = registry();
+
+    check_methods(|name| {
+        if !registry.info(name).map_or(true, |info| info.gpu) {
+            return Ok(());
+        }
+
+        let img = Image::new_gpu_2d(dim(), ImageDataType::Float32, &ctx)
+            .expect("failed to create GPU image");
+        let reference = render(&mut ctx, img, name);
+
+        let img = Image::new_gpu_2d(
+            ImageDim::new(SIZE, SIZE, CHANNELS),
+            ImageDataType::Float32,
+            &ctx,
+        )
+        .expect("failed to create GPU image");
+        let wide = render(&mut ctx, img, name);
+
+        check_extends(&reference, &wide)
+    });
+}
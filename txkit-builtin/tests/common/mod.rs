@@ -0,0 +1,236 @@
+//! Shared fixtures for the tests which render every built-in method
+
+// Each test binary only uses some of the fixtures
+#![allow(dead_code)]
+
+use std::any::Any;
+use std::path::PathBuf;
+
+use ndarray::Array4;
+
+use txkit_core::context::Context;
+use txkit_core::image::{Image, ImageDataType, ImageDim, IntoElementType};
+use txkit_core::method::MethodRegistry;
+
+/// Size of the rendered test images
+pub const SIZE: usize = 64;
+
+/// Dimensions of the rendered test images
+pub fn dim() -> ImageDim {
+    ImageDim::new(SIZE, SIZE, 4)
+}
+
+pub fn registry() -> MethodRegistry {
+    txkit_builtin::methods::new_registry()
+}
+
+/// Render the method `name` with its default parameters into `img`, and read back the result as
+/// floats
+pub fn render(ctx: &mut Context, img: Image, name: &str) -> Array4<f32> {
+    render_params(ctx, img, name, None)
+}
+
+/// Render the method `name` with `params` into `img`, and read back the result as floats
+pub fn render_params(
+    ctx: &mut Context,
+    mut img: Image,
+    name: &str,
+    params: Option<&dyn Any>,
+) -> Array4<f32> {
+    let mut method = registry()
+        .build(name)
+        .unwrap_or_else(|| panic!("{}: method is not registered", name));
+
+    method
+        .compute(ctx, &mut img, params)
+        .unwrap_or_else(|e| panic!("{}: failed to compute: {}", name, e));
+    img.download()
+        .unwrap_or_else(|e| panic!("{}: failed to download: {}", name, e));
+
+    let data = img.data().expect("failed to map image data");
+    let result = if let Some(data) = data.as_f32_nd_array() {
+        data.to_owned()
+    } else if let Some(data) = data.as_u8_nd_array() {
+        data.mapv(|x| x.into_f32())
+    } else if let Some(data) = data.as_u16_nd_array() {
+        data.mapv(|x| x.into_f32())
+    } else if let Some(data) = data.as_f16_nd_array() {
+        data.mapv(|x| x.into_f32())
+    } else if let Some(data) = data.as_i32_nd_array() {
+        data.mapv(|x| x.into_f32())
+    } else {
+        panic!("{}: unexpected element type", name)
+    };
+    result
+}
+
+/// Render the method `name` into a new image of dimensions `dim` on the CPU
+pub fn render_cpu_dim(name: &str, dim: ImageDim, element_type: ImageDataType) -> Array4<f32> {
+    let mut ctx = Context::new_cpu().expect("failed to create CPU context");
+    render(&mut ctx, Image::new_cpu(dim, element_type), name)
+}
+
+/// Render the method `name` with `params` into a new Float32 image of dimensions `dim` on the CPU
+pub fn render_cpu_params(name: &str, dim: ImageDim, params: &dyn Any) -> Array4<f32> {
+    let mut ctx = Context::new_cpu().expect("failed to create CPU context");
+    render_params(
+        &mut ctx,
+        Image::new_cpu(dim, ImageDataType::Float32),
+        name,
+        Some(params),
+    )
+}
+
+/// Render the method `name` into a new Float32 test image on the CPU
+pub fn render_cpu(name: &str) -> Array4<f32> {
+    render_cpu_dim(name, dim(), ImageDataType::Float32)
+}
+
+/// Run `check` on every registered method, and fail with all the reported errors
+///
+/// `check` returns `Err` with a description of the failure, which is prefixed with the method
+/// name.
+pub fn check_methods(mut check: impl FnMut(&str) -> Result<(), String>) {
+    let registry = registry();
+    let failures: Vec<_> = registry
+        .names()
+        .into_iter()
+        .filter_map(|name| check(name).err().map(|e| format!("{}: {}", name, e)))
+        .collect();
+
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+/// Maximum difference, in 8-bit steps, between a CPU render and its reference image
+const GOLDEN_MAX_STEPS: u8 = 1;
+
+fn golden_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("golden")
+        .join(format!("{}.png", name))
+}
+
+fn quantize(data: &Array4<f32>) -> Vec<u8> {
+    data.iter()
+        .map(|x| (x.clamp(0., 1.) * 255.).round() as u8)
+        .collect()
+}
+
+/// Compare a four-channel render to the reference image `tests/golden/{name}.png`
+///
+/// The layers of 3D renders are stacked vertically in the reference image. Set `TXKIT_BLESS=1`
+/// to write the reference image instead.
+pub fn check_golden(name: &str, data: &Array4<f32>) -> Result<(), String> {
+    let (depth, height, width, channels) = data.dim();
+    assert_eq!(channels, 4, "{}: reference images are RGBA", name);
+
+    let actual = quantize(data);
+    let path = golden_path(name);
+
+    if std::env::var_os("TXKIT_BLESS")
+        .filter(|v| v != "0")
+        .is_some()
+    {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        return image::save_buffer(
+            &path,
+            &actual,
+            width as u32,
+            (height * depth) as u32,
+            image::ColorType::Rgba8,
+        )
+        .map_err(|e| format!("failed to write golden image: {}", e));
+    }
+
+    let expected = image::open(&path)
+        .map_err(|e| {
+            format!(
+                "cannot load {} ({}), run with TXKIT_BLESS=1 to create it",
+                path.display(),
+                e
+            )
+        })?
+        .into_rgba8()
+        .into_raw();
+
+    if expected.len() != actual.len() {
+        return Err("golden image has the wrong size".to_owned());
+    }
+
+    let mismatches = expected
+        .iter()
+        .zip(actual.iter())
+        .filter(|(e, a)| (**e as i16 - **a as i16).abs() > GOLDEN_MAX_STEPS as i16)
+        .count();
+
+    if mismatches > 0 {
+        return Err(format!(
+            "{} values differ from {}",
+            mismatches,
+            path.display()
+        ));
+    }
+
+    Ok(())
+}
+
+/// Create a GPU context for comparing backends, preferably on a software rasterizer
+///
+/// Returns `None` if no GPU context can be created, so that the calling test is skipped.
+#[cfg(feature = "gpu")]
+pub fn gpu_context() -> Option<Context> {
+    // Prefer a software rasterizer for reproducible results, unless the caller decided otherwise
+    if std::env::var_os("LIBGL_ALWAYS_SOFTWARE").is_none() {
+        std::env::set_var("LIBGL_ALWAYS_SOFTWARE", "1");
+    }
+
+    match Context::new_gpu() {
+        Ok(ctx) => Some(ctx),
+        Err(e) => {
+            eprintln!("skipping GPU test: {}", e);
+            None
+        }
+    }
+}
+
+/// Allowed difference between the CPU and GPU renders of a method
+pub struct Tolerance {
+    /// Maximum absolute difference for any single value, except outliers
+    pub max_abs: f32,
+    /// Maximum mean absolute difference over the whole image
+    pub mean_abs: f32,
+    /// Maximum fraction of the values which may exceed `max_abs`
+    pub outliers: f32,
+}
+
+impl Tolerance {
+    /// Compare the renders of both backends
+    pub fn check(&self, cpu: &Array4<f32>, gpu: &Array4<f32>) -> Result<(), String> {
+        let mut sum_abs = 0.0f64;
+        let mut outliers = 0usize;
+        ndarray::Zip::from(cpu).and(gpu).for_each(|c, g| {
+            let d = (c - g).abs();
+            if d > self.max_abs {
+                outliers += 1;
+            }
+            sum_abs += d as f64;
+        });
+        let mean_abs = (sum_abs / cpu.len() as f64) as f32;
+        let outliers = outliers as f32 / cpu.len() as f32;
+
+        if outliers > self.outliers || mean_abs > self.mean_abs {
+            return Err(format!(
+                "{:.3}% of the values differ by more than {} (allowed {:.3}%), mean abs. \
+                 difference {} (allowed {})",
+                outliers * 100.,
+                self.max_abs,
+                self.outliers * 100.,
+                mean_abs,
+                self.mean_abs
+            ));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,144 @@
+//! Renders of the built-in methods to the other element types than Float32
+
+use ndarray::Axis;
+
+use txkit_core::context::Context;
+use txkit_core::image::{Image, ImageDataType};
+use txkit_core::Error;
+
+use txkit_builtin::methods::{WorleyNoiseParams, WORLEY_OUTPUT_CELL_ID};
+
+mod common;
+use common::*;
+
+#[test]
+fn cpu_element_types_match_float32() {
+    check_methods(|name| {
+        let reference = render_cpu(name);
+
+        // Unsigned integers hold normalized values, with a few steps of slack for methods which
+        // do not compute through floats (e.g. ranks)
+        let uint16 = render_cpu_dim(name, dim(), ImageDataType::UInt16);
+        let float16 = render_cpu_dim(name, dim(), ImageDataType::Float16);
+
+        let mut uint16_max_abs = 0.0f32;
+        let mut float16_max_rel = 0.0f32;
+        for ((r, u), f) in reference.iter().zip(uint16.iter()).zip(float16.iter()) {
+            uint16_max_abs = uint16_max_abs.max((r.clamp(0., 1.) - u).abs());
+            float16_max_rel = float16_max_rel.max((r - f).abs() / r.abs().max(1.));
+        }
+
+        if uint16_max_abs > 2e-4 {
+            return Err(format!(
+                "UInt16 render differs from Float32 by up to {}",
+                uint16_max_abs
+            ));
+        }
+
+        if float16_max_rel > 1e-3 {
+            return Err(format!(
+                "Float16 render differs from Float32 by up to {} (relative)",
+                float16_max_rel
+            ));
+        }
+
+        Ok(())
+    });
+}
+
+#[test]
+fn cpu_int32_needs_integer_values() {
+    let registry = registry();
+    let mut ctx = Context::new_cpu().expect("failed to create CPU context");
+
+    check_methods(|name| {
+        let info = registry.info(name).ok_or("no method info")?;
+        let supported = info.element_types.contains(&ImageDataType::Int32);
+
+        // Worley noise only computes integers for cell identifiers
+        let worley = WorleyNoiseParams {
+            noise_output: WORLEY_OUTPUT_CELL_ID,
+            ..Default::default()
+        };
+        let params: Option<&dyn std::any::Any> = if name == "worley_noise" {
+            Some(&worley)
+        } else {
+            None
+        };
+
+        // Normalized values would all be truncated to 0, so methods computing them reject Int32
+        let mut img = Image::new_cpu(dim(), ImageDataType::Int32);
+        match (
+            supported,
+            registry
+                .build(name)
+                .unwrap()
+                .compute(&mut ctx, &mut img, params),
+        ) {
+            (true, Ok(())) | (false, Err(Error::FormatNotSupported)) => Ok(()),
+            (supported, result) => Err(format!(
+                "Int32 support is {}, but rendering returned {:?}",
+                supported, result
+            )),
+        }
+    });
+}
+
+#[test]
+fn cpu_int32_blue_noise_ranks() {
+    let reference = render_cpu("blue_noise");
+    let ranks = render_cpu_dim("blue_noise", dim(), ImageDataType::Int32);
+    let count = (SIZE * SIZE) as f32;
+
+    // Float32 renders hold the centers of the rank intervals
+    for (r, rank) in reference.iter().zip(ranks.iter()) {
+        assert!((r * count - 0.5 - rank).abs() < 1e-2);
+    }
+
+    for channel in ranks.axis_iter(Axis(3)) {
+        let mut values: Vec<_> = channel.iter().map(|x| *x as usize).collect();
+        values.sort_unstable();
+        assert_eq!(values, (0..SIZE * SIZE).collect::<Vec<_>>());
+    }
+}
+
+#[test]
+fn cpu_int32_worley_cell_ids() {
+    let mut ctx = Context::new_cpu().expect("failed to create CPU context");
+    let params = WorleyNoiseParams {
+        noise_output: WORLEY_OUTPUT_CELL_ID,
+        ..Default::default()
+    };
+
+    let reference = render_cpu_params("worley_noise", dim(), &params);
+
+    let mut img = Image::new_cpu(dim(), ImageDataType::Int32);
+    let mut method = registry().build("worley_noise").unwrap();
+    method
+        .compute(&mut ctx, &mut img, Some(&params))
+        .expect("failed to compute");
+    let data = img.data().expect("failed to map image data");
+    let ids = data.as_i32_nd_array().expect("unexpected element type");
+
+    // Float32 renders hold the 23 high bits of the identifiers, Int32 renders all of them
+    for (r, id) in reference
+        .slice(ndarray::s![.., .., .., ..3])
+        .iter()
+        .zip(ids.slice(ndarray::s![.., .., .., ..3]).iter())
+    {
+        assert_eq!((r * 8388608.) as u32, (*id as u32) >> 9);
+    }
+
+    let first = ids[(0, 0, 0, 0)];
+    assert!(ids
+        .slice(ndarray::s![.., .., .., 0])
+        .iter()
+        .any(|id| *id != first));
+
+    // Distances are normalized values
+    let mut img = Image::new_cpu(dim(), ImageDataType::Int32);
+    assert!(matches!(
+        method.compute(&mut ctx, &mut img, Some(&WorleyNoiseParams::default())),
+        Err(Error::FormatNotSupported)
+    ));
+}
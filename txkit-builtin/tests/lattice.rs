@@ -0,0 +1,251 @@
+//! Output modes and lattice dimensions of the lattice noises (value, gradient and simplex)
+
+use std::any::Any;
+
+use ndarray::{s, Array4};
+
+use txkit_builtin::methods::*;
+use txkit_core::image::ImageDim;
+
+mod common;
+use common::*;
+
+const LATTICE_NOISES: &[&str] = &["value_noise", "gradient_noise", "simplex_noise"];
+
+/// Parameters of the lattice noise `name`
+fn params(name: &str, scale: f32, noise_output: i32, noise_dimensions: i32) -> Box<dyn Any> {
+    params_at(name, scale, noise_output, noise_dimensions, 0.)
+}
+
+/// Parameters of the lattice noise `name`, at `time` on 4D lattices
+fn params_at(
+    name: &str,
+    scale: f32,
+    noise_output: i32,
+    noise_dimensions: i32,
+    time: f32,
+) -> Box<dyn Any> {
+    match name {
+        "value_noise" => Box::new(ValueNoiseParams {
+            scale,
+            noise_output,
+            noise_dimensions,
+            time,
+            ..Default::default()
+        }),
+        "gradient_noise" => Box::new(GradientNoiseParams {
+            scale,
+            noise_output,
+            noise_dimensions,
+            time,
+            ..Default::default()
+        }),
+        "simplex_noise" => Box::new(SimplexNoiseParams {
+            scale,
+            noise_output,
+            noise_dimensions,
+            time,
+            ..Default::default()
+        }),
+        _ => unreachable!("{} is not a lattice noise", name),
+    }
+}
+
+/// Renders of the 3D and 4D lattices, as (reference image name, method name, params)
+fn lattice_cases() -> Vec<(String, &'static str, Box<dyn Any>)> {
+    let mut cases = Vec::new();
+
+    for name in LATTICE_NOISES {
+        cases.push((
+            format!("{}_3d", name),
+            *name,
+            params(name, 8., LATTICE_OUTPUT_VALUE, LATTICE_DIMENSIONS_3D),
+        ));
+        cases.push((
+            format!("{}_4d", name),
+            *name,
+            params_at(name, 8., LATTICE_OUTPUT_VALUE, LATTICE_DIMENSIONS_4D, 2.5),
+        ));
+    }
+
+    // Curl noise uses the 3D lattices for the vector potential of volumes
+    for (suffix, noise_potential) in [
+        ("gradient", CURL_POTENTIAL_GRADIENT),
+        ("simplex", CURL_POTENTIAL_SIMPLEX),
+    ] {
+        cases.push((
+            format!("curl_noise_3d_{}", suffix),
+            "curl_noise",
+            Box::new(CurlNoiseParams {
+                scale: 8.,
+                noise_potential,
+                ..Default::default()
+            }),
+        ));
+    }
+
+    cases
+}
+
+/// Dimensions of the volumes the 3D and 4D lattices are rendered on
+fn volume_dim() -> ImageDim {
+    ImageDim::new_3d(32, 32, 4, 4)
+}
+
+/// Largest difference between the derivative in channel `l` and the central differences of the
+/// value along `axis`, relative to the largest derivative
+fn derivative_error(data: &Array4<f32>, axis: usize, l: usize, step: f32) -> f32 {
+    let (d, h, w, _) = data.dim();
+    let offset = [(1, 0, 0), (0, 1, 0), (0, 0, 1)][2 - axis];
+
+    let mut max_err = 0.0f32;
+    let mut max_derivative = 0.0f32;
+
+    for k in offset.0..d - offset.0 {
+        for j in offset.1..h - offset.1 {
+            for i in offset.2..w - offset.2 {
+                let next = data[(k + offset.0, j + offset.1, i + offset.2, 0)];
+                let prev = data[(k - offset.0, j - offset.1, i - offset.2, 0)];
+                let fd = (next - prev) / (2. * step);
+                let derivative = data[(k, j, i, l)];
+
+                max_err = max_err.max((fd - derivative).abs());
+                max_derivative = max_derivative.max(derivative.abs());
+            }
+        }
+    }
+
+    max_err / max_derivative
+}
+
+#[test]
+fn derivatives_match_finite_differences() {
+    // Pixels are 1/32th of a lattice cell apart along every axis. Value and gradient noises only
+    // have continuous first derivatives, so the central differences converge linearly.
+    let cases = [
+        (LATTICE_DIMENSIONS_2D, ImageDim::new(128, 128, 4), 4., 2),
+        (
+            LATTICE_DIMENSIONS_3D,
+            ImageDim::new_3d(64, 64, 64, 4),
+            2.,
+            3,
+        ),
+    ];
+
+    for name in LATTICE_NOISES {
+        for (dimensions, dim, scale, axes) in cases.iter() {
+            let data = render_cpu_params(
+                name,
+                *dim,
+                &*params(name, *scale, LATTICE_OUTPUT_DERIVATIVES, *dimensions),
+            );
+
+            for axis in 0..*axes {
+                let err = derivative_error(&data, axis, axis + 1, scale / dim.width as f32);
+                assert!(
+                    err < 2e-2,
+                    "{}: derivative along axis {} of the {}D lattice differs by {}",
+                    name,
+                    axis,
+                    axes,
+                    err
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn derivatives_keep_the_value() {
+    for name in LATTICE_NOISES {
+        let value = render_cpu_params(
+            name,
+            dim(),
+            &*params(name, 8., LATTICE_OUTPUT_VALUE, LATTICE_DIMENSIONS_2D),
+        );
+        let derivatives = render_cpu_params(
+            name,
+            dim(),
+            &*params(name, 8., LATTICE_OUTPUT_DERIVATIVES, LATTICE_DIMENSIONS_2D),
+        );
+
+        let max_abs = (&value.slice(s![.., .., .., 0]) - &derivatives.slice(s![.., .., .., 0]))
+            .fold(0.0f32, |m, x| m.max(x.abs()));
+        assert!(max_abs < 1e-5, "{}: value differs by {}", name, max_abs);
+    }
+}
+
+#[test]
+fn lattice_dimensions_match_golden() {
+    let failures: Vec<_> = lattice_cases()
+        .into_iter()
+        .filter_map(|(golden, name, params)| {
+            check_golden(&golden, &render_cpu_params(name, volume_dim(), &*params))
+                .err()
+                .map(|e| format!("{}: {}", golden, e))
+        })
+        .collect();
+
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+#[test]
+fn time_loops_at_the_scale() {
+    // Simplex lattices do not loop
+    for name in &["value_noise", "gradient_noise"] {
+        let render = |time| {
+            render_cpu_params(
+                name,
+                dim(),
+                &*params_at(name, 8., LATTICE_OUTPUT_VALUE, LATTICE_DIMENSIONS_4D, time),
+            )
+        };
+
+        assert_eq!(render(1.25), render(9.25), "{}: time does not loop", name);
+        assert_ne!(render(1.25), render(1.5), "{}: time is ignored", name);
+    }
+}
+
+#[cfg(feature = "gpu")]
+#[test]
+fn gpu_lattice_dimensions_match_cpu() {
+    use txkit_core::image::{Image, ImageDataType};
+
+    let mut ctx = match gpu_context() {
+        Some(ctx) => ctx,
+        None => return,
+    };
+
+    let failures: Vec<_> = lattice_cases()
+        .into_iter()
+        .filter_map(|(golden, name, params)| {
+            let cpu = render_cpu_params(name, volume_dim(), &*params);
+
+            let img = Image::new_gpu_3d(volume_dim(), ImageDataType::Float32, &ctx)
+                .expect("failed to create GPU image");
+            let gpu = render_params(&mut ctx, img, name, Some(&*params));
+
+            // Derivatives amplify the differences in the hashed gradient angles
+            let tolerance = if name == "curl_noise" {
+                Tolerance {
+                    max_abs: 1e-2,
+                    mean_abs: 1e-3,
+                    outliers: 0.,
+                }
+            } else {
+                Tolerance {
+                    max_abs: 1e-3,
+                    mean_abs: 1e-4,
+                    outliers: 0.,
+                }
+            };
+
+            tolerance
+                .check(&cpu, &gpu)
+                .err()
+                .map(|e| format!("{}: {}", golden, e))
+        })
+        .collect();
+
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
@@ -0,0 +1,111 @@
+//! Runtime parameter schemas of the built-in methods
+
+use txkit_builtin::methods::ValueNoiseParams;
+use txkit_core::method::{ParamType, ParamValue};
+
+mod common;
+use common::*;
+
+/// Check if `value` is a value of `param_type`
+fn has_type(value: &ParamValue, param_type: ParamType) -> bool {
+    match value {
+        ParamValue::Int(_) => matches!(param_type, ParamType::UInt | ParamType::Int),
+        ParamValue::Float(_) => param_type == ParamType::Float,
+        ParamValue::Vector(v) => param_type.components() > 1 && v.len() == param_type.components(),
+    }
+}
+
+#[test]
+fn value_noise_schema() {
+    let schema = registry()
+        .param_schema("value_noise")
+        .expect("value noise has no schema");
+
+    let names: Vec<_> = schema.iter().map(|param| param.name).collect();
+    assert_eq!(
+        names,
+        [
+            "global_seed",
+            "scale",
+            "stats_mode",
+            "stats_look_at",
+            "noise_output",
+            "noise_dimensions",
+            "time",
+            "period"
+        ]
+    );
+
+    let scale = &schema[1];
+    assert_eq!(scale.param_type, ParamType::Float);
+    assert_eq!(scale.default, ParamValue::Float(32.));
+    assert_eq!(scale.doc, "lattice scale (size in pixels)");
+
+    assert_eq!(schema[0].param_type, ParamType::UInt);
+    assert_eq!(schema[2].param_type, ParamType::Int);
+    assert_eq!(schema[3].param_type, ParamType::Vector2);
+    assert_eq!(schema[7].param_type, ParamType::Vector4);
+}
+
+#[test]
+fn schemas_describe_default_params() {
+    let registry = registry();
+
+    check_methods(|name| {
+        let method = registry.build(name).unwrap();
+        let (schema, mut params) = match (method.param_schema(), method.default_params()) {
+            (Some(schema), Some(params)) => (schema, params),
+            (None, None) => return Ok(()),
+            _ => return Err("schema and default params disagree".to_owned()),
+        };
+
+        for param in schema {
+            if !has_type(&param.default, param.param_type) {
+                return Err(format!("{}: default has the wrong type", param.name));
+            }
+
+            if params.get_param(param.name).as_ref() != Some(&param.default) {
+                return Err(format!("{}: default does not match the params", param.name));
+            }
+
+            params
+                .set_param(param.name, &param.default)
+                .map_err(|e| format!("{}: failed to set the default: {}", param.name, e))?;
+        }
+
+        Ok(())
+    });
+}
+
+#[test]
+fn params_set_by_name_render() {
+    let mut params = registry()
+        .build("value_noise")
+        .and_then(|method| method.default_params())
+        .unwrap();
+
+    params.set_param("scale", &ParamValue::Int(8)).unwrap();
+    params
+        .set_param("period", &ParamValue::Vector(vec![4., 2., 0., 0.]))
+        .unwrap();
+    assert_eq!(params.get_param("scale"), Some(ParamValue::Float(8.)));
+
+    // Values of the wrong type or unknown fields are rejected
+    assert!(params
+        .set_param("scale", &ParamValue::Vector(vec![1., 2.]))
+        .is_err());
+    assert!(params
+        .set_param("global_seed", &ParamValue::Int(-1))
+        .is_err());
+    assert!(params.set_param("octaves", &ParamValue::Int(4)).is_err());
+
+    let expected = ValueNoiseParams {
+        scale: 8.,
+        period: cgmath::vec4(4., 2., 0., 0.),
+        ..Default::default()
+    };
+    assert_eq!(
+        render_cpu_params("value_noise", dim(), params.as_any()),
+        render_cpu_params("value_noise", dim(), &expected)
+    );
+}
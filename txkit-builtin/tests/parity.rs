@@ -0,0 +1,85 @@
+//! Cross-backend parity tests for the built-in methods
+//!
+//! Every method is rendered with its default parameters on the CPU and compared against a
+//! reference image in `tests/golden`. If a GPU context can be created (by default, Mesa's
+//! software rasterizer is requested), the GPU output is also compared against the CPU output.
+//!
+//! Set `TXKIT_BLESS=1` to regenerate the reference images after an intended change.
+
+mod common;
+use common::*;
+
+/// Tolerance for comparing the backends on the method `name`
+#[cfg_attr(not(feature = "gpu"), allow(dead_code))]
+fn tolerance(name: &str) -> Tolerance {
+    match name {
+        // Integer hashes only
+        "debug" | "white_noise" => Tolerance {
+            max_abs: 0.,
+            mean_abs: 0.,
+            outliers: 0.,
+        },
+        "value_noise" | "worley_noise" | "fractal_value_noise" => Tolerance {
+            max_abs: 1e-3,
+            mean_abs: 1e-5,
+            outliers: 0.,
+        },
+        // The phase of the noise is undefined where its magnitude vanishes, so the values around
+        // these isolated points depend on the rounding of each backend. They cover less than 0.5%
+        // of the default render.
+        "phasor_noise" => Tolerance {
+            max_abs: 1e-3,
+            mean_abs: 1e-3,
+            outliers: 5e-3,
+        },
+        // Derivatives amplify the differences in the hashed gradient angles
+        "curl_noise" => Tolerance {
+            max_abs: 1e-2,
+            mean_abs: 1e-3,
+            outliers: 0.,
+        },
+        "domain_warp" => Tolerance {
+            max_abs: 1e-2,
+            mean_abs: 1e-4,
+            outliers: 0.,
+        },
+        _ => Tolerance {
+            max_abs: 1e-3,
+            mean_abs: 1e-4,
+            outliers: 0.,
+        },
+    }
+}
+
+#[test]
+fn cpu_matches_golden() {
+    check_methods(|name| check_golden(name, &render_cpu(name)));
+}
+
+#[cfg(feature = "gpu")]
+#[test]
+fn gpu_matches_cpu() {
+    use txkit_core::image::{Image, ImageDataType};
+
+    let mut ctx = match gpu_context() {
+        Some(ctx) => ctx,
+        None => return,
+    };
+
+    let registry = registry();
+
+    check_methods(|name| {
+        // Methods without a GPU implementation, such as blue noise
+        if !registry.info(name).map_or(true, |info| info.gpu) {
+            return Ok(());
+        }
+
+        let cpu = render_cpu(name);
+
+        let img = Image::new_gpu_2d(dim(), ImageDataType::Float32, &ctx)
+            .expect("failed to create GPU image");
+        let gpu = render(&mut ctx, img, name);
+
+        tolerance(name).check(&cpu, &gpu)
+    });
+}
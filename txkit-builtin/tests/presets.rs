@@ -0,0 +1,68 @@
+//! Saving and reloading method parameters as presets, with the `serde` feature
+
+use txkit_builtin::methods::*;
+
+fn phasor_preset() -> PhasorNoiseParams {
+    PhasorNoiseParams {
+        global_seed: 42,
+        scale: 12.5,
+        stats_look_at: cgmath::vec2(0.25, 0.75),
+        period: cgmath::vec2(4., 2.),
+        kernel_count: 16,
+        noise_profile: PHASOR_PROFILE_SAW,
+        noise_frequency: 0.1,
+        noise_angle: std::f32::consts::FRAC_PI_3,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn json_round_trip() {
+    let params = phasor_preset();
+    let json = serde_json::to_string(&params).unwrap();
+
+    assert!(serde_json::from_str::<PhasorNoiseParams>(&json).unwrap() == params);
+}
+
+#[test]
+fn toml_round_trip() {
+    let params = phasor_preset();
+    let toml = toml::to_string(&params).unwrap();
+
+    assert!(toml::from_str::<PhasorNoiseParams>(&toml).unwrap() == params);
+}
+
+#[test]
+fn missing_fields_are_defaulted() {
+    let params: ValueNoiseParams =
+        serde_json::from_str(r#"{ "scale": 8.0, "period": [2.0, 2.0, 0.0, 0.0] }"#).unwrap();
+
+    assert!(
+        params
+            == ValueNoiseParams {
+                scale: 8.,
+                period: cgmath::vec4(2., 2., 0., 0.),
+                ..Default::default()
+            }
+    );
+}
+
+#[test]
+fn bound_inputs_fail_to_serialize() {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use txkit_core::image::{Image, ImageDataType, ImageDim};
+    use txkit_core::io::ImageBinding;
+
+    let mut params = phasor_preset();
+    assert!(!serde_json::to_string(&params).unwrap().contains("\"io\""));
+
+    let field = Image::new_cpu(ImageDim::new(4, 4, 4), ImageDataType::Float32);
+    params
+        .io
+        .set_texture_binding(0, ImageBinding::ImageRef(Rc::new(RefCell::new(field))));
+
+    assert!(serde_json::to_string(&params).is_err());
+    assert!(toml::to_string(&params).is_err());
+}
@@ -0,0 +1,161 @@
+//! Renders of image regions, windows of the full image plane
+
+use ndarray::{s, Array4};
+
+use txkit_core::context::{Context, CpuContext};
+use txkit_core::image::{Image, ImageDataType, ImageDim, ImageRegion};
+
+mod common;
+use common::*;
+
+/// Size of the regions the test images are split into
+const TILE_SIZE: usize = 24;
+
+/// Methods which only depend on the position of the fragments on the full image plane
+const LATTICE_METHODS: &[&str] = &[
+    "value_noise",
+    "gradient_noise",
+    "simplex_noise",
+    "domain_warp",
+];
+
+/// Region of the test image at `(x, y)`, with pixels `scale` full image pixels wide
+fn region(x: f32, y: f32, scale: f32) -> ImageRegion {
+    ImageRegion {
+        x,
+        y,
+        scale,
+        ..ImageRegion::full(dim())
+    }
+}
+
+/// Render `region` of the method `name` into a new Float32 image of dimensions `dim` on the CPU
+fn render_region(name: &str, dim: ImageDim, region: ImageRegion) -> Array4<f32> {
+    let mut img = Image::new_cpu(dim, ImageDataType::Float32);
+    img.set_region(region).expect("failed to set image region");
+
+    let mut ctx = Context::new_cpu().expect("failed to create CPU context");
+    render(&mut ctx, img, name)
+}
+
+fn max_abs_difference(a: &Array4<f32>, b: &Array4<f32>) -> f32 {
+    (a - b).fold(0.0f32, |m, d| m.max(d.abs()))
+}
+
+#[test]
+fn cpu_regions_stitch() {
+    check_methods(|name| {
+        let full = render_cpu(name);
+        let mut max_abs = 0.0f32;
+
+        for y in (0..SIZE).step_by(TILE_SIZE) {
+            for x in (0..SIZE).step_by(TILE_SIZE) {
+                let tile_dim = ImageDim::new(TILE_SIZE.min(SIZE - x), TILE_SIZE.min(SIZE - y), 4);
+                let tile = render_region(name, tile_dim, region(x as f32, y as f32, 1.));
+
+                for ((k, j, i, l), v) in tile.indexed_iter() {
+                    max_abs = max_abs.max((full[(k, j + y, i + x, l)] - v).abs());
+                }
+            }
+        }
+
+        if max_abs > 0. {
+            return Err(format!(
+                "regions differ from the full image by up to {}",
+                max_abs
+            ));
+        }
+
+        Ok(())
+    });
+}
+
+#[test]
+fn scaled_regions_match_finer_images() {
+    // Half-pixel windows over the full image sample the texture coordinates of a twice larger
+    // image
+    for name in LATTICE_METHODS {
+        let scaled = render_region(
+            name,
+            ImageDim::new(2 * SIZE, 2 * SIZE, 4),
+            region(0., 0., 0.5),
+        );
+        let finer = render_cpu_dim(
+            name,
+            ImageDim::new(2 * SIZE, 2 * SIZE, 4),
+            ImageDataType::Float32,
+        );
+
+        let max_abs = max_abs_difference(&scaled, &finer);
+        assert!(max_abs < 1e-5, "{}: differs by up to {}", name, max_abs);
+    }
+}
+
+#[test]
+fn negative_origins_loop() {
+    // The lattice loops at the full image, so a window straddling its left border shows the right
+    // half of the image followed by the left half
+    let half = SIZE / 2;
+
+    // Simplex lattices do not loop along the image axes
+    for name in &["value_noise", "gradient_noise"] {
+        let full = render_cpu(name);
+        let shifted = render_region(name, dim(), region(-(half as f32), 0., 1.));
+
+        let left = max_abs_difference(
+            &shifted.slice(s![.., .., ..half, ..]).to_owned(),
+            &full.slice(s![.., .., half.., ..]).to_owned(),
+        );
+        let right = max_abs_difference(
+            &shifted.slice(s![.., .., half.., ..]).to_owned(),
+            &full.slice(s![.., .., ..half, ..]).to_owned(),
+        );
+
+        assert!(
+            left.max(right) < 1e-4,
+            "{}: differs by up to {}",
+            name,
+            left.max(right)
+        );
+    }
+}
+
+#[test]
+fn cpu_fragments_match_renders() {
+    let mut ctx = CpuContext::new().expect("failed to create CPU context");
+    let region = ImageRegion::full(dim());
+
+    check_methods(|name| {
+        // Domain warp only evaluates its inputs at arbitrary fragments
+        if name == "domain_warp" {
+            return Ok(());
+        }
+
+        let full = render_cpu(name);
+        let fragments: Vec<_> = full
+            .indexed_iter()
+            .map(|(idx, _)| region.fragment(idx))
+            .collect();
+
+        let mut values = vec![0.; fragments.len()];
+        registry()
+            .build(name)
+            .unwrap()
+            .compute_fragments(&mut ctx, &fragments, dim(), None, &mut values)
+            .map_err(|err| err.to_string())?;
+
+        let max_abs = full
+            .iter()
+            .zip(&values)
+            .fold(0.0f32, |m, (a, b)| m.max((a - b).abs()));
+
+        if max_abs > 0. {
+            return Err(format!(
+                "fragments differ from the render by up to {}",
+                max_abs
+            ));
+        }
+
+        Ok(())
+    });
+}
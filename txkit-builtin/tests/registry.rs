@@ -0,0 +1,88 @@
+//! Listing and description of the built-in methods
+
+use txkit_core::image::ImageDataType;
+
+mod common;
+use common::*;
+
+#[test]
+fn names_are_sorted() {
+    assert_eq!(
+        registry().names(),
+        [
+            "blue_noise",
+            "curl_noise",
+            "debug",
+            "domain_warp",
+            "fractal_gradient_noise",
+            "fractal_simplex_noise",
+            "fractal_value_noise",
+            "gabor_noise",
+            "gradient_noise",
+            "phasor_noise",
+            "simplex_noise",
+            "value_noise",
+            "white_noise",
+            "worley_noise"
+        ]
+    );
+}
+
+#[test]
+fn every_method_is_described() {
+    let registry = registry();
+
+    check_methods(|name| {
+        let info = registry.info(name).ok_or("no method info")?;
+
+        if info.description.is_empty() {
+            return Err("empty description".to_owned());
+        }
+
+        if !info.cpu {
+            return Err("not supported on the CPU".to_owned());
+        }
+
+        if !info.element_types.contains(&ImageDataType::Float32) {
+            return Err("Float32 targets not supported".to_owned());
+        }
+
+        Ok(())
+    });
+
+    assert!(registry.info("no_such_method").is_none());
+}
+
+#[test]
+fn method_info() {
+    let registry = registry();
+
+    let value_noise = registry.info("value_noise").unwrap();
+    assert_eq!(
+        value_noise.description,
+        "Value noise, interpolating random values on a lattice"
+    );
+    assert_eq!(value_noise.gpu, cfg!(feature = "gpu"));
+    assert!(!value_noise.element_types.contains(&ImageDataType::Int32));
+    assert_eq!(value_noise.max_dimensions, 3);
+    assert_eq!(value_noise.params_type, "ValueNoiseParams");
+
+    // Methods evaluated on a 2D lattice render the same slice on every layer
+    for name in &["worley_noise", "phasor_noise", "gabor_noise"] {
+        assert_eq!(registry.info(name).unwrap().max_dimensions, 2, "{}", name);
+    }
+
+    // Threshold maps are only computed on the CPU, and can be written as integer ranks
+    let blue_noise = registry.info("blue_noise").unwrap();
+    assert!(!blue_noise.gpu);
+    assert_eq!(blue_noise.element_types, ImageDataType::ALL);
+
+    // Combinators describe themselves, with the backends of the methods they combine
+    let fractal = registry.info("fractal_value_noise").unwrap();
+    assert_eq!(fractal.params_type, "FractalParams");
+    assert_eq!(fractal.gpu, value_noise.gpu);
+
+    let domain_warp = registry.info("domain_warp").unwrap();
+    assert_eq!(domain_warp.params_type, "DomainWarpParams");
+    assert_eq!(domain_warp.max_dimensions, 3);
+}
@@ -0,0 +1,160 @@
+//! Explicit lattice periods of value, gradient and phasor noise
+
+use std::any::Any;
+
+use ndarray::{s, Array4};
+
+use txkit_builtin::methods::*;
+use txkit_core::context::Context;
+use txkit_core::image::{Image, ImageDataType};
+use txkit_core::Error;
+
+mod common;
+use common::*;
+
+/// Scale of the renders, in lattice cells over the image
+const SCALE: f32 = 8.;
+
+/// Renders whose lattice period divides the image into 2x4 tiles
+fn period_cases() -> Vec<(&'static str, Box<dyn Any>)> {
+    vec![
+        (
+            "value_noise",
+            Box::new(ValueNoiseParams {
+                scale: SCALE,
+                period: cgmath::vec4(SCALE / 2., SCALE / 4., 0., 0.),
+                ..Default::default()
+            }),
+        ),
+        (
+            "gradient_noise",
+            Box::new(GradientNoiseParams {
+                scale: SCALE,
+                period: cgmath::vec4(SCALE / 2., SCALE / 4., 0., 0.),
+                ..Default::default()
+            }),
+        ),
+        (
+            "phasor_noise",
+            Box::new(PhasorNoiseParams {
+                scale: SCALE,
+                period: cgmath::vec2(SCALE / 2., SCALE / 4.),
+                ..Default::default()
+            }),
+        ),
+    ]
+}
+
+/// Largest difference between adjacent tiles of `data`, which is split in 2x4 tiles
+fn tile_difference(data: &Array4<f32>) -> f32 {
+    let (_, h, w, _) = data.dim();
+    let reference = data.slice(s![.., ..h / 4, ..w / 2, ..]);
+
+    let mut max_abs = 0.0f32;
+    for y in 0..4 {
+        for x in 0..2 {
+            let tile = data.slice(s![
+                ..,
+                y * h / 4..(y + 1) * h / 4,
+                x * w / 2..(x + 1) * w / 2,
+                ..
+            ]);
+            max_abs = (&tile - &reference).fold(max_abs, |m, d| m.max(d.abs()));
+        }
+    }
+
+    max_abs
+}
+
+#[test]
+fn cpu_periods_tile() {
+    for (name, params) in period_cases() {
+        let data = render_cpu_params(name, dim(), &*params);
+        let max_abs = tile_difference(&data);
+
+        assert!(
+            max_abs < 1e-5,
+            "{}: tiles differ by up to {}",
+            name,
+            max_abs
+        );
+
+        // The default period loops at the scale, so a single period does not tile
+        let default = render_cpu(name);
+        assert!(
+            tile_difference(&default) > 1e-2,
+            "{}: period is ignored",
+            name
+        );
+    }
+}
+
+#[test]
+fn invalid_periods_are_rejected() {
+    let mut ctx = Context::new_cpu().expect("failed to create CPU context");
+
+    for period in [-1., 0.5, f32::NAN, f32::INFINITY, 1e20] {
+        let cases: Vec<(&str, Box<dyn Any>)> = vec![
+            (
+                "value_noise",
+                Box::new(ValueNoiseParams {
+                    period: cgmath::vec4(0., 0., 0., period),
+                    ..Default::default()
+                }),
+            ),
+            (
+                "gradient_noise",
+                Box::new(GradientNoiseParams {
+                    period: cgmath::vec4(period, 0., 0., 0.),
+                    ..Default::default()
+                }),
+            ),
+            (
+                "phasor_noise",
+                Box::new(PhasorNoiseParams {
+                    period: cgmath::vec2(0., period),
+                    ..Default::default()
+                }),
+            ),
+        ];
+
+        for (name, params) in cases {
+            let mut img = Image::new_cpu(dim(), ImageDataType::Float32);
+            let result =
+                registry()
+                    .build(name)
+                    .unwrap()
+                    .compute(&mut ctx, &mut img, Some(&*params));
+
+            assert!(
+                matches!(result, Err(Error::InvalidParameters)),
+                "{}: period {} is accepted",
+                name,
+                period
+            );
+        }
+    }
+}
+
+#[cfg(feature = "gpu")]
+#[test]
+fn gpu_periods_tile() {
+    let mut ctx = match gpu_context() {
+        Some(ctx) => ctx,
+        None => return,
+    };
+
+    for (name, params) in period_cases() {
+        let img = Image::new_gpu_2d(dim(), ImageDataType::Float32, &ctx)
+            .expect("failed to create GPU image");
+        let data = render_params(&mut ctx, img, name, Some(&*params));
+        let max_abs = tile_difference(&data);
+
+        assert!(
+            max_abs < 1e-5,
+            "{}: tiles differ by up to {}",
+            name,
+            max_abs
+        );
+    }
+}
@@ -0,0 +1,70 @@
+//! Renders into CPU images wrapping host memory
+//!
+//! Wrapped arrays may use any memory layout, and every method must render into them the same
+//! values as into a packed image.
+
+use ndarray::{s, Array4, ShapeBuilder};
+
+use txkit_core::context::Context;
+use txkit_core::image::{Image, ImageCreationError, ImageDim};
+
+mod common;
+use common::*;
+
+#[test]
+fn cpu_wrapped_arrays_match() {
+    check_methods(|name| {
+        let reference = render_cpu(name);
+        let mut ctx = Context::new_cpu().expect("failed to create CPU context");
+
+        // Adopted array, in column-major order
+        let shape = (1, SIZE, SIZE, 4);
+        let adopted = render(
+            &mut ctx,
+            Image::from_nd_array(Array4::<f32>::zeros(shape.f())),
+            name,
+        );
+
+        // Borrowed array, with padded rows
+        let mut padded = Array4::<f32>::from_elem((1, SIZE, SIZE + 3, 4), -1.);
+        let borrowed = render(
+            &mut ctx,
+            unsafe { Image::from_nd_array_view(padded.slice_mut(s![.., .., ..SIZE, ..])) },
+            name,
+        );
+
+        if adopted != reference {
+            return Err("adopted array differs".to_owned());
+        }
+
+        if borrowed != reference
+            || padded
+                .slice(s![.., .., SIZE.., ..])
+                .iter()
+                .any(|x| *x != -1.)
+        {
+            return Err("borrowed array differs".to_owned());
+        }
+
+        Ok(())
+    });
+}
+
+#[test]
+fn overflowing_strides_are_rejected() {
+    let mut data = [0f32; 4];
+    let ptr = data.as_mut_ptr();
+    let create = |dim, strides| unsafe { Image::from_raw_parts(ptr, dim, strides, None) };
+
+    // Packed strides of a huge image
+    assert!(matches!(
+        create(ImageDim::new(usize::MAX / 2, 4, 4), None),
+        Err(ImageCreationError::InvalidStrides)
+    ));
+
+    // Extent of the rows
+    assert!(matches!(
+        create(ImageDim::new(2, 2, 1), Some([0, usize::MAX, 1, 1])),
+        Err(ImageCreationError::InvalidStrides)
+    ));
+}
@@ -2,9 +2,12 @@ use std::any::Any;
 
 use txkit_core::{
     context::Context,
-    image::{Image, ImageDataType, ImageDim, MappedImageData, MappedImageDataMut},
+    image::{
+        f16, Image, ImageDataType, ImageDim, ImageRegion, MappedImageData, MappedImageDataMut,
+        ReleaseFn,
+    },
     io::{ImageBinding, ImageIo},
-    method::{Method, MethodRegistry},
+    method::{Method, MethodInfo, MethodParams, MethodRegistry, ParamInfo, ParamType, ParamValue},
     Error,
 };
 
@@ -29,6 +32,8 @@ pub struct MethodBox {
 /// * `params`: pointer to the parameter structure for this method
 /// * `params_size`: size of the parameter structure
 ///
+/// Methods write at most 4 channels of `tgt`, and fill the other ones with zeros.
+///
 /// # Returns
 ///
 /// TxKit_SUCCESS if no error occurred, else a non-zero code.
@@ -61,9 +66,358 @@ pub unsafe extern "C" fn txkit_method_destroy(method: *mut MethodBox) {
     std::mem::drop(Box::from_raw(method))
 }
 
+/// Wrapped method parameters for FFI
+pub struct ParamsBox {
+    params: Box<dyn MethodParams>,
+    schema: Vec<ParamInfo>,
+    names: Vec<std::ffi::CString>,
+    docs: Vec<std::ffi::CString>,
+}
+
+impl ParamsBox {
+    fn info(&self, name: *const libc::c_char) -> Result<&ParamInfo, Error> {
+        if name.is_null() {
+            return Err(Error::UnknownParameter(String::new()));
+        }
+
+        let name = unsafe { std::ffi::CStr::from_ptr(name as *const _) }.to_string_lossy();
+        self.schema
+            .iter()
+            .find(|info| info.name == name)
+            .ok_or_else(|| Error::UnknownParameter(name.into_owned()))
+    }
+}
+
+/// Description of a method parameter
+#[repr(C)]
+pub struct ParamDesc {
+    /// name of the parameter, valid until the parameters are destroyed
+    pub name: *const libc::c_char,
+    /// documentation of the parameter, valid until the parameters are destroyed
+    pub doc: *const libc::c_char,
+    /// type of the parameter
+    pub param_type: ParamType,
+}
+
+/// Create the default parameters of a method
+///
+/// # Parameters
+///
+/// * `method`: method to create the parameters of
+///
+/// # Returns
+///
+/// Null pointer if the method does not expose its parameters, otherwise pointer to the allocated
+/// parameters.
+#[no_mangle]
+pub extern "C" fn txkit_method_params_new(method: &MethodBox) -> *mut ParamsBox {
+    crate::api::wrap_result(|| {
+        let params = method
+            .method
+            .default_params()
+            .ok_or(Error::InvalidParameters)?;
+        let schema = params.schema();
+        let to_cstring = |s: &str| std::ffi::CString::new(s).unwrap_or_default();
+
+        Ok::<_, Error>(Box::into_raw(Box::new(ParamsBox {
+            names: schema.iter().map(|info| to_cstring(info.name)).collect(),
+            docs: schema.iter().map(|info| to_cstring(info.doc)).collect(),
+            params,
+            schema,
+        })))
+    })
+    .unwrap_or(std::ptr::null_mut())
+}
+
+/// Compute an image using the given method and parameters
+///
+/// # Parameters
+///
+/// * `ctx`: context to use for computing the image
+/// * `method`: texturing method
+/// * `tgt`: target image to be computed
+/// * `params`: parameters created from this method
+///
+/// # Returns
+///
+/// TxKit_SUCCESS if no error occurred, else a non-zero code.
+#[no_mangle]
+pub extern "C" fn txkit_method_compute_params(
+    ctx: &mut Context,
+    method: &mut MethodBox,
+    tgt: &mut Image,
+    params: &ParamsBox,
+) -> i32 {
+    crate::api::wrap_result_code(|| {
+        method
+            .method
+            .compute(ctx, tgt, Some(params.params.as_any()))
+    })
+}
+
+/// Destroy method parameters
+///
+/// # Parameters
+///
+/// * `params`: parameters to destroy
+#[no_mangle]
+pub unsafe extern "C" fn txkit_params_destroy(params: *mut ParamsBox) {
+    std::mem::drop(Box::from_raw(params))
+}
+
+/// Get the number of parameters that can be accessed by name
+///
+/// # Parameters
+///
+/// * `params`: method parameters
+#[no_mangle]
+pub extern "C" fn txkit_params_count(params: &ParamsBox) -> usize {
+    params.schema.len()
+}
+
+/// Describe a parameter
+///
+/// # Parameters
+///
+/// * `params`: method parameters
+/// * `index`: index of the parameter, less than `txkit_params_count(params)`
+/// * `desc`: description of the parameter to fill
+///
+/// # Returns
+///
+/// TxKit_SUCCESS if no error occurred, else a non-zero code.
+#[no_mangle]
+pub extern "C" fn txkit_params_describe(
+    params: &ParamsBox,
+    index: usize,
+    desc: &mut ParamDesc,
+) -> i32 {
+    crate::api::wrap_result_code(|| {
+        let info = params
+            .schema
+            .get(index)
+            .ok_or_else(|| Error::UnknownParameter(format!("#{}", index)))?;
+
+        *desc = ParamDesc {
+            name: params.names[index].as_ptr(),
+            doc: params.docs[index].as_ptr(),
+            param_type: info.param_type,
+        };
+
+        Ok::<_, Error>(())
+    })
+}
+
+/// Get the value of a parameter
+///
+/// # Parameters
+///
+/// * `params`: method parameters
+/// * `name`: name of the parameter
+/// * `values`: pointer to the components of the value to fill
+/// * `count`: number of components in `values`, which must match the parameter type
+///
+/// # Returns
+///
+/// TxKit_SUCCESS if no error occurred, else a non-zero code.
+///
+/// # Safety
+///
+/// `values` must point to at least `count` values.
+#[no_mangle]
+pub unsafe extern "C" fn txkit_params_get(
+    params: &ParamsBox,
+    name: *const libc::c_char,
+    values: *mut f64,
+    count: usize,
+) -> i32 {
+    crate::api::wrap_result_code(|| {
+        let info = params.info(name)?;
+        let invalid = || Error::InvalidParameterValue {
+            name: info.name.to_owned(),
+            expected: info.param_type.expected(),
+        };
+
+        if values.is_null() || count != info.param_type.components() {
+            return Err(invalid());
+        }
+
+        let values = std::slice::from_raw_parts_mut(values, count);
+        match params.params.get_param(info.name).ok_or_else(invalid)? {
+            ParamValue::Int(i) => values[0] = i as f64,
+            ParamValue::Float(x) => values[0] = x,
+            ParamValue::Vector(v) => values.copy_from_slice(&v),
+        }
+
+        Ok(())
+    })
+}
+
+/// Set the value of a parameter
+///
+/// # Parameters
+///
+/// * `params`: method parameters
+/// * `name`: name of the parameter
+/// * `values`: pointer to the components of the value
+/// * `count`: number of components in `values`, which must match the parameter type
+///
+/// # Returns
+///
+/// TxKit_SUCCESS if no error occurred, else a non-zero code.
+///
+/// # Safety
+///
+/// `values` must point to at least `count` values.
+#[no_mangle]
+pub unsafe extern "C" fn txkit_params_set(
+    params: &mut ParamsBox,
+    name: *const libc::c_char,
+    values: *const f64,
+    count: usize,
+) -> i32 {
+    crate::api::wrap_result_code(|| {
+        let info = params.info(name)?.clone();
+
+        if values.is_null() || count != info.param_type.components() {
+            return Err(Error::InvalidParameterValue {
+                name: info.name.to_owned(),
+                expected: info.param_type.expected(),
+            });
+        }
+
+        let values = std::slice::from_raw_parts(values, count);
+        let value = match info.param_type {
+            ParamType::UInt | ParamType::Int if values[0].fract() == 0. => {
+                ParamValue::Int(values[0] as i64)
+            }
+            ParamType::UInt | ParamType::Int | ParamType::Float => ParamValue::Float(values[0]),
+            _ => ParamValue::Vector(values.to_vec()),
+        };
+
+        params.params.set_param(info.name, &value)
+    })
+}
+
+/// Registered method, with its description kept alive for FFI
+struct RegisteredMethod {
+    name: std::ffi::CString,
+    description: std::ffi::CString,
+    params_type: std::ffi::CString,
+    info: Option<MethodInfo>,
+}
+
 /// Wrapped registry for FFI
 pub struct RegistryBox {
     registry: Box<MethodRegistry>,
+    methods: Vec<RegisteredMethod>,
+}
+
+impl RegistryBox {
+    fn new(registry: MethodRegistry) -> Self {
+        let mut result = Self {
+            registry: Box::new(registry),
+            methods: Vec::new(),
+        };
+
+        result.update_methods();
+        result
+    }
+
+    fn update_methods(&mut self) {
+        let registry = &self.registry;
+        let to_cstring = |s: &str| std::ffi::CString::new(s).unwrap_or_default();
+
+        self.methods = registry
+            .names()
+            .into_iter()
+            .map(|name| {
+                let info = registry.info(name);
+
+                RegisteredMethod {
+                    name: to_cstring(name),
+                    description: to_cstring(info.as_ref().map(|i| i.description).unwrap_or("")),
+                    params_type: to_cstring(info.as_ref().map(|i| i.params_type).unwrap_or("")),
+                    info,
+                }
+            })
+            .collect();
+    }
+}
+
+/// Description of a registered method
+///
+/// Methods which do not expose their metadata only have their name set. Strings are valid until the
+/// registry is destroyed, or a plugin is loaded into it.
+#[repr(C)]
+pub struct MethodDesc {
+    /// name of the method
+    pub name: *const libc::c_char,
+    /// short description of the method
+    pub description: *const libc::c_char,
+    /// name of the parameter type of the method
+    pub params_type: *const libc::c_char,
+    /// true if the method can compute images in CPU contexts
+    pub cpu: bool,
+    /// true if the method can compute images in GPU contexts
+    pub gpu: bool,
+    /// set of the supported element types, with bit `1 << t` set for the element type `t`
+    pub element_types: u32,
+    /// maximum number of dimensions of the images the method can compute
+    pub max_dimensions: usize,
+}
+
+/// Get the number of methods in a registry
+///
+/// # Parameters
+///
+/// * `registry`: registry of methods
+#[no_mangle]
+pub extern "C" fn txkit_registry_count(registry: &RegistryBox) -> usize {
+    registry.methods.len()
+}
+
+/// Describe a registered method
+///
+/// Methods are sorted by name.
+///
+/// # Parameters
+///
+/// * `registry`: registry of methods
+/// * `index`: index of the method, less than `txkit_registry_count(registry)`
+/// * `desc`: description of the method to fill
+///
+/// # Returns
+///
+/// TxKit_SUCCESS if no error occurred, else a non-zero code.
+#[no_mangle]
+pub extern "C" fn txkit_registry_describe(
+    registry: &RegistryBox,
+    index: usize,
+    desc: &mut MethodDesc,
+) -> i32 {
+    crate::api::wrap_result_code(|| {
+        let method = registry.methods.get(index).ok_or(Error::MethodNotFound)?;
+        let info = method.info.as_ref();
+
+        *desc = MethodDesc {
+            name: method.name.as_ptr(),
+            description: method.description.as_ptr(),
+            params_type: method.params_type.as_ptr(),
+            cpu: info.map(|info| info.cpu).unwrap_or(false),
+            gpu: info.map(|info| info.gpu).unwrap_or(false),
+            element_types: info
+                .map(|info| {
+                    info.element_types
+                        .iter()
+                        .fold(0, |bits, t| bits | 1 << *t as u32)
+                })
+                .unwrap_or(0),
+            max_dimensions: info.map(|info| info.max_dimensions).unwrap_or(0),
+        };
+
+        Ok::<_, Error>(())
+    })
 }
 
 /// Create a new registry with txkit built-in methods registered
@@ -74,9 +428,9 @@ pub struct RegistryBox {
 #[no_mangle]
 pub extern "C" fn txkit_registry_new_builtin() -> *mut RegistryBox {
     crate::api::wrap(|| {
-        Box::into_raw(Box::new(RegistryBox {
-            registry: Box::new(txkit_builtin::methods::new_registry()),
-        }))
+        Box::into_raw(Box::new(RegistryBox::new(
+            txkit_builtin::methods::new_registry(),
+        )))
     })
     .unwrap_or(std::ptr::null_mut())
 }
@@ -116,6 +470,56 @@ pub extern "C" fn txkit_method_new(
     .unwrap_or(std::ptr::null_mut())
 }
 
+/// Load the methods of a plugin shared library into a registry
+///
+/// Loading a plugin invalidates the method descriptions previously returned for this registry.
+///
+/// # Parameters
+///
+/// * `registry`: registry to load the methods into
+/// * `path`: path to the shared library, as raw bytes on unix and UTF-8 on other platforms
+///
+/// # Returns
+///
+/// TxKit_SUCCESS if no error occurred, else a non-zero code.
+///
+/// # Safety
+///
+/// Loading a library runs its initialization code, and the methods of the plugin are trusted to be
+/// sound.
+#[no_mangle]
+pub unsafe extern "C" fn txkit_registry_load_plugin(
+    registry: &mut RegistryBox,
+    path: *const libc::c_char,
+) -> i32 {
+    crate::api::wrap_result_code(|| {
+        if path.is_null() {
+            return Err(Error::PluginLoadFailed {
+                path: Default::default(),
+                reason: "null path".to_owned(),
+            });
+        }
+
+        let path = std::ffi::CStr::from_ptr(path);
+
+        // Paths are arbitrary bytes on unix, and must be UTF-8 elsewhere
+        #[cfg(unix)]
+        let path = {
+            use std::os::unix::ffi::OsStrExt;
+            std::path::Path::new(std::ffi::OsStr::from_bytes(path.to_bytes()))
+        };
+        #[cfg(not(unix))]
+        let path = std::path::Path::new(path.to_str().map_err(|_| Error::PluginLoadFailed {
+            path: path.to_string_lossy().into_owned().into(),
+            reason: "the path is not valid UTF-8".to_owned(),
+        })?);
+
+        registry.registry.load_plugin(path)?;
+        registry.update_methods();
+        Ok(())
+    })
+}
+
 /// Destroy a registry
 ///
 /// # Parameters
@@ -141,6 +545,64 @@ pub extern "C" fn txkit_image_new_cpu(dim: ImageDim, element_type: ImageDataType
     Box::into_raw(Box::new(Image::new_cpu(dim, element_type)))
 }
 
+/// Create a new image for CPU-based computations over a caller-owned buffer, without copying it
+///
+/// # Parameters
+///
+/// * `dim`: dimensions of the image
+/// * `element_type`: type of the elements in the image
+/// * `data`: pointer to the first element of the image
+/// * `strides`: distance, in elements, between consecutive layers, rows, pixels and channels (4
+///   values), or null for tightly packed elements
+/// * `release`: function called with `data` and `user_data` when the image is destroyed, or null
+///   if the caller keeps ownership of the buffer
+/// * `user_data`: pointer passed to `release`
+///
+/// # Returns
+///
+/// Allocated image, or null if an error occurred. `release` is not called in this case.
+///
+/// # Safety
+///
+/// `data` must be valid for reads and writes of all the elements of the image until it is
+/// destroyed, and `strides` must be null or point to 4 values.
+#[no_mangle]
+pub unsafe extern "C" fn txkit_image_new_cpu_from_buffer(
+    dim: ImageDim,
+    element_type: ImageDataType,
+    data: *mut std::ffi::c_void,
+    strides: *const usize,
+    release: Option<unsafe extern "C" fn(*mut std::ffi::c_void, *mut std::ffi::c_void)>,
+    user_data: *mut std::ffi::c_void,
+) -> *mut Image {
+    crate::api::wrap_result(|| {
+        let strides = if strides.is_null() {
+            None
+        } else {
+            let strides = std::slice::from_raw_parts(strides, 4);
+            Some([strides[0], strides[1], strides[2], strides[3]])
+        };
+
+        let release = release
+            .map(|release| -> ReleaseFn { Box::new(move || unsafe { release(data, user_data) }) });
+
+        match element_type {
+            ImageDataType::UInt8 => Image::from_raw_parts(data as *mut u8, dim, strides, release),
+            ImageDataType::Float32 => {
+                Image::from_raw_parts(data as *mut f32, dim, strides, release)
+            }
+            ImageDataType::UInt16 => Image::from_raw_parts(data as *mut u16, dim, strides, release),
+            ImageDataType::Float16 => {
+                Image::from_raw_parts(data as *mut f16, dim, strides, release)
+            }
+            ImageDataType::Int32 => Image::from_raw_parts(data as *mut i32, dim, strides, release),
+        }
+        .map(Box::new)
+        .map(Box::into_raw)
+    })
+    .unwrap_or(std::ptr::null_mut())
+}
+
 /// Create a new 1D image for GPU-based computations
 ///
 /// # Parameters
@@ -243,6 +705,34 @@ pub extern "C" fn txkit_image_dim(image: &Image) -> ImageDim {
     image.dim()
 }
 
+/// Return the region of the full image rendered into the image
+///
+/// # Parameters
+///
+/// * `image`: target image
+#[no_mangle]
+pub extern "C" fn txkit_image_region(image: &Image) -> ImageRegion {
+    image.region()
+}
+
+/// Set the region of the full image rendered into the image
+///
+/// Methods computing the image then render this window of the full image, so that adjacent
+/// regions can be rendered separately and stitched together.
+///
+/// # Parameters
+///
+/// * `image`: target image
+/// * `region`: window of the full image plane, with a positive scale and a non-empty full image
+///
+/// # Returns
+///
+/// TxKit_SUCCESS if no error occurred, else a non-zero code.
+#[no_mangle]
+pub extern "C" fn txkit_image_set_region(image: &mut Image, region: ImageRegion) -> i32 {
+    crate::api::wrap_result_code(|| image.set_region(region))
+}
+
 /// Download the device data of an image to the host memory
 ///
 /// # Parameters
@@ -291,12 +781,14 @@ pub extern "C" fn txkit_image_map_read(image: &'static Image) -> *mut MappedImag
 ///
 /// # Returns
 ///
-/// Pointer to the pixel data, or null if the conversion failed.
+/// Pointer to the packed, row-major pixel data, or null if the conversion failed or the image
+/// has another memory layout.
 #[no_mangle]
 pub extern "C" fn txkit_image_map_read_data_u8(read_map: &MappedImageDataReadBox) -> *const u8 {
     read_map
         .ptr
         .as_u8_nd_array()
+        .filter(|data| data.is_standard_layout())
         .map(|ptr| ptr.as_ptr())
         .unwrap_or(std::ptr::null())
 }
@@ -309,12 +801,76 @@ pub extern "C" fn txkit_image_map_read_data_u8(read_map: &MappedImageDataReadBox
 ///
 /// # Returns
 ///
-/// Pointer to the pixel data, or null if the conversion failed.
+/// Pointer to the packed, row-major pixel data, or null if the conversion failed or the image
+/// has another memory layout.
 #[no_mangle]
 pub extern "C" fn txkit_image_map_read_data_f32(read_map: &MappedImageDataReadBox) -> *const f32 {
     read_map
         .ptr
         .as_f32_nd_array()
+        .filter(|data| data.is_standard_layout())
+        .map(|ptr| ptr.as_ptr())
+        .unwrap_or(std::ptr::null())
+}
+
+/// Get a pointer to the image pixels through the given map.
+///
+/// # Parameters
+///
+/// * `read_map`: map to access
+///
+/// # Returns
+///
+/// Pointer to the packed, row-major pixel data, or null if the conversion failed or the image
+/// has another memory layout.
+#[no_mangle]
+pub extern "C" fn txkit_image_map_read_data_u16(read_map: &MappedImageDataReadBox) -> *const u16 {
+    read_map
+        .ptr
+        .as_u16_nd_array()
+        .filter(|data| data.is_standard_layout())
+        .map(|ptr| ptr.as_ptr())
+        .unwrap_or(std::ptr::null())
+}
+
+/// Get a pointer to the image pixels through the given map.
+///
+/// Half-precision floating point values are returned as their raw IEEE 754 binary16 bits.
+///
+/// # Parameters
+///
+/// * `read_map`: map to access
+///
+/// # Returns
+///
+/// Pointer to the packed, row-major pixel data, or null if the conversion failed or the image
+/// has another memory layout.
+#[no_mangle]
+pub extern "C" fn txkit_image_map_read_data_f16(read_map: &MappedImageDataReadBox) -> *const u16 {
+    read_map
+        .ptr
+        .as_f16_nd_array()
+        .filter(|data| data.is_standard_layout())
+        .map(|ptr| ptr.as_ptr() as *const u16)
+        .unwrap_or(std::ptr::null())
+}
+
+/// Get a pointer to the image pixels through the given map.
+///
+/// # Parameters
+///
+/// * `read_map`: map to access
+///
+/// # Returns
+///
+/// Pointer to the packed, row-major pixel data, or null if the conversion failed or the image
+/// has another memory layout.
+#[no_mangle]
+pub extern "C" fn txkit_image_map_read_data_i32(read_map: &MappedImageDataReadBox) -> *const i32 {
+    read_map
+        .ptr
+        .as_i32_nd_array()
+        .filter(|data| data.is_standard_layout())
         .map(|ptr| ptr.as_ptr())
         .unwrap_or(std::ptr::null())
 }
@@ -357,7 +913,8 @@ pub extern "C" fn txkit_image_map_write(image: &'static mut Image) -> *mut Mappe
 ///
 /// # Returns
 ///
-/// Pointer to the pixel data, or null if the conversion failed.
+/// Pointer to the packed, row-major pixel data, or null if the conversion failed or the image
+/// has another memory layout.
 #[no_mangle]
 pub extern "C" fn txkit_image_map_write_data_u8(
     write_map: &mut MappedImageDataWriteBox,
@@ -365,6 +922,7 @@ pub extern "C" fn txkit_image_map_write_data_u8(
     write_map
         .ptr
         .as_u8_nd_array_mut()
+        .filter(|data| data.is_standard_layout())
         .map(|mut ptr| ptr.as_mut_ptr())
         .unwrap_or(std::ptr::null_mut())
 }
@@ -377,7 +935,8 @@ pub extern "C" fn txkit_image_map_write_data_u8(
 ///
 /// # Returns
 ///
-/// Pointer to the pixel data, or null if the conversion failed.
+/// Pointer to the packed, row-major pixel data, or null if the conversion failed or the image
+/// has another memory layout.
 #[no_mangle]
 pub extern "C" fn txkit_image_map_write_data_f32(
     write_map: &mut MappedImageDataWriteBox,
@@ -385,6 +944,75 @@ pub extern "C" fn txkit_image_map_write_data_f32(
     write_map
         .ptr
         .as_f32_nd_array_mut()
+        .filter(|data| data.is_standard_layout())
+        .map(|mut ptr| ptr.as_mut_ptr())
+        .unwrap_or(std::ptr::null_mut())
+}
+
+/// Get a pointer to the image pixels through the given map.
+///
+/// # Parameters
+///
+/// * `write_map`: map to access
+///
+/// # Returns
+///
+/// Pointer to the packed, row-major pixel data, or null if the conversion failed or the image
+/// has another memory layout.
+#[no_mangle]
+pub extern "C" fn txkit_image_map_write_data_u16(
+    write_map: &mut MappedImageDataWriteBox,
+) -> *mut u16 {
+    write_map
+        .ptr
+        .as_u16_nd_array_mut()
+        .filter(|data| data.is_standard_layout())
+        .map(|mut ptr| ptr.as_mut_ptr())
+        .unwrap_or(std::ptr::null_mut())
+}
+
+/// Get a pointer to the image pixels through the given map.
+///
+/// Half-precision floating point values are returned as their raw IEEE 754 binary16 bits.
+///
+/// # Parameters
+///
+/// * `write_map`: map to access
+///
+/// # Returns
+///
+/// Pointer to the packed, row-major pixel data, or null if the conversion failed or the image
+/// has another memory layout.
+#[no_mangle]
+pub extern "C" fn txkit_image_map_write_data_f16(
+    write_map: &mut MappedImageDataWriteBox,
+) -> *mut u16 {
+    write_map
+        .ptr
+        .as_f16_nd_array_mut()
+        .filter(|data| data.is_standard_layout())
+        .map(|mut ptr| ptr.as_mut_ptr() as *mut u16)
+        .unwrap_or(std::ptr::null_mut())
+}
+
+/// Get a pointer to the image pixels through the given map.
+///
+/// # Parameters
+///
+/// * `write_map`: map to access
+///
+/// # Returns
+///
+/// Pointer to the packed, row-major pixel data, or null if the conversion failed or the image
+/// has another memory layout.
+#[no_mangle]
+pub extern "C" fn txkit_image_map_write_data_i32(
+    write_map: &mut MappedImageDataWriteBox,
+) -> *mut i32 {
+    write_map
+        .ptr
+        .as_i32_nd_array_mut()
+        .filter(|data| data.is_standard_layout())
         .map(|mut ptr| ptr.as_mut_ptr())
         .unwrap_or(std::ptr::null_mut())
 }
@@ -5,7 +5,10 @@ use std::io::prelude::*;
 use std::path::PathBuf;
 
 use argh::FromArgs;
-use color_eyre::eyre::Result;
+use color_eyre::eyre::{eyre, Result};
+
+mod params;
+mod tiled;
 
 fn write_method_result(
     width: u32,
@@ -75,6 +78,7 @@ fn write_method_result(
 
 fn write_gpu_method_result(
     mut method: Box<dyn txkit_core::method::Method>,
+    params: Option<&dyn txkit_core::method::MethodParams>,
     args: &Args,
 ) -> Result<()> {
     let width = args.size;
@@ -91,7 +95,7 @@ fn write_gpu_method_result(
     )?;
 
     // Compute resulting image
-    method.compute(&mut ctx, &mut img, None)?;
+    method.compute(&mut ctx, &mut img, params.map(|params| params.as_any()))?;
 
     // Sync image
     img.download()?;
@@ -105,6 +109,7 @@ fn write_gpu_method_result(
 
 fn write_cpu_method_result(
     mut method: Box<dyn txkit_core::method::Method>,
+    params: Option<&dyn txkit_core::method::MethodParams>,
     args: &Args,
 ) -> Result<()> {
     let width = args.size;
@@ -120,7 +125,7 @@ fn write_cpu_method_result(
     );
 
     // Compute resulting image
-    method.compute(&mut ctx, &mut img, None)?;
+    method.compute(&mut ctx, &mut img, params.map(|params| params.as_any()))?;
 
     // Sync image
     img.download()?;
@@ -136,8 +141,16 @@ fn write_cpu_method_result(
 /// txkit command-line interface
 struct Args {
     #[argh(option, short = 'm')]
-    /// built-in method to render
-    method: String,
+    /// method to render
+    method: Option<String>,
+
+    #[argh(switch)]
+    /// list the available methods instead of rendering one
+    list: bool,
+
+    #[argh(option)]
+    /// load methods from a plugin shared library (can be repeated)
+    plugin: Vec<PathBuf>,
 
     #[argh(option, short = 'o')]
     /// output path
@@ -147,11 +160,69 @@ struct Args {
     /// size of the output in pixels
     size: usize,
 
+    #[argh(option, short = 'p')]
+    /// set a method parameter, as key=value (vectors as comma-separated components)
+    param: Vec<String>,
+
+    #[argh(option)]
+    /// read method parameters from a TOML or JSON file
+    params: Option<PathBuf>,
+
+    #[argh(switch)]
+    /// list the parameters of the method instead of rendering it
+    list_params: bool,
+
+    #[argh(option, short = 't')]
+    /// render the output in tiles of this size in pixels, streaming them to the output path
+    tile_size: Option<usize>,
+
     #[argh(switch)]
     /// force use of the CPU for computing results
     cpu: bool,
 }
 
+impl Args {
+    /// Name of the method to render
+    fn method_name(&self) -> Result<&str> {
+        self.method
+            .as_deref()
+            .ok_or_else(|| eyre!("no method given, use --list to list the available methods"))
+    }
+}
+
+/// Print the registered methods, with their descriptions
+fn print_methods(registry: &txkit_core::method::MethodRegistry) {
+    for name in registry.names() {
+        let info = match registry.info(name) {
+            Some(info) => info,
+            None => {
+                println!("{}", name);
+                continue;
+            }
+        };
+
+        let contexts = [("CPU", info.cpu), ("GPU", info.gpu)]
+            .iter()
+            .filter(|(_, supported)| *supported)
+            .map(|(context, _)| *context)
+            .collect::<Vec<_>>();
+        let element_types = info
+            .element_types
+            .iter()
+            .map(|element_type| format!("{:?}", element_type))
+            .collect::<Vec<_>>();
+
+        println!("{}: {}", name, info.description);
+        println!(
+            "    contexts: {}; element types: {}; up to {}D; params: {}",
+            contexts.join(", "),
+            element_types.join(", "),
+            info.max_dimensions,
+            info.params_type
+        );
+    }
+}
+
 fn main() -> Result<()> {
     color_eyre::install()?;
     env_logger::Builder::from_default_env()
@@ -160,21 +231,40 @@ fn main() -> Result<()> {
         .try_init()?;
 
     let args: Args = argh::from_env();
-    let registry = txkit_builtin::methods::new_registry();
-
-    if args.cpu {
-        write_cpu_method_result(
-            registry
-                .build(args.method.as_str())
-                .ok_or(txkit_core::Error::MethodNotFound)?,
-            &args,
-        )
+    let mut registry = txkit_builtin::methods::new_registry();
+
+    for path in &args.plugin {
+        // Plugins are trusted, as they are explicitly given on the command line
+        unsafe { registry.load_plugin(path)? };
+        info!("Loaded plugin {}", path.display());
+    }
+
+    if args.list {
+        print_methods(&registry);
+        return Ok(());
+    }
+
+    let method = registry
+        .build(args.method_name()?)
+        .ok_or(txkit_core::Error::MethodNotFound)?;
+
+    if args.list_params {
+        return params::print_schema(method.as_ref(), &args);
+    }
+
+    let params = params::method_params(method.as_ref(), &args)?;
+
+    if let Some(tile_size) = args.tile_size {
+        let ctx = if args.cpu {
+            txkit_core::context::Context::new_cpu()?
+        } else {
+            txkit_core::context::Context::new_gpu()?
+        };
+
+        tiled::write_tiled_method_result(method, params.as_deref(), ctx, tile_size, &args)
+    } else if args.cpu {
+        write_cpu_method_result(method, params.as_deref(), &args)
     } else {
-        write_gpu_method_result(
-            registry
-                .build(args.method.as_str())
-                .ok_or(txkit_core::Error::MethodNotFound)?,
-            &args,
-        )
+        write_gpu_method_result(method, params.as_deref(), &args)
     }
 }
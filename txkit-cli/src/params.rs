@@ -0,0 +1,161 @@
+//! Method parameters given on the command line
+
+use std::path::Path;
+
+use color_eyre::eyre::{eyre, Result, WrapErr};
+
+use txkit_core::method::{Method, MethodParams, ParamValue};
+
+use crate::Args;
+
+/// Set the parameter `name`, with the list of valid parameters in the error for unknown names
+fn set_param(params: &mut dyn MethodParams, name: &str, value: &ParamValue) -> Result<()> {
+    match params.set_param(name, value) {
+        Err(txkit_core::Error::UnknownParameter(name)) => Err(eyre!(
+            "unknown parameter `{}`, expected one of: {}",
+            name,
+            params
+                .schema()
+                .iter()
+                .map(|info| info.name)
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+        other => Ok(other?),
+    }
+}
+
+fn from_toml(value: &toml::Value) -> Option<ParamValue> {
+    match value {
+        toml::Value::Integer(i) => Some(ParamValue::Int(*i)),
+        toml::Value::Float(f) => Some(ParamValue::Float(*f)),
+        toml::Value::String(s) => s.parse().ok(),
+        toml::Value::Array(a) => a
+            .iter()
+            .map(|c| c.as_float().or_else(|| c.as_integer().map(|i| i as f64)))
+            .collect::<Option<_>>()
+            .map(ParamValue::Vector),
+        _ => None,
+    }
+}
+
+fn from_json(value: &serde_json::Value) -> Option<ParamValue> {
+    match value {
+        serde_json::Value::Number(n) => n
+            .as_i64()
+            .map(ParamValue::Int)
+            .or_else(|| n.as_f64().map(ParamValue::Float)),
+        serde_json::Value::String(s) => s.parse().ok(),
+        serde_json::Value::Array(a) => a
+            .iter()
+            .map(|c| c.as_f64())
+            .collect::<Option<_>>()
+            .map(ParamValue::Vector),
+        _ => None,
+    }
+}
+
+/// Read the parameters in a TOML or JSON file, as a table of values
+fn read_params_file(path: &Path) -> Result<Vec<(String, Option<ParamValue>)>> {
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_ascii_lowercase());
+
+    if !matches!(extension.as_deref(), Some("toml") | Some("json")) {
+        return Err(eyre!(
+            "unsupported parameter file {}, expected a .toml or .json file",
+            path.display()
+        ));
+    }
+
+    let contents = std::fs::read_to_string(path)
+        .wrap_err_with(|| format!("failed to read {}", path.display()))?;
+
+    if extension.as_deref() == Some("toml") {
+        let table: toml::value::Table = toml::from_str(&contents)
+            .wrap_err_with(|| format!("failed to parse {}", path.display()))?;
+
+        Ok(table
+            .iter()
+            .map(|(name, value)| (name.clone(), from_toml(value)))
+            .collect())
+    } else {
+        let object: serde_json::Map<String, serde_json::Value> = serde_json::from_str(&contents)
+            .wrap_err_with(|| format!("failed to parse {}", path.display()))?;
+
+        Ok(object
+            .iter()
+            .map(|(name, value)| (name.clone(), from_json(value)))
+            .collect())
+    }
+}
+
+/// Build the parameters for `method` from the command-line arguments
+///
+/// Parameters from the `--params` file are applied first, and can be overridden by `--param`
+/// flags. Methods which do not expose their parameters are only rendered with their defaults.
+pub fn method_params(method: &dyn Method, args: &Args) -> Result<Option<Box<dyn MethodParams>>> {
+    let method_name = args.method_name()?;
+    let mut params = match method.default_params() {
+        Some(params) => params,
+        None if args.params.is_none() && args.param.is_empty() => return Ok(None),
+        None => {
+            return Err(eyre!(
+                "method {} does not expose its parameters",
+                method_name
+            ))
+        }
+    };
+
+    if let Some(path) = &args.params {
+        for (name, value) in read_params_file(path)? {
+            let value = value.ok_or_else(|| {
+                eyre!(
+                    "unsupported value for parameter `{}` in {}",
+                    name,
+                    path.display()
+                )
+            })?;
+
+            set_param(params.as_mut(), &name, &value)?;
+        }
+    }
+
+    for param in &args.param {
+        let (name, value) = param
+            .split_once('=')
+            .ok_or_else(|| eyre!("invalid parameter `{}`, expected key=value", param))?;
+        let name = name.trim();
+        let value = value
+            .parse()
+            .map_err(|_| eyre!("invalid value for parameter `{}`: {}", name, value))?;
+
+        set_param(params.as_mut(), name, &value)?;
+    }
+
+    Ok(Some(params))
+}
+
+/// Print the parameters of `method`, with their types and default values
+pub fn print_schema(method: &dyn Method, args: &Args) -> Result<()> {
+    let method_name = args.method_name()?;
+    let schema = method
+        .param_schema()
+        .ok_or_else(|| eyre!("method {} does not expose its parameters", method_name))?;
+
+    for info in schema {
+        print!(
+            "{} ({:?}, default {})",
+            info.name, info.param_type, info.default
+        );
+
+        if info.doc.is_empty() {
+            println!();
+        } else {
+            println!(": {}", info.doc);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,248 @@
+//! Tiled rendering of outputs too large to be computed in a single image
+//!
+//! The output is rendered one band of rows at a time, each band being split into square tiles
+//! which are computed as regions of the full output. Bands are streamed to the encoder as soon as
+//! they are complete, so memory usage only depends on the width of the output and the tile size.
+
+use std::convert::TryFrom;
+use std::fs::File;
+use std::io::{BufWriter, Seek, Write};
+use std::path::Path;
+
+use color_eyre::eyre::{eyre, Result};
+use tiff::encoder::{TiffEncoder, TiffKind};
+
+use txkit_core::context::Context;
+use txkit_core::image::{Image, ImageDataType, ImageDim, ImageRegion};
+use txkit_core::method::{Method, MethodParams};
+
+use crate::Args;
+
+/// Renders bands of rows of the full output, tile by tile
+struct BandRenderer<'a> {
+    method: &'a mut dyn Method,
+    params: Option<&'a dyn MethodParams>,
+    ctx: &'a mut Context,
+    size: usize,
+    tile_size: usize,
+    /// RGBA8 data of the current band
+    band: Vec<u8>,
+}
+
+impl<'a> BandRenderer<'a> {
+    fn new_image(&self, dim: ImageDim) -> Result<Image> {
+        Ok(match self.ctx {
+            Context::Cpu(_) => Image::new_cpu(dim, ImageDataType::UInt8),
+            Context::Gpu(_) => Image::new_gpu_2d(dim, ImageDataType::UInt8, self.ctx)?,
+        })
+    }
+
+    /// Render the band of rows starting at row `y`
+    fn render(&mut self, y: usize) -> Result<&[u8]> {
+        let rows = self.tile_size.min(self.size - y);
+        self.band.resize(self.size * rows * 4, 0);
+
+        for x in (0..self.size).step_by(self.tile_size) {
+            let columns = self.tile_size.min(self.size - x);
+
+            let mut img = self.new_image(ImageDim::new(columns, rows, 4))?;
+            // Pixel offsets are exact in f32 for sizes up to 2^24
+            img.set_region(ImageRegion {
+                x: x as f32,
+                y: y as f32,
+                ..ImageRegion::full(ImageDim::new(self.size, self.size, 4))
+            })?;
+
+            self.method.compute(
+                self.ctx,
+                &mut img,
+                self.params.map(|params| params.as_any()),
+            )?;
+            img.download()?;
+
+            // Copy the rows of the tile into the band
+            let data = img.data()?;
+            let tile = data
+                .as_u8_nd_array()
+                .ok_or_else(|| eyre!("tile image is not UInt8"))?;
+            let tile = tile
+                .as_slice()
+                .ok_or_else(|| eyre!("tile image is not contiguous"))?;
+
+            for (j, row) in tile.chunks_exact(columns * 4).enumerate() {
+                let start = (j * self.size + x) * 4;
+                self.band[start..start + row.len()].copy_from_slice(row);
+            }
+        }
+
+        debug!("Rendered rows {} to {} of {}", y, y + rows, self.size);
+        Ok(&self.band)
+    }
+}
+
+/// Convert the output size to the 32-bit size of the encoders
+fn encoder_size(size: usize) -> Result<u32> {
+    u32::try_from(size).map_err(|_| eyre!("the output size {} does not fit in 32 bits", size))
+}
+
+/// Check if an RGBA8 output of `size` pixels, with strips of `tile_size` rows, has to be written
+/// as a BigTIFF file
+///
+/// Classic TIFF files address their contents with 32-bit offsets, so the pixel data and the strip
+/// tables written after it must end within 4 GiB. Larger outputs use the 64-bit offsets of
+/// BigTIFF, which not every reader supports.
+fn needs_big_tiff(size: usize, tile_size: usize) -> bool {
+    // Header, image file directory and tag values other than the strip tables
+    const OVERHEAD: u64 = 4096;
+
+    // Upper bound on the number of strips, without overflowing
+    let strips = (size / tile_size + 1) as u64;
+    let file_size = (size as u64)
+        .checked_mul(size as u64)
+        .and_then(|pixels| pixels.checked_mul(4))
+        .and_then(|data| data.checked_add(strips * 2 * 4))
+        .and_then(|data| data.checked_add(OVERHEAD));
+
+    !matches!(file_size, Some(file_size) if file_size <= u64::from(u32::MAX))
+}
+
+fn write_png(path: &Path, renderer: &mut BandRenderer) -> Result<()> {
+    let size = encoder_size(renderer.size)?;
+
+    let mut encoder = png::Encoder::new(BufWriter::new(File::create(path)?), size, size);
+    encoder.set_color(png::ColorType::RGBA);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header()?.into_stream_writer();
+
+    for y in (0..renderer.size).step_by(renderer.tile_size) {
+        writer.write_all(renderer.render(y)?)?;
+    }
+
+    writer.finish()?;
+    Ok(())
+}
+
+fn write_tiff(path: &Path, renderer: &mut BandRenderer) -> Result<()> {
+    let writer = BufWriter::new(File::create(path)?);
+
+    if needs_big_tiff(renderer.size, renderer.tile_size) {
+        info!("Output exceeds 4 GiB, writing a BigTIFF file");
+        write_tiff_image(TiffEncoder::new_big(writer)?, renderer)
+    } else {
+        write_tiff_image(TiffEncoder::new(writer)?, renderer)
+    }
+}
+
+fn write_tiff_image<W: Write + Seek, K: TiffKind>(
+    mut encoder: TiffEncoder<W, K>,
+    renderer: &mut BandRenderer,
+) -> Result<()> {
+    let size = encoder_size(renderer.size)?;
+    let mut image = encoder.new_image::<tiff::encoder::colortype::RGBA8>(size, size)?;
+
+    // One strip per band, which is at most the size of the output
+    image.rows_per_strip(encoder_size(renderer.tile_size.min(renderer.size))?)?;
+
+    for y in (0..renderer.size).step_by(renderer.tile_size) {
+        image.write_strip(renderer.render(y)?)?;
+    }
+
+    image.finish()?;
+    Ok(())
+}
+
+/// Render the output of `method` in tiles of `tile_size` pixels, and stream it to the output path
+///
+/// The output format is selected from the extension of the output path, either PNG or TIFF. TIFF
+/// outputs over 4 GiB are written as BigTIFF files.
+pub fn write_tiled_method_result(
+    mut method: Box<dyn Method>,
+    params: Option<&dyn MethodParams>,
+    mut ctx: Context,
+    tile_size: usize,
+    args: &Args,
+) -> Result<()> {
+    let output_path = args
+        .output_path
+        .as_ref()
+        .ok_or_else(|| eyre!("tiled rendering requires an output path"))?;
+
+    if tile_size == 0 {
+        return Err(eyre!("the tile size must be positive"));
+    }
+
+    let extension = output_path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_ascii_lowercase());
+
+    // Fail early rather than after rendering most of the output
+    encoder_size(args.size)?;
+
+    let mut renderer = BandRenderer {
+        method: method.as_mut(),
+        params,
+        ctx: &mut ctx,
+        size: args.size,
+        tile_size,
+        band: Vec::new(),
+    };
+
+    match extension.as_deref() {
+        Some("png") => write_png(output_path, &mut renderer)?,
+        Some("tif") | Some("tiff") => write_tiff(output_path, &mut renderer)?,
+        _ => {
+            return Err(eyre!(
+                "tiled rendering only supports PNG and TIFF outputs, got {}",
+                output_path.display()
+            ))
+        }
+    }
+
+    info!("Wrote {}", output_path.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn big_tiff_above_4_gib() {
+        // 16k outputs fit in 1 GiB, 32k outputs need 4 GiB for the pixel data alone
+        assert!(!needs_big_tiff(16384, 512));
+        assert!(needs_big_tiff(32768, 512));
+        assert!(needs_big_tiff(usize::MAX, 512));
+
+        // The largest square RGBA8 classic TIFF, with room for the strip tables and header
+        assert!(!needs_big_tiff(32767, 32767));
+        assert!(needs_big_tiff(32767, 1));
+    }
+
+    #[cfg(feature = "cpu")]
+    #[test]
+    fn big_tiff_is_readable() {
+        // Write the smallest output through the BigTIFF encoder, and read back its pixels
+        let mut ctx = Context::new_cpu().unwrap();
+        let mut method = txkit_builtin::methods::Debug::new();
+        let mut renderer = BandRenderer {
+            method: &mut method,
+            params: None,
+            ctx: &mut ctx,
+            size: 16,
+            tile_size: 8,
+            band: Vec::new(),
+        };
+
+        let mut data = std::io::Cursor::new(Vec::new());
+        write_tiff_image(TiffEncoder::new_big(&mut data).unwrap(), &mut renderer).unwrap();
+
+        data.set_position(0);
+        let mut decoder = tiff::decoder::Decoder::new(data).unwrap();
+        assert_eq!(decoder.dimensions().unwrap(), (16, 16));
+        assert!(matches!(
+            decoder.read_image().unwrap(),
+            tiff::decoder::DecodingResult::U8(pixels) if pixels.len() == 16 * 16 * 4
+        ));
+    }
+}
@@ -0,0 +1,109 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Dependencies whose types are shared with plugins
+const SHARED_DEPENDENCIES: &[(&str, Option<&str>)] =
+    &[("ndarray", None), ("tinygl", Some("gpu-core"))];
+
+fn feature_enabled(feature: &str) -> bool {
+    let key = format!("CARGO_FEATURE_{}", feature.to_uppercase().replace('-', "_"));
+    std::env::var_os(key).is_some()
+}
+
+/// Find the lock file of the workspace being built
+///
+/// The target directory is usually in the workspace of the crate being built, which can depend on
+/// txkit-core from outside of it, so the output directory is searched first.
+fn find_lock_file() -> Option<PathBuf> {
+    ["OUT_DIR", "CARGO_MANIFEST_DIR"]
+        .iter()
+        .filter_map(std::env::var_os)
+        .flat_map(|dir| {
+            Path::new(&dir)
+                .ancestors()
+                .map(|dir| dir.join("Cargo.lock"))
+                .collect::<Vec<_>>()
+        })
+        .find(|path| path.is_file())
+}
+
+/// Resolved versions of the package `name` in `lock`, with the commit of git sources
+fn locked_versions(lock: &str, name: &str) -> Vec<String> {
+    lock.split("[[package]]")
+        .filter_map(|package| {
+            let field = |key: &str| {
+                package.lines().find_map(|line| {
+                    let value = line.strip_prefix(key)?.trim_start().strip_prefix('=')?;
+                    Some(value.trim().trim_matches('"').to_owned())
+                })
+            };
+
+            if field("name")? != name {
+                return None;
+            }
+
+            let version = field("version")?;
+            Some(match field("source") {
+                Some(source) if source.starts_with("git+") => match source.rsplit_once('#') {
+                    Some((_, commit)) => format!("{}#{}", version, commit),
+                    None => version,
+                },
+                _ => version,
+            })
+        })
+        .collect()
+}
+
+fn main() {
+    // Plugins must be built with the same compiler, txkit-core features and versions of the
+    // dependencies whose types are shared with the host, since methods are shared as Rust trait
+    // objects
+    let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_owned());
+    let rustc_version = Command::new(rustc)
+        .arg("--version")
+        .output()
+        .ok()
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .unwrap_or_default();
+
+    // Only these features change the types of txkit-core, `plugin` only adds the loader
+    let features: Vec<_> = ["cpu", "gpu", "gpu45", "gpu-core"]
+        .iter()
+        .copied()
+        .filter(|feature| feature_enabled(feature))
+        .collect();
+
+    let lock_file = find_lock_file();
+    let lock = lock_file
+        .as_ref()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .unwrap_or_default();
+
+    let dependencies: Vec<_> = SHARED_DEPENDENCIES
+        .iter()
+        .filter(|(_, feature)| feature.map(feature_enabled).unwrap_or(true))
+        .map(|(name, _)| {
+            let versions = locked_versions(&lock, name);
+            if versions.is_empty() {
+                format!("{} unknown", name)
+            } else {
+                format!("{} {}", name, versions.join("/"))
+            }
+        })
+        .collect();
+
+    println!("cargo:rerun-if-changed=build.rs");
+    if let Some(lock_file) = lock_file {
+        println!("cargo:rerun-if-changed={}", lock_file.display());
+    }
+
+    println!(
+        "cargo:rustc-env=TXKIT_RUSTC_VERSION={}",
+        rustc_version.trim()
+    );
+    println!(
+        "cargo:rustc-env=TXKIT_CORE_BUILD={}; {}",
+        features.join(","),
+        dependencies.join(", ")
+    );
+}
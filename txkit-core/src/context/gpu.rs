@@ -8,6 +8,9 @@ use tinygl::wrappers::GlHandle;
 use crate::image::{gpu::GpuImageData, ImageDim};
 use crate::Result;
 
+/// Maximum number of planes of a render target, the minimum value of `GL_MAX_DRAW_BUFFERS`
+const MAX_RENDER_PLANES: usize = 8;
+
 /// txkit internal context for GPU computations
 #[allow(dead_code)]
 pub struct GpuContext {
@@ -83,6 +86,16 @@ impl GpuContext {
         tgt: &mut GpuImageData,
         mut f: impl FnMut(&Rc<tinygl::Context>, u32) -> Result<()>,
     ) -> Result<()> {
+        // Methods write normalized or floating-point colors, which integer targets cannot hold
+        if tgt.element_type.is_integer() {
+            return Err(crate::Error::FormatNotSupported);
+        }
+
+        // Every plane needs its own draw buffer
+        if tgt.planes.len() > MAX_RENDER_PLANES {
+            return Err(crate::Error::FormatNotSupported);
+        }
+
         // Setup framebuffer
         let dim = tgt.dim;
 
@@ -102,47 +115,68 @@ impl GpuContext {
 
             let mut r = Ok(());
 
-            match tgt.target() {
-                tinygl::gl::TEXTURE_2D => {
-                    tgt.texture.bind(&*self.gl, tinygl::gl::TEXTURE_2D);
+            let gl = &self.gl;
+            let target = tgt.target();
+            let attach =
+                |texture: Option<&tinygl::wrappers::Texture>, attachment: u32, layer: usize| {
+                    match target {
+                        tinygl::gl::TEXTURE_2D => {
+                            gl.framebuffer_texture_2d(
+                                tinygl::gl::FRAMEBUFFER,
+                                attachment,
+                                tinygl::gl::TEXTURE_2D,
+                                texture,
+                                0,
+                            );
+                        }
+                        tinygl::gl::TEXTURE_3D => {
+                            gl.framebuffer_texture_3d(
+                                tinygl::gl::FRAMEBUFFER,
+                                attachment,
+                                tinygl::gl::TEXTURE_3D,
+                                texture,
+                                0,
+                                layer as i32,
+                            );
+                        }
+                        _ => panic!("invalid texture target"),
+                    }
+                };
+
+            // Each plane is attached at the same index. Shaders only write the first plane, at
+            // location 0: the other planes are cleared to zero, and kept out of the draw since
+            // their contents would be undefined after it.
+            let draw_buffers: Vec<_> = (0..tgt.planes.len())
+                .map(|index| tinygl::gl::COLOR_ATTACHMENT0 + index as u32)
+                .collect();
+            let shader_buffers: Vec<_> = draw_buffers
+                .iter()
+                .enumerate()
+                .map(|(index, attachment)| {
+                    if index == 0 {
+                        *attachment
+                    } else {
+                        tinygl::gl::NONE
+                    }
+                })
+                .collect();
 
-                    // Set texture
-                    self.gl.framebuffer_texture_2d(
-                        tinygl::gl::FRAMEBUFFER,
-                        tinygl::gl::COLOR_ATTACHMENT0,
-                        tinygl::gl::TEXTURE_2D,
-                        Some(&tgt.texture),
-                        0,
-                    );
-                }
-                tinygl::gl::TEXTURE_3D => {
-                    tgt.texture.bind(&*self.gl, tinygl::gl::TEXTURE_3D);
+            for layer in 0..dim.depth {
+                for (plane, attachment) in tgt.planes.iter().zip(&draw_buffers) {
+                    attach(Some(&plane.texture), *attachment, layer);
                 }
-                _ => panic!("invalid texture target"),
-            }
 
-            for layer in 0..dim.depth {
-                match tgt.target() {
-                    tinygl::gl::TEXTURE_2D => {}
-                    tinygl::gl::TEXTURE_3D => {
-                        // Set texture
-                        self.gl.framebuffer_texture_3d(
-                            tinygl::gl::FRAMEBUFFER,
-                            tinygl::gl::COLOR_ATTACHMENT0,
-                            tinygl::gl::TEXTURE_3D,
-                            Some(&tgt.texture),
-                            0,
-                            layer as i32,
-                        );
-                    }
-                    _ => panic!("invalid texture target"),
+                if draw_buffers.len() > 1 {
+                    gl.draw_buffers(&draw_buffers);
+                    gl.clear_color(0., 0., 0., 0.);
+                    gl.clear(tinygl::gl::COLOR_BUFFER_BIT);
                 }
 
                 // Setup draw buffers
-                //self.gl.draw_buffers(&[tinygl::gl::COLOR_ATTACHMENT0]);
+                gl.draw_buffers(&shader_buffers);
 
                 // Call rendering method
-                r = f(&self.gl, layer as u32);
+                r = f(gl, layer as u32);
                 if r.is_err() {
                     // Abort rendering on first layer error
                     break;
@@ -150,13 +184,13 @@ impl GpuContext {
             }
 
             // Cleanup
-            // Unbind texture from framebuffer
-            self.gl.framebuffer_texture(
-                tinygl::gl::FRAMEBUFFER,
-                tinygl::gl::COLOR_ATTACHMENT0,
-                None,
-                0,
-            );
+            // Unbind textures from framebuffer
+            for attachment in &draw_buffers {
+                self.gl
+                    .framebuffer_texture(tinygl::gl::FRAMEBUFFER, *attachment, None, 0);
+            }
+
+            self.gl.draw_buffers(&[tinygl::gl::COLOR_ATTACHMENT0]);
 
             self.gl.bind_texture(tgt.target(), None);
             self.gl.bind_vertex_array(None);
@@ -15,10 +15,35 @@ pub enum Error {
     CpuContextCreationFailed(#[from] rayon::ThreadPoolBuildError),
     #[error("method initialization failed: {0}")]
     MethodInitializationFailed(String),
+    #[error("image creation failed: {0}")]
+    ImageCreationFailed(#[from] crate::image::ImageCreationError),
     #[error("mapping image failed: {0}")]
     MappingFailed(#[from] crate::image::ImageDataError),
     #[error("the provided parameters do not apply to the given method")]
     InvalidParameters,
+    #[error("the image region is invalid or not supported by the method")]
+    InvalidImageRegion,
+    #[error("the images have different dimensions")]
+    ImageDimensionMismatch,
+    #[error("invalid conversion policy: {0}")]
+    InvalidConversionPolicy(&'static str),
+    #[error("unknown parameter `{0}`")]
+    UnknownParameter(String),
+    #[error("invalid value for parameter `{name}`: expected {expected}")]
+    InvalidParameterValue {
+        name: String,
+        expected: &'static str,
+    },
+    #[error("failed to load plugin {}: {reason}", path.display())]
+    PluginLoadFailed {
+        path: std::path::PathBuf,
+        reason: String,
+    },
+    #[error("incompatible plugin {}: {reason}", path.display())]
+    IncompatiblePlugin {
+        path: std::path::PathBuf,
+        reason: String,
+    },
 
     #[cfg(feature = "gpu-core")]
     #[error("gpu context creation failed: {0}")]
@@ -1,4 +1,8 @@
+mod convert;
+pub use convert::*;
+
 mod cpu;
+pub use cpu::{CpuElement, ReleaseFn};
 #[cfg(feature = "gpu-core")]
 pub mod gpu;
 
@@ -11,16 +15,23 @@ pub use image_data_type::*;
 mod image_dimensions;
 pub use image_dimensions::*;
 
+mod image_region;
+pub use image_region::*;
+
 mod into_element_type;
 pub use into_element_type::*;
 
 pub mod prelude;
 
+pub use half::f16;
+
+use ndarray::{Array4, ArrayViewMut4, ShapeBuilder};
 use thiserror::Error;
 
 /// Image that can be sent accross for FFI
 pub struct Image {
     data: Box<dyn ImageData>,
+    region: Option<ImageRegion>,
 }
 
 impl std::ops::Deref for Image {
@@ -44,10 +55,41 @@ pub enum ImageCreationError {
     #[cfg(feature = "gpu-core")]
     #[error("failed to create the image: {0}")]
     ImageCreationFailed(#[from] tinygl::Error),
-    #[error("unsupported number of channels: {0} (expected <= 4)")]
+    #[error("unsupported number of channels: {0} (expected at least 1)")]
     InvalidChannelCount(usize),
     #[error("invalid image dimensions for the requested dimension")]
     InvalidImageSize,
+    #[error("null image buffer")]
+    NullBuffer,
+    #[error("the image strides make elements overlap or overflow")]
+    InvalidStrides,
+}
+
+/// Check that no two elements of an image with the given strides share the same memory, and
+/// that the offset of every element fits in an `isize`
+fn strides_are_disjoint(dim: ImageDim, strides: [usize; 4]) -> bool {
+    let (d, h, w, c) = dim.into_nd_array_dim();
+    let mut axes: Vec<_> = [d, h, w, c]
+        .iter()
+        .copied()
+        .zip(strides.iter().copied())
+        .filter(|(len, _)| *len > 1)
+        .collect();
+    axes.sort_by_key(|(_, stride)| *stride);
+
+    let mut extent = 1;
+    for (len, stride) in axes {
+        if stride < extent {
+            return false;
+        }
+
+        extent = match stride.checked_mul(len) {
+            Some(extent) => extent,
+            None => return false,
+        };
+    }
+
+    extent <= isize::MAX as usize
 }
 
 impl Image {
@@ -56,10 +98,90 @@ impl Image {
             data: match element_type {
                 ImageDataType::UInt8 => Box::new(cpu::UInt8ImageData::new(dim)),
                 ImageDataType::Float32 => Box::new(cpu::FloatImageData::new(dim)),
+                ImageDataType::UInt16 => Box::new(cpu::UInt16ImageData::new(dim)),
+                ImageDataType::Float16 => Box::new(cpu::HalfImageData::new(dim)),
+                ImageDataType::Int32 => Box::new(cpu::Int32ImageData::new(dim)),
             },
+            region: None,
+        }
+    }
+
+    /// Create a CPU image adopting the elements of `data`, without copying them
+    ///
+    /// The axes of `data` are the depth, height, width and channels of the image, in any memory
+    /// order.
+    pub fn from_nd_array<T: CpuElement>(data: Array4<T>) -> Self {
+        Self {
+            data: T::new_owned(data),
+            region: None,
+        }
+    }
+
+    /// Create a CPU image over the elements of `data`, without copying them
+    ///
+    /// # Safety
+    ///
+    /// The elements viewed by `data` must outlive the image, and must not be accessed otherwise
+    /// while the image exists.
+    pub unsafe fn from_nd_array_view<T: CpuElement>(data: ArrayViewMut4<'_, T>) -> Self {
+        Self {
+            data: T::new_external(
+                std::mem::transmute::<ArrayViewMut4<'_, T>, ArrayViewMut4<'static, T>>(data),
+                None,
+            ),
+            region: None,
         }
     }
 
+    /// Create a CPU image over host memory, without copying it
+    ///
+    /// # Parameters
+    ///
+    /// * `data`: pointer to the first element of the image
+    /// * `dim`: dimensions of the image
+    /// * `strides`: distance, in elements, between consecutive layers, rows, pixels and channels,
+    ///   or `None` for tightly packed elements
+    /// * `release`: function called when the image is dropped, to adopt the memory
+    ///
+    /// # Safety
+    ///
+    /// `data` must be valid for reads and writes of all the elements of the image until it is
+    /// dropped, and the elements must not be accessed otherwise while the image exists.
+    pub unsafe fn from_raw_parts<T: CpuElement>(
+        data: *mut T,
+        dim: ImageDim,
+        strides: Option<[usize; 4]>,
+        release: Option<ReleaseFn>,
+    ) -> Result<Self, ImageCreationError> {
+        if data.is_null() {
+            return Err(ImageCreationError::NullBuffer);
+        }
+
+        let strides = match strides {
+            Some(strides) => strides,
+            None => {
+                let row = dim.width.checked_mul(dim.channels);
+                match row.and_then(|row| row.checked_mul(dim.height).map(|layer| (layer, row))) {
+                    Some((layer, row)) => [layer, row, dim.channels, 1],
+                    None => return Err(ImageCreationError::InvalidStrides),
+                }
+            }
+        };
+
+        if !strides_are_disjoint(dim, strides) {
+            return Err(ImageCreationError::InvalidStrides);
+        }
+
+        let shape = dim
+            .into_nd_array_dim()
+            .strides((strides[0], strides[1], strides[2], strides[3]));
+
+        Ok(Self {
+            data: T::new_external(ArrayViewMut4::from_shape_ptr(shape, data), release),
+            region: None,
+        })
+    }
+
     #[cfg(feature = "gpu-core")]
     pub fn new_gpu_1d(
         dim: ImageDim,
@@ -76,6 +198,7 @@ impl Image {
                         dim,
                         element_type,
                     )?),
+                    region: None,
                 })
             })
     }
@@ -96,6 +219,7 @@ impl Image {
                         dim,
                         element_type,
                     )?),
+                    region: None,
                 })
             })
     }
@@ -116,6 +240,7 @@ impl Image {
                         dim,
                         element_type,
                     )?),
+                    region: None,
                 })
             })
     }
@@ -146,6 +271,27 @@ impl Image {
     ) -> Result<Self, ImageCreationError> {
         Err(ImageCreationError::ContextNotSupported)
     }
+
+    /// Get the region of the full image rendered into this image
+    ///
+    /// Unless set otherwise, this image is the full image.
+    pub fn region(&self) -> ImageRegion {
+        self.region.unwrap_or_else(|| ImageRegion::full(self.dim()))
+    }
+
+    /// Set the region of the full image rendered into this image
+    ///
+    /// # Parameters
+    ///
+    /// * `region`: window of the full image plane, see [`ImageRegion::is_valid`]
+    pub fn set_region(&mut self, region: ImageRegion) -> crate::Result<()> {
+        if !region.is_valid(self.dim()) {
+            return Err(crate::Error::InvalidImageRegion);
+        }
+
+        self.region = Some(region);
+        Ok(())
+    }
 }
 
 impl std::fmt::Debug for Image {
@@ -0,0 +1,378 @@
+//! Conversion of images between element types
+//!
+//! Conversions always run on the host. GPU images are downloaded before being converted and
+//! uploaded afterwards, including when both images are on the GPU: there is no shader pass for
+//! conversions yet.
+
+use ndarray::{Array2, Array4, ArrayViewMut4, Axis, Zip};
+
+use super::*;
+
+/// Mapping of the source values to the range of the target element type
+///
+/// Values are mapped as floats, in the same range as [`IntoElementType::into_f32`]: [0, 1] for
+/// normalized types, unnormalized for `Int32`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ValueMapping {
+    /// Keep the values, which are clamped to the range of integer target types
+    #[default]
+    Clamp,
+    /// Remap the [min, max] range of each channel to [0, 1]
+    Normalize,
+    /// Linearly remap the `from` range to the `to` range
+    ///
+    /// The bounds must be finite, and the `from` range must not be empty.
+    Remap { from: [f32; 2], to: [f32; 2] },
+    /// Encode linear values with the sRGB transfer function, leaving the alpha channel linear
+    ///
+    /// Only luminance-alpha (2 channels) and RGBA (4 channels) images have an alpha channel, the
+    /// last one. Every channel of the other images is encoded.
+    SrgbEncode,
+}
+
+/// Threshold mask added to the values before they are quantized
+#[derive(Debug, Clone, PartialEq)]
+pub enum DitherMask {
+    /// 8x8 Bayer ordered dithering matrix
+    Ordered,
+    /// Threshold map in [0, 1), indexed by (y, x) and tiled over the image
+    ///
+    /// A blue noise map, as rendered by the built-in `blue_noise` method, gives the best results.
+    Map(Array2<f32>),
+}
+
+impl DitherMask {
+    fn threshold(&self, x: usize, y: usize) -> f32 {
+        match self {
+            Self::Ordered => {
+                // Bit-reversed interleaving of the coordinates gives the Bayer index
+                let (x, y) = (x % 8, y % 8);
+                let v = x ^ y;
+                let index = ((v & 1) << 5)
+                    | ((x & 1) << 4)
+                    | ((v & 2) << 2)
+                    | ((x & 2) << 1)
+                    | ((v & 4) >> 1)
+                    | ((x & 4) >> 2);
+                (index as f32 + 0.5) / 64.
+            }
+            Self::Map(map) => {
+                let (h, w) = map.dim();
+                map[(y % h, x % w)]
+            }
+        }
+    }
+}
+
+/// Policy for converting an image to another element type
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConversionPolicy {
+    /// Mapping of the source values
+    pub mapping: ValueMapping,
+    /// Dithering applied when quantizing to integer types, none by default
+    pub dither: Option<DitherMask>,
+}
+
+/// Size of a quantization step of `element_type`, for integer types
+fn quantization_step(element_type: ImageDataType) -> Option<f32> {
+    match element_type {
+        ImageDataType::UInt8 => Some(1. / 255.),
+        ImageDataType::UInt16 => Some(1. / 65535.),
+        ImageDataType::Int32 => Some(1.),
+        ImageDataType::Float32 | ImageDataType::Float16 => None,
+    }
+}
+
+fn srgb_encode(x: f32) -> f32 {
+    let x = x.clamp(0., 1.);
+    if x <= 0.0031308 {
+        12.92 * x
+    } else {
+        1.055 * x.powf(1. / 2.4) - 0.055
+    }
+}
+
+/// Index of the alpha channel of images with `channels` channels, if they have one
+fn alpha_channel(channels: usize) -> Option<usize> {
+    match channels {
+        2 | 4 => Some(channels - 1),
+        _ => None,
+    }
+}
+
+/// Read the values of `data` as floats
+fn read_values(data: &dyn MappedImageData) -> Option<Array4<f32>> {
+    if let Some(data) = data.as_f32_nd_array() {
+        Some(data.to_owned())
+    } else if let Some(data) = data.as_u8_nd_array() {
+        Some(data.mapv(|x| x.into_f32()))
+    } else if let Some(data) = data.as_u16_nd_array() {
+        Some(data.mapv(|x| x.into_f32()))
+    } else if let Some(data) = data.as_f16_nd_array() {
+        Some(data.mapv(|x| x.into_f32()))
+    } else {
+        data.as_i32_nd_array()
+            .map(|data| data.mapv(|x| x.into_f32()))
+    }
+}
+
+fn write_values<T>(mut tgt: ArrayViewMut4<T>, values: &Array4<f32>, convert: impl Fn(f32) -> T) {
+    Zip::from(&mut tgt)
+        .and(values)
+        .for_each(|t, v| *t = convert(*v));
+}
+
+/// Map and quantize `values` for the target `element_type`, according to `policy`
+fn apply_policy(
+    values: &mut Array4<f32>,
+    element_type: ImageDataType,
+    policy: &ConversionPolicy,
+) -> crate::Result<()> {
+    match policy.mapping {
+        ValueMapping::Clamp => {}
+        ValueMapping::Normalize => {
+            for mut channel in values.axis_iter_mut(Axis(3)) {
+                let (min, max) = channel
+                    .iter()
+                    .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), x| {
+                        (min.min(*x), max.max(*x))
+                    });
+                let range = max - min;
+
+                channel.mapv_inplace(|x| if range > 0. { (x - min) / range } else { 0. });
+            }
+        }
+        ValueMapping::Remap { from, to } => {
+            let range = from[1] - from[0];
+            if range == 0. || !range.is_finite() || !(to[1] - to[0]).is_finite() {
+                return Err(crate::Error::InvalidConversionPolicy(
+                    "remapped ranges must be finite, and the source range not empty",
+                ));
+            }
+
+            let scale = (to[1] - to[0]) / range;
+            values.mapv_inplace(|x| to[0] + (x - from[0]) * scale);
+        }
+        ValueMapping::SrgbEncode => {
+            let alpha = alpha_channel(values.len_of(Axis(3)));
+
+            for (l, mut channel) in values.axis_iter_mut(Axis(3)).enumerate() {
+                if Some(l) != alpha {
+                    channel.mapv_inplace(srgb_encode);
+                }
+            }
+        }
+    }
+
+    if let (Some(mask), Some(step)) = (&policy.dither, quantization_step(element_type)) {
+        // Conversions to integers round down, so the threshold decides the rounding direction
+        for ((_, j, i, _), x) in values.indexed_iter_mut() {
+            let threshold = mask.threshold(i, j);
+
+            *x = if element_type == ImageDataType::Int32 {
+                (*x + threshold).floor()
+            } else {
+                *x + threshold * step
+            };
+        }
+    }
+
+    Ok(())
+}
+
+impl Image {
+    /// Convert this image into `tgt`, which may have another element type
+    ///
+    /// Both images must have the same dimensions. Conversions run on the host, even between GPU
+    /// images: the source is downloaded first, and the target is uploaded once written.
+    ///
+    /// # Parameters
+    ///
+    /// * `tgt`: image to write the converted values to
+    /// * `policy`: mapping and dithering of the values
+    pub fn convert_to(&mut self, tgt: &mut Image, policy: &ConversionPolicy) -> crate::Result<()> {
+        if self.dim() != tgt.dim() {
+            return Err(crate::Error::ImageDimensionMismatch);
+        }
+
+        self.download()?;
+
+        let mut values = {
+            let data = self.data()?;
+            read_values(&*data).ok_or(crate::Error::FormatNotSupported)?
+        };
+
+        let element_type = tgt.element_type();
+        apply_policy(&mut values, element_type, policy)?;
+
+        {
+            let mut data = tgt.data_mut()?;
+
+            if let Some(data) = data.as_u8_nd_array_mut() {
+                write_values(data, &values, |x| x.into_u8());
+            } else if let Some(data) = data.as_f32_nd_array_mut() {
+                write_values(data, &values, |x| x);
+            } else if let Some(data) = data.as_u16_nd_array_mut() {
+                write_values(data, &values, |x| x.into_u16());
+            } else if let Some(data) = data.as_f16_nd_array_mut() {
+                write_values(data, &values, |x| x.into_f16());
+            } else if let Some(data) = data.as_i32_nd_array_mut() {
+                write_values(data, &values, |x| x.into_i32());
+            } else {
+                return Err(crate::Error::FormatNotSupported);
+            }
+        }
+
+        tgt.upload()
+    }
+
+    /// Convert this image to a new CPU image of `element_type`
+    ///
+    /// The new image covers the same region of the full image as this one.
+    pub fn converted(
+        &mut self,
+        element_type: ImageDataType,
+        policy: &ConversionPolicy,
+    ) -> crate::Result<Image> {
+        let mut tgt = Image::new_cpu(self.dim(), element_type);
+        tgt.region = self.region;
+
+        self.convert_to(&mut tgt, policy)?;
+        Ok(tgt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ramp(width: usize) -> Image {
+        let mut img = Image::new_cpu(ImageDim::new(width, 8, 1), ImageDataType::Float32);
+        img.data_mut()
+            .unwrap()
+            .as_f32_nd_array_mut()
+            .unwrap()
+            .indexed_iter_mut()
+            .for_each(|((_, _, i, _), x)| *x = i as f32 / (width - 1) as f32);
+        img
+    }
+
+    fn u8_values(img: &Image) -> Vec<u8> {
+        img.data()
+            .unwrap()
+            .as_u8_nd_array()
+            .unwrap()
+            .iter()
+            .copied()
+            .collect()
+    }
+
+    #[test]
+    fn clamp_matches_into_element_type() {
+        let mut img = ramp(16);
+        let converted = img
+            .converted(ImageDataType::UInt8, &ConversionPolicy::default())
+            .unwrap();
+
+        let expected: Vec<_> = img
+            .data()
+            .unwrap()
+            .as_f32_nd_array()
+            .unwrap()
+            .iter()
+            .map(|x| x.into_u8())
+            .collect();
+        assert_eq!(u8_values(&converted), expected);
+    }
+
+    #[test]
+    fn normalize_and_remap() {
+        let mut img = ramp(16);
+        let policy = ConversionPolicy {
+            mapping: ValueMapping::Remap {
+                from: [0., 1.],
+                to: [2., 4.],
+            },
+            ..Default::default()
+        };
+        let mut remapped = img.converted(ImageDataType::Float32, &policy).unwrap();
+
+        let policy = ConversionPolicy {
+            mapping: ValueMapping::Normalize,
+            ..Default::default()
+        };
+        let normalized = remapped.converted(ImageDataType::Float32, &policy).unwrap();
+
+        let data = img.data().unwrap();
+        let normalized = normalized.data().unwrap();
+        let max_diff = Zip::from(data.as_f32_nd_array().unwrap())
+            .and(normalized.as_f32_nd_array().unwrap())
+            .fold(0.0f32, |max, a, b| max.max((a - b).abs()));
+        assert!(max_diff < 1e-6);
+    }
+
+    #[test]
+    fn dithering_preserves_mean() {
+        // Flat image halfway between two 8-bit levels
+        let mut img = Image::new_cpu(ImageDim::new(8, 8, 1), ImageDataType::Float32);
+        img.data_mut()
+            .unwrap()
+            .as_f32_nd_array_mut()
+            .unwrap()
+            .fill(100.5 / 255.);
+
+        let policy = ConversionPolicy {
+            dither: Some(DitherMask::Ordered),
+            ..Default::default()
+        };
+        let values = u8_values(&img.converted(ImageDataType::UInt8, &policy).unwrap());
+
+        assert_eq!(values.iter().filter(|x| **x == 100).count(), 32);
+        assert_eq!(values.iter().filter(|x| **x == 101).count(), 32);
+    }
+
+    #[test]
+    fn empty_remap_ranges_are_rejected() {
+        let mut img = ramp(16);
+        let policy = ConversionPolicy {
+            mapping: ValueMapping::Remap {
+                from: [0.5, 0.5],
+                to: [0., 1.],
+            },
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            img.converted(ImageDataType::Float32, &policy),
+            Err(crate::Error::InvalidConversionPolicy(_))
+        ));
+    }
+
+    #[test]
+    fn srgb_encode_alpha_follows_channel_count() {
+        let policy = ConversionPolicy {
+            mapping: ValueMapping::SrgbEncode,
+            ..Default::default()
+        };
+
+        for (channels, alpha) in [(1, None), (2, Some(1)), (3, None), (4, Some(3)), (8, None)] {
+            let mut img = Image::new_cpu(ImageDim::new(1, 1, channels), ImageDataType::Float32);
+            img.data_mut()
+                .unwrap()
+                .as_f32_nd_array_mut()
+                .unwrap()
+                .fill(0.5);
+
+            let converted = img.converted(ImageDataType::Float32, &policy).unwrap();
+            let data = converted.data().unwrap();
+            for (l, x) in data.as_f32_nd_array().unwrap().iter().enumerate() {
+                let expected = if Some(l) == alpha {
+                    0.5
+                } else {
+                    srgb_encode(0.5)
+                };
+                assert_eq!(*x, expected, "{} channels, channel {}", channels, l);
+            }
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use half::f16;
 use ndarray::{Array4, ArrayView4, ArrayViewMut4};
 
 use super::*;
@@ -26,6 +27,45 @@ impl<T: IntoElementType> ImageDataBase for NdArrayImageData<T> {
     }
 }
 
+/// Function called when an image wrapping external memory is dropped
+pub type ReleaseFn = Box<dyn FnOnce()>;
+
+/// Image data in memory owned by the caller
+pub struct ExternalImageData<T: 'static> {
+    data: ArrayViewMut4<'static, T>,
+    release: Option<ReleaseFn>,
+}
+
+impl<T: IntoElementType> ImageDataBase for ExternalImageData<T> {
+    fn dim(&self) -> ImageDim {
+        self.data.dim().into()
+    }
+
+    fn element_type(&self) -> ImageDataType {
+        T::into_element_type()
+    }
+}
+
+impl<T> Drop for ExternalImageData<T> {
+    fn drop(&mut self) {
+        if let Some(release) = self.release.take() {
+            release();
+        }
+    }
+}
+
+/// Element types of CPU images
+pub trait CpuElement: IntoElementType + Sized + 'static {
+    /// Image data adopting `data`
+    fn new_owned(data: Array4<Self>) -> Box<dyn ImageData>;
+
+    /// Image data over the memory of `data`, calling `release` when dropped
+    fn new_external(
+        data: ArrayViewMut4<'static, Self>,
+        release: Option<ReleaseFn>,
+    ) -> Box<dyn ImageData>;
+}
+
 struct MappedNdArray<T> {
     tgt: T,
 }
@@ -52,6 +92,22 @@ macro_rules! _mapped_image_data {
                 }
             }
         }
+
+        impl MappedImageData for MappedNdArray<&ExternalImageData<$t>> {
+            paste::item! {
+                fn [<as_ $t _nd_array>](&self) -> Option<ArrayView4<$t>> {
+                    Some(self.tgt.data.view())
+                }
+            }
+        }
+
+        impl MappedImageDataMut for MappedNdArray<&mut ExternalImageData<$t>> {
+            paste::item! {
+                fn [<as_ $t _nd_array_mut>](&mut self) -> Option<ArrayViewMut4<$t>> {
+                    Some(self.tgt.data.view_mut())
+                }
+            }
+        }
     }
 }
 
@@ -71,6 +127,29 @@ macro_rules! _image_data {
                 Ok(Box::new(MappedNdArray { tgt: self }))
             }
         }
+
+        impl ImageData for ExternalImageData<$t> {
+            fn data(&self) -> Result<Box<dyn MappedImageData + '_>, ImageDataError> {
+                Ok(Box::new(MappedNdArray { tgt: self }))
+            }
+
+            fn data_mut(&mut self) -> Result<Box<dyn MappedImageDataMut + '_>, ImageDataError> {
+                Ok(Box::new(MappedNdArray { tgt: self }))
+            }
+        }
+
+        impl CpuElement for $t {
+            fn new_owned(data: Array4<Self>) -> Box<dyn ImageData> {
+                Box::new(NdArrayImageData { data })
+            }
+
+            fn new_external(
+                data: ArrayViewMut4<'static, Self>,
+                release: Option<ReleaseFn>,
+            ) -> Box<dyn ImageData> {
+                Box::new(ExternalImageData { data, release })
+            }
+        }
     };
 }
 
@@ -93,4 +172,4 @@ macro_rules! impl_for_types {
     };
 }
 
-impl_for_types!(u8 => UInt8, f32 => Float);
+impl_for_types!(u8 => UInt8, f32 => Float, u16 => UInt16, f16 => Half, i32 => Int32);
@@ -1,15 +1,31 @@
 use std::cell::RefCell;
 use std::rc::Rc;
 
+use half::f16;
 use tinygl::wrappers::{Buffer, GlRefHandle, Texture};
 
 use super::*;
 use crate::Error;
 
+/// Maximum number of channels held by a single texture
+pub const PLANE_CHANNELS: usize = 4;
+
+/// Texture holding up to four consecutive channels of an image, with its transfer buffer
+pub(crate) struct GpuPlane {
+    pub(crate) texture: Texture,
+    buffer: Buffer,
+    /// Dimensions of the plane, with the number of channels it holds
+    pub(crate) dim: ImageDim,
+}
+
+/// Image stored in GPU memory
+///
+/// Images with more than four channels are backed by several textures (planes), the plane `k`
+/// holding the channels `4k` to `4k + 3`. Methods render to all the planes at once, the plane `k`
+/// being the draw buffer `k`, and they are interleaved again when the image is mapped.
 pub struct GpuImageData {
     gl: Rc<tinygl::Context>,
-    pub(crate) texture: Texture,
-    pub(crate) buffer: Buffer,
+    pub(crate) planes: Vec<GpuPlane>,
     pub(crate) element_type: ImageDataType,
     pub(crate) dim: ImageDim,
 
@@ -25,58 +41,76 @@ impl GpuImageData {
         target: u32,
         allocator: impl Fn(ImageDim, ImageDataType) -> Result<(), ImageCreationError>,
     ) -> Result<Self, ImageCreationError> {
-        let texture = GlRefHandle::new(&*gl, Texture::new(gl)?);
-        let buffer = GlRefHandle::new(&*gl, Buffer::new(gl)?);
-
-        unsafe {
-            texture.bind(gl, target);
-
-            // Allocation result
-            let res = {
-                gl.tex_parameteri(
-                    target,
-                    tinygl::gl::TEXTURE_MIN_FILTER,
-                    tinygl::gl::LINEAR as i32,
-                );
-
-                gl.tex_parameteri(
-                    target,
-                    tinygl::gl::TEXTURE_MAG_FILTER,
-                    tinygl::gl::LINEAR as i32,
-                );
-
-                allocator(dim, element_type)?;
+        if dim.channels == 0 {
+            return Err(ImageCreationError::InvalidChannelCount(dim.channels));
+        }
 
-                // Check that the allocation succeeded
-                gl.check_last_error()
+        let mut planes = Vec::new();
+        for first_channel in (0..dim.channels).step_by(PLANE_CHANNELS) {
+            let plane_dim = ImageDim {
+                channels: (dim.channels - first_channel).min(PLANE_CHANNELS),
+                ..dim
             };
 
-            // Unbind texture after allocation
-            gl.bind_texture(target, None);
+            let texture = GlRefHandle::new(&*gl, Texture::new(gl)?);
+            let buffer = GlRefHandle::new(&*gl, Buffer::new(gl)?);
 
-            // Bind buffer for initialization
-            buffer.bind(&*gl, tinygl::gl::PIXEL_PACK_BUFFER);
+            unsafe {
+                texture.bind(gl, target);
+
+                // Allocation result
+                let res = {
+                    // Integer textures cannot be filtered
+                    let filter = if element_type.is_integer() {
+                        tinygl::gl::NEAREST
+                    } else {
+                        tinygl::gl::LINEAR
+                    };
+
+                    gl.tex_parameteri(target, tinygl::gl::TEXTURE_MIN_FILTER, filter as i32);
+                    gl.tex_parameteri(target, tinygl::gl::TEXTURE_MAG_FILTER, filter as i32);
+
+                    allocator(plane_dim, element_type)?;
+
+                    // Check that the allocation succeeded
+                    gl.check_last_error()
+                };
+
+                // Unbind texture after allocation
+                gl.bind_texture(target, None);
+
+                // Bind buffer for initialization
+                buffer.bind(&*gl, tinygl::gl::PIXEL_PACK_BUFFER);
+
+                // Only needed once since image sizes are immutable
+                gl.buffer_data(
+                    tinygl::gl::PIXEL_PACK_BUFFER,
+                    Self::calc_byte_size(element_type, plane_dim) as isize,
+                    std::ptr::null(),
+                    tinygl::gl::DYNAMIC_READ,
+                );
 
-            // Only needed once since image sizes are immutable
-            gl.buffer_data(
-                tinygl::gl::PIXEL_PACK_BUFFER,
-                Self::calc_byte_size(element_type, dim) as isize,
-                std::ptr::null(),
-                tinygl::gl::DYNAMIC_READ,
-            );
+                gl.check_last_error()?;
 
-            gl.check_last_error()?;
+                // Unbind buffer
+                gl.bind_buffer(tinygl::gl::PIXEL_PACK_BUFFER, None);
 
-            // Unbind buffer
-            gl.bind_buffer(tinygl::gl::PIXEL_PACK_BUFFER, None);
+                res?
+            };
 
-            res?
-        };
+            planes.push((texture, buffer, plane_dim));
+        }
 
         Ok(Self {
             gl: gl.clone(),
-            texture: texture.into_inner(),
-            buffer: buffer.into_inner(),
+            planes: planes
+                .into_iter()
+                .map(|(texture, buffer, dim)| GpuPlane {
+                    texture: texture.into_inner(),
+                    buffer: buffer.into_inner(),
+                    dim,
+                })
+                .collect(),
             element_type,
             dim,
             transfer_sync: RefCell::new(None),
@@ -107,7 +141,7 @@ impl GpuImageData {
                             .ok_or_else(|| ImageCreationError::InvalidChannelCount(dim.channels))?,
                         dim.width as i32,
                         0,
-                        dim.unsized_format()
+                        dim.unsized_format(element_type)
                             .ok_or_else(|| ImageCreationError::InvalidChannelCount(dim.channels))?,
                         element_type.format_type(),
                         None,
@@ -143,7 +177,7 @@ impl GpuImageData {
                         dim.width as i32,
                         dim.height as i32,
                         0,
-                        dim.unsized_format()
+                        dim.unsized_format(element_type)
                             .ok_or_else(|| ImageCreationError::InvalidChannelCount(dim.channels))?,
                         element_type.format_type(),
                         None,
@@ -176,7 +210,7 @@ impl GpuImageData {
                         dim.height as i32,
                         dim.depth as i32,
                         0,
-                        dim.unsized_format()
+                        dim.unsized_format(element_type)
                             .ok_or_else(|| ImageCreationError::InvalidChannelCount(dim.channels))?,
                         element_type.format_type(),
                         None,
@@ -192,6 +226,11 @@ impl GpuImageData {
         self.target
     }
 
+    /// Texture holding the first four channels, which are the ones methods can sample or write
+    pub fn texture(&self) -> &Texture {
+        &self.planes[0].texture
+    }
+
     pub fn byte_size(&self) -> usize {
         Self::calc_byte_size(self.element_type, self.dim)
     }
@@ -202,16 +241,18 @@ impl GpuImageData {
 
     fn start_download(&mut self) -> Result<(), Error> {
         unsafe {
-            self.buffer.bind(&*self.gl, tinygl::gl::PIXEL_PACK_BUFFER);
-
-            self.texture.bind(&*self.gl, self.target);
-            self.gl.get_tex_image(
-                self.target,
-                0,
-                self.dim.unsized_format().unwrap(),
-                self.element_type.format_type(),
-                std::ptr::null_mut(),
-            );
+            for plane in &self.planes {
+                plane.buffer.bind(&*self.gl, tinygl::gl::PIXEL_PACK_BUFFER);
+
+                plane.texture.bind(&*self.gl, self.target);
+                self.gl.get_tex_image(
+                    self.target,
+                    0,
+                    plane.dim.unsized_format(self.element_type).unwrap(),
+                    self.element_type.format_type(),
+                    std::ptr::null_mut(),
+                );
+            }
 
             self.gl.bind_buffer(tinygl::gl::PIXEL_PACK_BUFFER, None);
 
@@ -237,65 +278,75 @@ impl GpuImageData {
 
     fn start_upload(&mut self) -> Result<(), Error> {
         unsafe {
-            self.buffer.bind(&*self.gl, tinygl::gl::PIXEL_UNPACK_BUFFER);
-            self.texture.bind(&*self.gl, self.target);
-
-            match self.target {
-                tinygl::gl::TEXTURE_1D => {
-                    self.gl.tex_image_1d(
-                        self.target,
-                        0,
-                        self.dim
-                            .internal_format(self.element_type)
-                            .expect("incompatible internal format"),
-                        self.dim.width as _,
-                        0,
-                        self.dim
-                            .unsized_format()
-                            .expect("incompatible unsized format"),
-                        self.element_type.format_type(),
-                        None,
-                    );
-                }
-                tinygl::gl::TEXTURE_2D => {
-                    self.gl.tex_image_2d(
-                        self.target,
-                        0,
-                        self.dim
-                            .internal_format(self.element_type)
-                            .expect("incompatible internal format"),
-                        self.dim.width as _,
-                        self.dim.height as _,
-                        0,
-                        self.dim
-                            .unsized_format()
-                            .expect("incompatible unsized format"),
-                        self.element_type.format_type(),
-                        None,
-                    );
-                }
-                tinygl::gl::TEXTURE_3D => {
-                    self.gl.tex_image_3d(
-                        self.target,
-                        0,
-                        self.dim
-                            .internal_format(self.element_type)
-                            .expect("incompatible internal format"),
-                        self.dim.width as _,
-                        self.dim.height as _,
-                        self.dim.depth as _,
-                        0,
-                        self.dim
-                            .unsized_format()
-                            .expect("incompatible unsized format"),
-                        self.element_type.format_type(),
-                        None,
-                    );
+            for plane in &self.planes {
+                plane
+                    .buffer
+                    .bind(&*self.gl, tinygl::gl::PIXEL_UNPACK_BUFFER);
+                plane.texture.bind(&*self.gl, self.target);
+
+                match self.target {
+                    tinygl::gl::TEXTURE_1D => {
+                        self.gl.tex_image_1d(
+                            self.target,
+                            0,
+                            plane
+                                .dim
+                                .internal_format(self.element_type)
+                                .expect("incompatible internal format"),
+                            plane.dim.width as _,
+                            0,
+                            plane
+                                .dim
+                                .unsized_format(self.element_type)
+                                .expect("incompatible unsized format"),
+                            self.element_type.format_type(),
+                            None,
+                        );
+                    }
+                    tinygl::gl::TEXTURE_2D => {
+                        self.gl.tex_image_2d(
+                            self.target,
+                            0,
+                            plane
+                                .dim
+                                .internal_format(self.element_type)
+                                .expect("incompatible internal format"),
+                            plane.dim.width as _,
+                            plane.dim.height as _,
+                            0,
+                            plane
+                                .dim
+                                .unsized_format(self.element_type)
+                                .expect("incompatible unsized format"),
+                            self.element_type.format_type(),
+                            None,
+                        );
+                    }
+                    tinygl::gl::TEXTURE_3D => {
+                        self.gl.tex_image_3d(
+                            self.target,
+                            0,
+                            plane
+                                .dim
+                                .internal_format(self.element_type)
+                                .expect("incompatible internal format"),
+                            plane.dim.width as _,
+                            plane.dim.height as _,
+                            plane.dim.depth as _,
+                            0,
+                            plane
+                                .dim
+                                .unsized_format(self.element_type)
+                                .expect("incompatible unsized format"),
+                            self.element_type.format_type(),
+                            None,
+                        );
+                    }
+                    _ => unreachable!("unknown texture target"),
                 }
-                _ => unreachable!("unknown texture target"),
-            }
 
-            self.gl.check_last_error()?;
+                self.gl.check_last_error()?;
+            }
 
             self.gl.bind_texture(self.target, None);
             self.gl.bind_buffer(tinygl::gl::PIXEL_UNPACK_BUFFER, None);
@@ -304,7 +355,7 @@ impl GpuImageData {
         }
     }
 
-    unsafe fn map_buffer(&self, usage: u32) -> Result<*mut u8, ImageDataError> {
+    unsafe fn map_buffer(&self, plane: usize, usage: u32) -> Result<*mut u8, ImageDataError> {
         if let Some(fence_sync) = self.transfer_sync.borrow_mut().take() {
             loop {
                 match self.gl.client_wait_sync(
@@ -327,12 +378,13 @@ impl GpuImageData {
             }
         }
 
-        self.buffer.bind(&*self.gl, tinygl::gl::PIXEL_PACK_BUFFER);
+        let plane = &self.planes[plane];
+        plane.buffer.bind(&*self.gl, tinygl::gl::PIXEL_PACK_BUFFER);
 
         let ptr = self.gl.map_buffer_range(
             tinygl::gl::PIXEL_PACK_BUFFER,
             0,
-            self.byte_size() as isize,
+            Self::calc_byte_size(self.element_type, plane.dim) as isize,
             usage,
         );
 
@@ -345,11 +397,64 @@ impl GpuImageData {
         }
     }
 
-    unsafe fn unmap_buffer(&self) {
-        self.buffer.bind(&*self.gl, tinygl::gl::PIXEL_PACK_BUFFER);
+    unsafe fn unmap_buffer(&self, plane: usize) {
+        self.planes[plane]
+            .buffer
+            .bind(&*self.gl, tinygl::gl::PIXEL_PACK_BUFFER);
         self.gl.unmap_buffer(tinygl::gl::PIXEL_PACK_BUFFER);
         self.gl.bind_buffer(tinygl::gl::PIXEL_PACK_BUFFER, None);
     }
+
+    /// Allocate a host buffer for all the channels of the image, aligned for any element type
+    fn new_staging(&self) -> Vec<u32> {
+        vec![0; (self.byte_size() + 3) / 4]
+    }
+
+    /// Map each plane in turn, calling `f` with the mapped plane, and the byte offsets and length
+    /// of each run of plane channels in the plane and in the interleaved image
+    unsafe fn map_planes(
+        &self,
+        usage: u32,
+        mut f: impl FnMut(*mut u8, usize, usize, usize),
+    ) -> Result<(), ImageDataError> {
+        let element_size = self.element_type.byte_size();
+        let texels = self.dim.width * self.dim.height * self.dim.depth;
+
+        for (index, plane) in self.planes.iter().enumerate() {
+            let ptr = self.map_buffer(index, usage)?;
+            let run = plane.dim.channels * element_size;
+
+            for texel in 0..texels {
+                let image_offset =
+                    (texel * self.dim.channels + index * PLANE_CHANNELS) * element_size;
+                f(ptr, texel * run, image_offset, run);
+            }
+
+            self.unmap_buffer(index);
+        }
+
+        Ok(())
+    }
+
+    /// Copy the channels of all planes to `dst`, interleaved
+    unsafe fn gather_planes(&self, dst: *mut u8) -> Result<(), ImageDataError> {
+        self.map_planes(
+            tinygl::gl::MAP_READ_BIT,
+            |plane, offset, image_offset, len| {
+                std::ptr::copy_nonoverlapping(plane.add(offset), dst.add(image_offset), len)
+            },
+        )
+    }
+
+    /// Copy the interleaved channels in `src` back to their planes
+    unsafe fn scatter_planes(&self, src: *const u8) -> Result<(), ImageDataError> {
+        self.map_planes(
+            tinygl::gl::MAP_WRITE_BIT,
+            |plane, offset, image_offset, len| {
+                std::ptr::copy_nonoverlapping(src.add(image_offset), plane.add(offset), len)
+            },
+        )
+    }
 }
 
 impl Drop for GpuImageData {
@@ -357,8 +462,10 @@ impl Drop for GpuImageData {
         use tinygl::wrappers::GlDrop;
 
         unsafe {
-            self.texture.drop(&*self.gl);
-            self.buffer.drop(&*self.gl);
+            for plane in &self.planes {
+                plane.texture.drop(&*self.gl);
+                plane.buffer.drop(&*self.gl);
+            }
         }
     }
 }
@@ -384,105 +491,136 @@ impl ImageDataBase for GpuImageData {
     }
 }
 
+/// Mapped image, pointing to the transfer buffer of single-plane images, or to an interleaved
+/// copy of the planes otherwise
 struct MappedGpuImage<'t> {
     tgt: &'t GpuImageData,
     mapped_ptr: *const u8,
+    staging: Option<Vec<u32>>,
 }
 
 struct MappedGpuImageMut<'t> {
     tgt: &'t mut GpuImageData,
     mapped_ptr: *mut u8,
+    staging: Option<Vec<u32>>,
 }
 
 impl<'t> MappedGpuImage<'t> {
     fn map(tgt: &'t GpuImageData) -> std::result::Result<Self, ImageDataError> {
-        let mapped_ptr = unsafe { tgt.map_buffer(tinygl::gl::MAP_READ_BIT)? };
+        if tgt.planes.len() == 1 {
+            let mapped_ptr = unsafe { tgt.map_buffer(0, tinygl::gl::MAP_READ_BIT)? };
+
+            return Ok(Self {
+                tgt,
+                mapped_ptr,
+                staging: None,
+            });
+        }
+
+        let mut staging = tgt.new_staging();
+        unsafe { tgt.gather_planes(staging.as_mut_ptr() as *mut u8)? };
 
-        Ok(Self { tgt, mapped_ptr })
+        Ok(Self {
+            tgt,
+            mapped_ptr: staging.as_ptr() as *const u8,
+            staging: Some(staging),
+        })
     }
 }
 
 impl Drop for MappedGpuImage<'_> {
     fn drop(&mut self) {
-        unsafe {
-            self.tgt.unmap_buffer();
+        if self.staging.is_none() {
+            unsafe {
+                self.tgt.unmap_buffer(0);
+            }
         }
     }
 }
 
 impl<'t> MappedGpuImageMut<'t> {
     fn map(tgt: &'t mut GpuImageData) -> std::result::Result<Self, ImageDataError> {
-        let mapped_ptr =
-            unsafe { tgt.map_buffer(tinygl::gl::MAP_READ_BIT | tinygl::gl::MAP_WRITE_BIT)? };
+        if tgt.planes.len() == 1 {
+            let mapped_ptr =
+                unsafe { tgt.map_buffer(0, tinygl::gl::MAP_READ_BIT | tinygl::gl::MAP_WRITE_BIT)? };
+
+            return Ok(Self {
+                tgt,
+                mapped_ptr,
+                staging: None,
+            });
+        }
+
+        let mut staging = tgt.new_staging();
+        unsafe { tgt.gather_planes(staging.as_mut_ptr() as *mut u8)? };
 
-        Ok(Self { tgt, mapped_ptr })
+        Ok(Self {
+            tgt,
+            mapped_ptr: staging.as_mut_ptr() as *mut u8,
+            staging: Some(staging),
+        })
     }
 }
 
 impl Drop for MappedGpuImageMut<'_> {
     fn drop(&mut self) {
         unsafe {
-            self.tgt.unmap_buffer();
-        }
-    }
-}
-
-impl MappedImageData for MappedGpuImage<'_> {
-    fn as_f32_nd_array(&self) -> Option<ndarray::ArrayView4<f32>> {
-        if let ImageDataType::Float32 = self.tgt.element_type {
-            unsafe {
-                Some(ndarray::ArrayView4::from_shape_ptr(
-                    self.tgt.dim.into_nd_array_dim(),
-                    std::mem::transmute::<*const u8, *const f32>(self.mapped_ptr),
-                ))
-            }
-        } else {
-            None
-        }
-    }
-
-    fn as_u8_nd_array(&self) -> Option<ndarray::ArrayView4<u8>> {
-        if let ImageDataType::UInt8 = self.tgt.element_type {
-            unsafe {
-                Some(ndarray::ArrayView4::from_shape_ptr(
-                    self.tgt.dim.into_nd_array_dim(),
-                    self.mapped_ptr,
-                ))
+            match &self.staging {
+                Some(staging) => {
+                    if let Err(e) = self.tgt.scatter_planes(staging.as_ptr() as *const u8) {
+                        log::error!("failed to write back mapped image: {}", e);
+                    }
+                }
+                None => self.tgt.unmap_buffer(0),
             }
-        } else {
-            None
         }
     }
 }
 
-impl MappedImageDataMut for MappedGpuImageMut<'_> {
-    fn as_f32_nd_array_mut(&mut self) -> Option<ndarray::ArrayViewMut4<f32>> {
-        if let ImageDataType::Float32 = self.tgt.element_type {
-            unsafe {
-                Some(ndarray::ArrayViewMut4::from_shape_ptr(
-                    self.tgt.dim.into_nd_array_dim(),
-                    std::mem::transmute::<*mut u8, *mut f32>(self.mapped_ptr),
-                ))
+macro_rules! mapped_gpu_image {
+    ($($t:ty => $n:ident),+) => {
+        impl MappedImageData for MappedGpuImage<'_> {
+            paste::item! {
+                $(
+                    fn [<as_ $t _nd_array>](&self) -> Option<ndarray::ArrayView4<$t>> {
+                        if let ImageDataType::$n = self.tgt.element_type {
+                            unsafe {
+                                Some(ndarray::ArrayView4::from_shape_ptr(
+                                    self.tgt.dim.into_nd_array_dim(),
+                                    self.mapped_ptr as *const $t,
+                                ))
+                            }
+                        } else {
+                            None
+                        }
+                    }
+                )+
             }
-        } else {
-            None
         }
-    }
 
-    fn as_u8_nd_array_mut(&mut self) -> Option<ndarray::ArrayViewMut4<u8>> {
-        if let ImageDataType::UInt8 = self.tgt.element_type {
-            unsafe {
-                Some(ndarray::ArrayViewMut4::from_shape_ptr(
-                    self.tgt.dim.into_nd_array_dim(),
-                    self.mapped_ptr,
-                ))
+        impl MappedImageDataMut for MappedGpuImageMut<'_> {
+            paste::item! {
+                $(
+                    fn [<as_ $t _nd_array_mut>](&mut self) -> Option<ndarray::ArrayViewMut4<$t>> {
+                        if let ImageDataType::$n = self.tgt.element_type {
+                            unsafe {
+                                Some(ndarray::ArrayViewMut4::from_shape_ptr(
+                                    self.tgt.dim.into_nd_array_dim(),
+                                    self.mapped_ptr as *mut $t,
+                                ))
+                            }
+                        } else {
+                            None
+                        }
+                    }
+                )+
             }
-        } else {
-            None
         }
-    }
+    };
 }
 
+mapped_gpu_image!(u8 => UInt8, f32 => Float32, u16 => UInt16, f16 => Float16, i32 => Int32);
+
 impl ImageData for GpuImageData {
     fn data(&self) -> std::result::Result<Box<dyn MappedImageData + '_>, ImageDataError> {
         Ok(Box::new(MappedGpuImage::map(self)?))
@@ -1,3 +1,4 @@
+use half::f16;
 use ndarray::{ArrayView4, ArrayViewMut4};
 use thiserror::Error;
 
@@ -45,6 +46,21 @@ pub trait MappedImageData {
     fn as_u8_nd_array(&self) -> Option<ArrayView4<u8>> {
         None
     }
+
+    /// Get the image as an u16 nd-array
+    fn as_u16_nd_array(&self) -> Option<ArrayView4<u16>> {
+        None
+    }
+
+    /// Get the image as an f16 nd-array
+    fn as_f16_nd_array(&self) -> Option<ArrayView4<f16>> {
+        None
+    }
+
+    /// Get the image as an i32 nd-array
+    fn as_i32_nd_array(&self) -> Option<ArrayView4<i32>> {
+        None
+    }
 }
 
 pub trait MappedImageDataMut {
@@ -57,6 +73,21 @@ pub trait MappedImageDataMut {
     fn as_u8_nd_array_mut(&mut self) -> Option<ArrayViewMut4<u8>> {
         None
     }
+
+    /// Get the image as a mutable u16 nd-array
+    fn as_u16_nd_array_mut(&mut self) -> Option<ArrayViewMut4<u16>> {
+        None
+    }
+
+    /// Get the image as a mutable f16 nd-array
+    fn as_f16_nd_array_mut(&mut self) -> Option<ArrayViewMut4<f16>> {
+        None
+    }
+
+    /// Get the image as a mutable i32 nd-array
+    fn as_i32_nd_array_mut(&mut self) -> Option<ArrayViewMut4<i32>> {
+        None
+    }
 }
 
 #[derive(Debug, Error)]
@@ -6,15 +6,42 @@ pub enum ImageDataType {
     UInt8,
     /// Single-precision floating point (32 bits)
     Float32,
+    /// Unsigned short integers (16 bits)
+    UInt16,
+    /// Half-precision floating point (16 bits)
+    Float16,
+    /// Signed integers (32 bits), holding unnormalized values such as ranks
+    ///
+    /// Methods computing normalized values do not support this type.
+    Int32,
 }
 
 impl ImageDataType {
+    /// All the element types, in declaration order
+    pub const ALL: [ImageDataType; 5] = [
+        Self::UInt8,
+        Self::Float32,
+        Self::UInt16,
+        Self::Float16,
+        Self::Int32,
+    ];
+
     pub fn byte_size(&self) -> usize {
         match self {
             Self::UInt8 => std::mem::size_of::<u8>(),
             Self::Float32 => std::mem::size_of::<f32>(),
+            Self::UInt16 => std::mem::size_of::<u16>(),
+            Self::Float16 => std::mem::size_of::<half::f16>(),
+            Self::Int32 => std::mem::size_of::<i32>(),
         }
     }
+
+    /// true if elements are unnormalized integers, which cannot be rendered to by GPU methods
+    ///
+    /// Unsigned types are normalized to the [0, 1] range, and can be rendered to.
+    pub fn is_integer(&self) -> bool {
+        matches!(self, Self::Int32)
+    }
 }
 
 #[cfg(feature = "gpu-core")]
@@ -31,6 +58,9 @@ mod gpu {
             match self {
                 ImageDataType::UInt8 => gl::UNSIGNED_BYTE,
                 ImageDataType::Float32 => gl::FLOAT,
+                ImageDataType::UInt16 => gl::UNSIGNED_SHORT,
+                ImageDataType::Float16 => gl::HALF_FLOAT,
+                ImageDataType::Int32 => gl::INT,
             }
         }
     }
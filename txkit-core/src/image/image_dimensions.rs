@@ -1,5 +1,5 @@
 #[repr(C)]
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct ImageDimensions<T> {
     pub width: T,
     pub height: T,
@@ -69,7 +69,7 @@ mod gpu {
     pub trait ImageDimGpuExt {
         fn internal_format(&self, element_type: ImageDataType) -> Option<i32>;
 
-        fn unsized_format(&self) -> Option<u32>;
+        fn unsized_format(&self, element_type: ImageDataType) -> Option<u32>;
 
         fn into_cgmath(&self) -> cgmath::Vector3<u32>;
     }
@@ -91,15 +91,47 @@ mod gpu {
                     4 => Some(gl::RGBA32F as i32),
                     _ => None,
                 },
+                ImageDataType::UInt16 => match self.channels {
+                    1 => Some(gl::R16 as i32),
+                    2 => Some(gl::RG16 as i32),
+                    3 => Some(gl::RGB16 as i32),
+                    4 => Some(gl::RGBA16 as i32),
+                    _ => None,
+                },
+                ImageDataType::Float16 => match self.channels {
+                    1 => Some(gl::R16F as i32),
+                    2 => Some(gl::RG16F as i32),
+                    3 => Some(gl::RGB16F as i32),
+                    4 => Some(gl::RGBA16F as i32),
+                    _ => None,
+                },
+                ImageDataType::Int32 => match self.channels {
+                    1 => Some(gl::R32I as i32),
+                    2 => Some(gl::RG32I as i32),
+                    3 => Some(gl::RGB32I as i32),
+                    4 => Some(gl::RGBA32I as i32),
+                    _ => None,
+                },
             }
         }
-        fn unsized_format(&self) -> Option<u32> {
-            match self.channels {
-                1 => Some(gl::RED),
-                2 => Some(gl::RG),
-                3 => Some(gl::RGB),
-                4 => Some(gl::RGBA),
-                _ => None,
+        fn unsized_format(&self, element_type: ImageDataType) -> Option<u32> {
+            // Unnormalized integer textures are transferred using the integer formats
+            if element_type.is_integer() {
+                match self.channels {
+                    1 => Some(gl::RED_INTEGER),
+                    2 => Some(gl::RG_INTEGER),
+                    3 => Some(gl::RGB_INTEGER),
+                    4 => Some(gl::RGBA_INTEGER),
+                    _ => None,
+                }
+            } else {
+                match self.channels {
+                    1 => Some(gl::RED),
+                    2 => Some(gl::RG),
+                    3 => Some(gl::RGB),
+                    4 => Some(gl::RGBA),
+                    _ => None,
+                }
             }
         }
 
@@ -0,0 +1,166 @@
+use std::convert::TryFrom;
+
+use super::ImageDim;
+
+/// Window of the plane of a full image which is rendered into a target image
+///
+/// Methods map their [0, 1] texture coordinates to the full image, so the size of a pixel in
+/// texture space is the inverse of the full image size. The window is placed anywhere on the
+/// unbounded plane of the full image, in full image pixels, and its pixels are `scale` full image
+/// pixels wide. Targets rendered with adjacent windows of the same full image thus stitch
+/// seamlessly, and with a scale of 1 and integer offsets they are identical to the matching part
+/// of a render of the full image.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ImageRegion {
+    /// horizontal position of the window origin on the full image plane, in full image pixels
+    pub x: f32,
+    /// vertical position of the window origin on the full image plane, in full image pixels
+    pub y: f32,
+    /// layer position of the window origin on the full image plane, in full image pixels
+    pub z: f32,
+    /// size of a window pixel, in full image pixels
+    pub scale: f32,
+    /// width of the full image, in pixels
+    pub width: usize,
+    /// height of the full image, in pixels
+    pub height: usize,
+    /// depth of the full image, in pixels
+    pub depth: usize,
+}
+
+/// Pixel of a target image, located on the plane of its full image
+///
+/// CPU methods compute the values of the target from its fragments.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Fragment {
+    /// Position of the pixel center on the full image plane, in full image pixels, along x, y
+    /// and z
+    pub position: [f32; 3],
+    /// Channel of the value being computed
+    pub channel: usize,
+}
+
+impl Fragment {
+    /// Index (x, y, z) of the full image pixel the center of this fragment lies in
+    ///
+    /// The pixel may lie outside of the full image.
+    pub fn pixel(&self) -> [i64; 3] {
+        let [x, y, z] = self.position;
+        [x.floor() as i64, y.floor() as i64, z.floor() as i64]
+    }
+}
+
+impl ImageRegion {
+    /// Region covering the whole of an image
+    pub fn full(dim: ImageDim) -> Self {
+        Self {
+            x: 0.,
+            y: 0.,
+            z: 0.,
+            scale: 1.,
+            width: dim.width,
+            height: dim.height,
+            depth: dim.depth,
+        }
+    }
+
+    /// Check if this region can be rendered into a target of dimensions `dim`
+    ///
+    /// The full image must not be empty, and its dimensions must fit in 32 bits like GPU texture
+    /// coordinates. The scale must be positive, and every fragment of the target must have a
+    /// finite position.
+    pub fn is_valid(&self, dim: ImageDim) -> bool {
+        let full_dims_valid = [self.width, self.height, self.depth]
+            .iter()
+            .all(|d| *d > 0 && u32::try_from(*d).is_ok());
+
+        let far_corner = self.fragment((dim.depth, dim.height, dim.width, 0));
+        let positions_finite = [self.x, self.y, self.z]
+            .iter()
+            .chain(far_corner.position.iter())
+            .all(|p| p.is_finite());
+
+        full_dims_valid && self.scale > 0. && self.scale.is_finite() && positions_finite
+    }
+
+    /// Dimensions of the full image, with `channels` channels
+    pub fn full_dim(&self, channels: usize) -> ImageDim {
+        ImageDim::new_3d(self.width, self.height, self.depth, channels)
+    }
+
+    /// Locate a value of the target image on the full image plane
+    ///
+    /// # Parameters
+    ///
+    /// * `idx`: (depth, height, width, channel) index of the value in the target image
+    pub fn fragment(&self, (k, j, i, l): (usize, usize, usize, usize)) -> Fragment {
+        Fragment {
+            position: [
+                self.x + (i as f32 + 0.5) * self.scale,
+                self.y + (j as f32 + 0.5) * self.scale,
+                self.z + (k as f32 + 0.5) * self.scale,
+            ],
+            channel: l,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tile(x: f32, y: f32, scale: f32) -> ImageRegion {
+        ImageRegion {
+            x,
+            y,
+            scale,
+            ..ImageRegion::full(ImageDim::new(64, 64, 4))
+        }
+    }
+
+    #[test]
+    fn full_region_matches_pixel_centers() {
+        let region = ImageRegion::full(ImageDim::new(64, 32, 4));
+        let frag = region.fragment((0, 3, 5, 2));
+
+        assert_eq!(frag.position, [5.5, 3.5, 0.5]);
+        assert_eq!(frag.pixel(), [5, 3, 0]);
+        assert_eq!(frag.channel, 2);
+    }
+
+    #[test]
+    fn windows_lie_anywhere_on_the_plane() {
+        let dim = ImageDim::new(16, 16, 4);
+
+        // Negative and fractional origins, beyond the full image
+        let region = tile(-40.25, 100.5, 0.5);
+        assert!(region.is_valid(dim));
+        assert_eq!(region.fragment((0, 0, 0, 0)).position, [-40., 100.75, 0.25]);
+        assert_eq!(region.fragment((0, 0, 0, 0)).pixel(), [-40, 100, 0]);
+        assert_eq!(region.fragment((0, 1, 1, 0)).pixel(), [-40, 101, 0]);
+    }
+
+    #[test]
+    fn adjacent_windows_stitch() {
+        // The last fragment of a window is one pixel before the first one of the next window
+        let dim = ImageDim::new(16, 16, 4);
+        let left = tile(8., 0., 0.25).fragment((0, 0, 15, 0));
+        let right = tile(8. + 16. * 0.25, 0., 0.25).fragment((0, 0, 0, 0));
+
+        assert_eq!(right.position[0] - left.position[0], 0.25);
+        assert!(tile(8., 0., 0.25).is_valid(dim));
+    }
+
+    #[test]
+    fn invalid_regions() {
+        let dim = ImageDim::new(16, 16, 4);
+
+        assert!(!tile(0., 0., 0.).is_valid(dim));
+        assert!(!tile(0., 0., -1.).is_valid(dim));
+        assert!(!tile(f32::NAN, 0., 1.).is_valid(dim));
+        assert!(!tile(0., f32::INFINITY, 1.).is_valid(dim));
+        assert!(!tile(0., 0., f32::MAX).is_valid(dim));
+        assert!(!ImageRegion::full(ImageDim::new(0, 16, 4)).is_valid(dim));
+    }
+}
@@ -1,9 +1,33 @@
+use half::f16;
+
 use super::ImageDataType;
 
+/// Conversion of computed values to image elements
+///
+/// Unsigned integer elements are normalized to the [0, 1] range, while signed integer elements
+/// hold unnormalized values (e.g. ranks). Normalized values would all be truncated to 0 or 1, so
+/// only types with [`IntoElementType::INTEGER`] set can be written to `Int32` images. The
+/// conversions to the 16-bit and 32-bit integer types default to going through `into_f32`.
+#[allow(clippy::wrong_self_convention)]
 pub trait IntoElementType {
+    /// Whether `into_i32` keeps the values unnormalized, so they can be written to `Int32` images
+    const INTEGER: bool = false;
+
     fn into_element_type() -> ImageDataType;
     fn into_u8(&self) -> u8;
     fn into_f32(&self) -> f32;
+
+    fn into_u16(&self) -> u16 {
+        (self.into_f32() * 65535.0f32).clamp(0.0f32, 65535.0f32) as u16
+    }
+
+    fn into_f16(&self) -> f16 {
+        f16::from_f32(self.into_f32())
+    }
+
+    fn into_i32(&self) -> i32 {
+        self.into_f32() as i32
+    }
 }
 
 impl IntoElementType for f32 {
@@ -32,4 +56,77 @@ impl IntoElementType for u8 {
     fn into_f32(&self) -> f32 {
         *self as f32 / 255.0f32
     }
+
+    fn into_u16(&self) -> u16 {
+        // 0xff maps to 0xffff
+        *self as u16 * 257
+    }
+
+    fn into_i32(&self) -> i32 {
+        *self as i32
+    }
+}
+
+impl IntoElementType for u16 {
+    fn into_element_type() -> ImageDataType {
+        ImageDataType::UInt16
+    }
+
+    fn into_u8(&self) -> u8 {
+        (*self >> 8) as u8
+    }
+
+    fn into_f32(&self) -> f32 {
+        *self as f32 / 65535.0f32
+    }
+
+    fn into_u16(&self) -> u16 {
+        *self
+    }
+
+    fn into_i32(&self) -> i32 {
+        *self as i32
+    }
+}
+
+impl IntoElementType for f16 {
+    fn into_element_type() -> ImageDataType {
+        ImageDataType::Float16
+    }
+
+    fn into_u8(&self) -> u8 {
+        self.to_f32().into_u8()
+    }
+
+    fn into_f32(&self) -> f32 {
+        self.to_f32()
+    }
+
+    fn into_f16(&self) -> f16 {
+        *self
+    }
+}
+
+impl IntoElementType for i32 {
+    const INTEGER: bool = true;
+
+    fn into_element_type() -> ImageDataType {
+        ImageDataType::Int32
+    }
+
+    fn into_u8(&self) -> u8 {
+        (*self).clamp(0, 255) as u8
+    }
+
+    fn into_f32(&self) -> f32 {
+        *self as f32
+    }
+
+    fn into_u16(&self) -> u16 {
+        (*self).clamp(0, 65535) as u16
+    }
+
+    fn into_i32(&self) -> i32 {
+        *self
+    }
 }
@@ -51,6 +51,14 @@ impl ImageIo {
         Self::default()
     }
 
+    /// Check if no image is bound to any texture or image unit
+    pub fn is_unbound(&self) -> bool {
+        self.texture_bindings
+            .iter()
+            .chain(self.image_bindings.iter())
+            .all(|binding| matches!(binding, ImageBinding::None))
+    }
+
     /// Get a texture binding from the given value
     ///
     /// # Parameters
@@ -138,6 +146,116 @@ impl ImageIo {
     }
 }
 
+pub mod cpu {
+    use super::*;
+    use crate::image::IntoElementType;
+    use crate::Result;
+
+    /// Host copy of an image bound to a texture unit
+    ///
+    /// Sampling follows the default state of txkit GPU textures, i.e. `GL_LINEAR` filtering with
+    /// `GL_REPEAT` wrapping, and returns `(0, 0, 0, 1)` for unbound units.
+    #[derive(Debug, Clone, Default)]
+    pub struct TextureSampler {
+        width: usize,
+        height: usize,
+        texels: Vec<cgmath::Vector4<f32>>,
+    }
+
+    impl TextureSampler {
+        /// Copy the first layer of an image for sampling
+        ///
+        /// # Parameters
+        ///
+        /// * `image`: image to copy the texels of
+        pub fn new(image: &Image) -> Result<Self> {
+            let dim = image.dim();
+            let data = image.data()?;
+
+            // Convert the first layer to floats, with the element type checked only once
+            let layer = if let Some(data) = data.as_f32_nd_array() {
+                Self::layer_texels(data)
+            } else if let Some(data) = data.as_u8_nd_array() {
+                Self::layer_texels(data)
+            } else if let Some(data) = data.as_u16_nd_array() {
+                Self::layer_texels(data)
+            } else if let Some(data) = data.as_f16_nd_array() {
+                Self::layer_texels(data)
+            } else if let Some(data) = data.as_i32_nd_array() {
+                Self::layer_texels(data)
+            } else {
+                return Err(crate::Error::FormatNotSupported);
+            };
+
+            let mut texels = Vec::with_capacity(dim.width * dim.height);
+            for j in 0..dim.height {
+                for i in 0..dim.width {
+                    let mut texel = cgmath::vec4(0., 0., 0., 1.);
+
+                    for l in 0..dim.channels.min(4) {
+                        texel[l] = layer[(j, i, l)];
+                    }
+
+                    texels.push(texel);
+                }
+            }
+
+            Ok(Self {
+                width: dim.width,
+                height: dim.height,
+                texels,
+            })
+        }
+
+        fn layer_texels<T: IntoElementType>(data: ndarray::ArrayView4<T>) -> ndarray::Array3<f32> {
+            data.index_axis(ndarray::Axis(0), 0)
+                .map(IntoElementType::into_f32)
+        }
+
+        fn texel(&self, i: i64, j: i64) -> cgmath::Vector4<f32> {
+            let i = i.rem_euclid(self.width as i64) as usize;
+            let j = j.rem_euclid(self.height as i64) as usize;
+            self.texels[i + j * self.width]
+        }
+
+        /// Sample the texture at the given normalized coordinates
+        ///
+        /// # Parameters
+        ///
+        /// * `p`: texture coordinates
+        pub fn texture(&self, p: cgmath::Vector2<f32>) -> cgmath::Vector4<f32> {
+            if self.texels.is_empty() {
+                return cgmath::vec4(0., 0., 0., 1.);
+            }
+
+            let u = p.x * self.width as f32 - 0.5;
+            let v = p.y * self.height as f32 - 0.5;
+            let (i, j) = (u.floor(), v.floor());
+            let (a, b) = (u - i, v - j);
+            let (i, j) = (i as i64, j as i64);
+
+            (self.texel(i, j) * (1. - a) + self.texel(i + 1, j) * a) * (1. - b)
+                + (self.texel(i, j + 1) * (1. - a) + self.texel(i + 1, j + 1) * a) * b
+        }
+    }
+
+    pub trait CpuImageIoExt {
+        fn texture_sampler(&self, index: usize) -> Result<TextureSampler>;
+    }
+
+    impl CpuImageIoExt for ImageIo {
+        fn texture_sampler(&self, index: usize) -> Result<TextureSampler> {
+            match self.get_texture_binding(index) {
+                ImageBinding::None => Ok(TextureSampler::default()),
+                ImageBinding::ImageRef(img) => TextureSampler::new(&img.borrow()),
+                ImageBinding::ImagePtr(img) => TextureSampler::new(unsafe {
+                    img.as_ref().expect("null pointer in CPU image ref")
+                }),
+            }
+        }
+    }
+}
+
 #[cfg(feature = "gpu-core")]
 pub mod gpu {
     use super::*;
@@ -176,7 +294,7 @@ pub mod gpu {
 
                         gl.bind_image_texture(
                             index as _,
-                            Some(&gpu.texture),
+                            Some(gpu.texture()),
                             0,
                             false,
                             0,
@@ -193,7 +311,7 @@ pub mod gpu {
 
                         gl.bind_image_texture(
                             index as _,
-                            Some(&gpu.texture),
+                            Some(gpu.texture()),
                             0,
                             false,
                             0,
@@ -219,7 +337,7 @@ pub mod gpu {
                             .as_gpu_image()
                             .expect("a GPU image is required for an image binding");
 
-                        gl.bind_texture_unit(index as _, gpu.texture.name());
+                        gl.bind_texture_unit(index as _, gpu.texture().name());
                     }
                     ImageBinding::ImagePtr(img) => {
                         let gpu = img
@@ -228,7 +346,7 @@ pub mod gpu {
                             .as_gpu_image()
                             .expect("a GPU image is required for an image binding");
 
-                        gl.bind_texture_unit(index as _, gpu.texture.name());
+                        gl.bind_texture_unit(index as _, gpu.texture().name());
                     }
                 }
             }
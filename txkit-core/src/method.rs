@@ -1,8 +1,17 @@
 use std::any::Any;
 
-use crate::context::Context;
+use crate::context::{Context, CpuContext};
 use crate::error::*;
-use crate::image::Image;
+use crate::image::{Fragment, Image, ImageDim};
+
+mod info;
+pub use info::*;
+
+mod params;
+pub use params::*;
+
+mod plugin;
+pub use plugin::*;
 
 mod registry;
 pub use registry::*;
@@ -17,6 +26,12 @@ mod gpu;
 #[cfg(feature = "gpu-core")]
 pub use self::gpu::*;
 
+/// Number of channels computed by methods, matching the `vec4` output of their shaders
+///
+/// Images may store more channels, e.g. to hold data assembled by the caller, but methods write
+/// zeros to the channels past this count.
+pub const OUTPUT_CHANNELS: usize = 4;
+
 /// Try to downcast a generic params struct into the target params type
 pub fn downcast_params<'u, U: Default + 'static>(
     params: Option<&'u dyn std::any::Any>,
@@ -44,6 +59,9 @@ pub fn downcast_params<'u, U: Default + 'static>(
 }
 
 /// Generic interface to a procedural texturing method
+///
+/// Methods write at most [`OUTPUT_CHANNELS`] channels of their target, and fill the other ones
+/// with zeros.
 pub trait Method {
     fn compute(
         &mut self,
@@ -51,4 +69,43 @@ pub trait Method {
         tgt: &mut Image,
         params: Option<&dyn Any>,
     ) -> Result<()>;
+
+    /// Compute the values of this method at arbitrary fragments, on the host
+    ///
+    /// Unlike [`Method::compute`], the fragments do not have to form the pixels of an image, so
+    /// methods built on top of other methods can evaluate them at the exact positions they look
+    /// up. Methods which only compute whole images fail with [`Error::ContextNotSupported`].
+    ///
+    /// # Parameters
+    ///
+    /// * `ctx`: CPU context to perform computations in
+    /// * `fragments`: fragments to compute, located on the plane of the full image
+    /// * `dim`: dimensions of the full image
+    /// * `params`: parameters of the values to compute
+    /// * `values`: computed values, one for each fragment
+    fn compute_fragments(
+        &mut self,
+        _ctx: &mut CpuContext,
+        _fragments: &[Fragment],
+        _dim: ImageDim,
+        _params: Option<&dyn Any>,
+        _values: &mut [f32],
+    ) -> Result<()> {
+        Err(Error::ContextNotSupported)
+    }
+
+    /// Default parameters of this method, if it exposes them
+    fn default_params(&self) -> Option<Box<dyn MethodParams>> {
+        None
+    }
+
+    /// Describe the parameters of this method, if it exposes them
+    fn param_schema(&self) -> Option<Vec<ParamInfo>> {
+        self.default_params().map(|params| params.schema())
+    }
+
+    /// Describe this method, if it exposes its metadata
+    fn info(&self) -> Option<MethodInfo> {
+        None
+    }
 }
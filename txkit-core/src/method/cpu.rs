@@ -1,7 +1,7 @@
 //! CPU Procedural texturing method types
 
 use crate::context::CpuContext;
-use crate::image::Image;
+use crate::image::{Fragment, Image, ImageDim, IntoElementType};
 use crate::Result;
 
 /// Represents a CPU procedural texturing method
@@ -22,4 +22,29 @@ pub trait CpuMethod {
         tgt: &mut Image,
         params: &Self::Params,
     ) -> Result<()>;
+
+    /// Compute the values of this method at arbitrary fragments
+    ///
+    /// See [`crate::method::Method::compute_fragments`].
+    fn compute_fragments_cpu(
+        &mut self,
+        ctx: &mut CpuContext,
+        fragments: &[Fragment],
+        dim: ImageDim,
+        params: &Self::Params,
+        values: &mut [f32],
+    ) -> Result<()>;
+
+    /// Whether the values computed by this method are integers, which can be written to `Int32`
+    /// images
+    fn integer_values(&self) -> bool {
+        false
+    }
+}
+
+/// Check if an iter function computes integer values, see [`IntoElementType::INTEGER`]
+pub fn iter_values_are_integers<P, T: IntoElementType>(
+    _iter: impl Fn(Fragment, ImageDim, &P) -> T,
+) -> bool {
+    T::INTEGER
 }
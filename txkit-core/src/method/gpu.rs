@@ -1,7 +1,7 @@
 //! GPU Procedural texturing method types
 
 use crate::context::GpuContext;
-use crate::image::gpu::GpuImageData;
+use crate::image::{gpu::GpuImageData, ImageRegion};
 use crate::Result;
 
 /// Represents a GPU procedural texturing method
@@ -15,11 +15,13 @@ pub trait GpuMethod {
     ///
     /// * `ctx`: GPU context to perform computations in
     /// * `tgt`: frame to fill with computation results
+    /// * `region`: region of the full image covered by the frame
     /// * `params`: parameters of the frame to compute
     fn compute_gpu(
         &mut self,
         ctx: &mut GpuContext,
         tgt: &mut GpuImageData,
+        region: &ImageRegion,
         params: &Self::Params,
     ) -> Result<()>;
 }
@@ -0,0 +1,20 @@
+//! Description of texturing methods
+
+use crate::image::ImageDataType;
+
+/// Description of a procedural texturing method
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MethodInfo {
+    /// Short description of the method
+    pub description: &'static str,
+    /// Whether the method can compute images in CPU contexts
+    pub cpu: bool,
+    /// Whether the method can compute images in GPU contexts
+    pub gpu: bool,
+    /// Element types of the images the method can compute
+    pub element_types: Vec<ImageDataType>,
+    /// Maximum number of dimensions of the images the method can compute, from 1 to 3
+    pub max_dimensions: usize,
+    /// Name of the parameter type of the method
+    pub params_type: &'static str,
+}
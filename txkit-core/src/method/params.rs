@@ -0,0 +1,266 @@
+//! Type-erased method parameters
+
+use std::any::Any;
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+use crate::{Error, Result};
+
+/// Value of a single method parameter, before conversion to the type of its field
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParamValue {
+    /// Integer value
+    Int(i64),
+    /// Floating-point value
+    Float(f64),
+    /// Vector value, with one component per element
+    Vector(Vec<f64>),
+}
+
+impl FromStr for ParamValue {
+    type Err = std::num::ParseFloatError;
+
+    /// Parse a parameter value, either a number or comma-separated vector components
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        if s.contains(',') {
+            return s
+                .split(',')
+                .map(|c| c.trim().parse())
+                .collect::<std::result::Result<_, _>>()
+                .map(Self::Vector);
+        }
+
+        let s = s.trim();
+        match s.parse() {
+            Ok(i) => Ok(Self::Int(i)),
+            Err(_) => s.parse().map(Self::Float),
+        }
+    }
+}
+
+/// Format a floating-point parameter value, using single precision if it is lossless
+///
+/// Parameter fields are single precision, so this avoids printing their widening error.
+fn fmt_float(f: &mut std::fmt::Formatter<'_>, x: f64) -> std::fmt::Result {
+    if f64::from(x as f32) == x {
+        write!(f, "{:?}", x as f32)
+    } else {
+        write!(f, "{:?}", x)
+    }
+}
+
+impl std::fmt::Display for ParamValue {
+    /// Format a parameter value, in the syntax accepted by its `FromStr` implementation
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Int(i) => write!(f, "{}", i),
+            Self::Float(x) => fmt_float(f, *x),
+            Self::Vector(v) => {
+                for (i, c) in v.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+
+                    fmt_float(f, *c)?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Type of a method parameter
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParamType {
+    /// Unsigned 32-bit integer
+    UInt,
+    /// Signed 32-bit integer
+    Int,
+    /// 32-bit floating-point number
+    Float,
+    /// Vector of 2 floating-point numbers
+    Vector2,
+    /// Vector of 3 floating-point numbers
+    Vector3,
+    /// Vector of 4 floating-point numbers
+    Vector4,
+}
+
+impl ParamType {
+    /// Number of components of values of this type
+    pub fn components(&self) -> usize {
+        match self {
+            Self::UInt | Self::Int | Self::Float => 1,
+            Self::Vector2 => 2,
+            Self::Vector3 => 3,
+            Self::Vector4 => 4,
+        }
+    }
+
+    /// Description of the values of this type, for error messages
+    pub fn expected(&self) -> &'static str {
+        match self {
+            Self::UInt => "a non-negative integer",
+            Self::Int => "an integer",
+            Self::Float => "a number",
+            Self::Vector2 => "a vector of 2 numbers",
+            Self::Vector3 => "a vector of 3 numbers",
+            Self::Vector4 => "a vector of 4 numbers",
+        }
+    }
+}
+
+/// Description of a method parameter
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParamInfo {
+    /// Name of the parameter field
+    pub name: &'static str,
+    /// Type of the parameter
+    pub param_type: ParamType,
+    /// Value of the parameter in the default parameters of the method
+    pub default: ParamValue,
+    /// Documentation of the parameter field
+    pub doc: &'static str,
+}
+
+/// Type of a parameter field that can be set from a [`ParamValue`]
+pub trait ParamField: Sized {
+    /// Type of the parameter in schemas
+    const TYPE: ParamType;
+
+    /// Convert `value` to this type, if it has the right type and range
+    fn from_param_value(value: &ParamValue) -> Option<Self>;
+
+    /// Convert this field to a parameter value
+    fn to_param_value(&self) -> ParamValue;
+}
+
+impl ParamField for u32 {
+    const TYPE: ParamType = ParamType::UInt;
+
+    fn from_param_value(value: &ParamValue) -> Option<Self> {
+        match value {
+            ParamValue::Int(i) => Self::try_from(*i).ok(),
+            _ => None,
+        }
+    }
+
+    fn to_param_value(&self) -> ParamValue {
+        ParamValue::Int(i64::from(*self))
+    }
+}
+
+impl ParamField for i32 {
+    const TYPE: ParamType = ParamType::Int;
+
+    fn from_param_value(value: &ParamValue) -> Option<Self> {
+        match value {
+            ParamValue::Int(i) => Self::try_from(*i).ok(),
+            _ => None,
+        }
+    }
+
+    fn to_param_value(&self) -> ParamValue {
+        ParamValue::Int(i64::from(*self))
+    }
+}
+
+impl ParamField for f32 {
+    const TYPE: ParamType = ParamType::Float;
+
+    fn from_param_value(value: &ParamValue) -> Option<Self> {
+        match value {
+            ParamValue::Int(i) => Some(*i as f32),
+            ParamValue::Float(f) => Some(*f as f32),
+            _ => None,
+        }
+    }
+
+    fn to_param_value(&self) -> ParamValue {
+        ParamValue::Float(f64::from(*self))
+    }
+}
+
+macro_rules! impl_param_field_vector {
+    ($t:ident, $n:literal, $($c:ident),*) => {
+        impl ParamField for cgmath::$t<f32> {
+            const TYPE: ParamType = ParamType::$t;
+
+            fn from_param_value(value: &ParamValue) -> Option<Self> {
+                match value {
+                    ParamValue::Vector(v) if v.len() == $n => {
+                        let mut c = v.iter().map(|c| *c as f32);
+                        Some(Self { $($c: c.next()?),* })
+                    }
+                    _ => None,
+                }
+            }
+
+            fn to_param_value(&self) -> ParamValue {
+                ParamValue::Vector(vec![$(f64::from(self.$c)),*])
+            }
+        }
+    };
+}
+
+impl_param_field_vector!(Vector2, 2, x, y);
+impl_param_field_vector!(Vector3, 3, x, y, z);
+impl_param_field_vector!(Vector4, 4, x, y, z, w);
+
+/// Type-erased parameters of a procedural texturing method
+///
+/// This is implemented by the `ParamsFor` derive, so combinators (such as fractal sums) can
+/// adjust the parameters of methods without knowing their concrete type.
+pub trait MethodParams: Any {
+    /// Get these parameters as a value that can be passed to [`super::Method::compute`]
+    fn as_any(&self) -> &dyn Any;
+
+    /// Clone these parameters
+    fn clone_params(&self) -> Box<dyn MethodParams>;
+
+    /// Lattice scale, if these parameters have one
+    fn scale(&self) -> Option<f32> {
+        None
+    }
+
+    /// Set the lattice scale
+    ///
+    /// Returns `false` if these parameters have no lattice scale.
+    fn set_scale(&mut self, _scale: f32) -> bool {
+        false
+    }
+
+    /// Pseudo-random seed, if these parameters have one
+    fn global_seed(&self) -> Option<u32> {
+        None
+    }
+
+    /// Set the pseudo-random seed
+    ///
+    /// Returns `false` if these parameters have no pseudo-random seed.
+    fn set_global_seed(&mut self, _global_seed: u32) -> bool {
+        false
+    }
+
+    /// Describe the parameters that can be accessed by name
+    fn schema(&self) -> Vec<ParamInfo> {
+        Vec::new()
+    }
+
+    /// Get a parameter from its field name
+    fn get_param(&self, _name: &str) -> Option<ParamValue> {
+        None
+    }
+
+    /// Set a parameter from its field name
+    ///
+    /// # Parameters
+    ///
+    /// * `name`: name of the parameter field
+    /// * `value`: new value, which must be convertible to the type of the field
+    fn set_param(&mut self, name: &str, _value: &ParamValue) -> Result<()> {
+        Err(Error::UnknownParameter(name.to_owned()))
+    }
+}
@@ -0,0 +1,201 @@
+//! Loading of methods from shared libraries
+//!
+//! A plugin is a `cdylib` crate which declares a registration function with [`declare_plugin!`]:
+//!
+//! ```ignore
+//! fn register(registry: &mut txkit_core::method::MethodRegistry) {
+//!     registry.register("my_noise", Box::new(|| Box::new(MyNoise::new())));
+//! }
+//!
+//! txkit_core::declare_plugin!(register);
+//! ```
+//!
+//! Methods are shared as Rust trait objects, so plugins must be built with the same compiler, the
+//! same version and features of txkit-core, and the same versions of the dependencies whose types
+//! txkit-core exposes (ndarray, and tinygl for GPU builds) as the program loading them. These are
+//! checked before the registration function is called.
+
+use std::os::raw::c_char;
+
+use super::MethodRegistry;
+
+/// Version of the layout of [`PluginDeclaration`]
+pub const PLUGIN_ABI_VERSION: u32 = 1;
+
+/// Entry point of a plugin, exported as `TXKIT_PLUGIN_DECLARATION` by [`declare_plugin!`]
+#[repr(C)]
+pub struct PluginDeclaration {
+    /// version of the declaration layout, checked before any other field is read
+    pub abi_version: u32,
+    /// version and features of txkit-core, and versions of its shared dependencies, the plugin was
+    /// built with, NUL-terminated
+    pub core_build: *const c_char,
+    /// version of the compiler the plugin was built with, NUL-terminated
+    pub rustc_version: *const c_char,
+    /// register the methods of the plugin
+    pub register: fn(&mut MethodRegistry),
+}
+
+// The declaration only points to static strings
+unsafe impl Sync for PluginDeclaration {}
+
+#[doc(hidden)]
+pub const CORE_BUILD: &str = concat!(
+    env!("CARGO_PKG_VERSION"),
+    " (",
+    env!("TXKIT_CORE_BUILD"),
+    ")\0"
+);
+
+#[doc(hidden)]
+pub const RUSTC_VERSION: &str = concat!(env!("TXKIT_RUSTC_VERSION"), "\0");
+
+/// Export the plugin declaration of a shared library, given its registration function
+#[macro_export]
+macro_rules! declare_plugin {
+    ($register:path) => {
+        #[no_mangle]
+        pub static TXKIT_PLUGIN_DECLARATION: $crate::method::PluginDeclaration =
+            $crate::method::PluginDeclaration {
+                abi_version: $crate::method::PLUGIN_ABI_VERSION,
+                core_build: $crate::method::CORE_BUILD.as_ptr() as *const _,
+                rustc_version: $crate::method::RUSTC_VERSION.as_ptr() as *const _,
+                register: $register,
+            };
+    };
+}
+
+#[cfg(feature = "plugin")]
+mod loader {
+    use std::ffi::CStr;
+    use std::path::Path;
+
+    use super::*;
+    use crate::{Error, Result};
+
+    /// Check that a plugin declaration matches the current build
+    ///
+    /// # Safety
+    ///
+    /// If the ABI version matches, the strings of the declaration must be valid.
+    unsafe fn check_declaration(
+        declaration: &PluginDeclaration,
+    ) -> std::result::Result<(), String> {
+        if declaration.abi_version != PLUGIN_ABI_VERSION {
+            return Err(format!(
+                "plugin ABI version {}, expected {}",
+                declaration.abi_version, PLUGIN_ABI_VERSION
+            ));
+        }
+
+        let expected = |s: &'static str| s.trim_end_matches('\0');
+
+        let core_build = CStr::from_ptr(declaration.core_build).to_string_lossy();
+        if core_build != expected(CORE_BUILD) {
+            return Err(format!(
+                "plugin built with txkit-core {}, expected {}",
+                core_build,
+                expected(CORE_BUILD)
+            ));
+        }
+
+        let rustc_version = CStr::from_ptr(declaration.rustc_version).to_string_lossy();
+        if rustc_version != expected(RUSTC_VERSION) {
+            return Err(format!(
+                "plugin built with {}, expected {}",
+                rustc_version,
+                expected(RUSTC_VERSION)
+            ));
+        }
+
+        Ok(())
+    }
+
+    impl MethodRegistry {
+        /// Load the shared library at `path`, and register the methods of its plugin
+        ///
+        /// Methods registered by the plugin replace the existing methods with the same name. The
+        /// library is never unloaded, since methods and parameters built from it can outlive the
+        /// registry.
+        ///
+        /// # Safety
+        ///
+        /// Loading a library runs its initialization code, and the methods of the plugin are
+        /// trusted to be sound.
+        pub unsafe fn load_plugin(&mut self, path: impl AsRef<Path>) -> Result<()> {
+            let path = path.as_ref();
+            let load_failed = |e: libloading::Error| Error::PluginLoadFailed {
+                path: path.to_owned(),
+                reason: e.to_string(),
+            };
+
+            let library = libloading::Library::new(path).map_err(load_failed)?;
+            let declaration = *library
+                .get::<*const PluginDeclaration>(b"TXKIT_PLUGIN_DECLARATION\0")
+                .map_err(|_| Error::IncompatiblePlugin {
+                    path: path.to_owned(),
+                    reason: "no TXKIT_PLUGIN_DECLARATION, not declared with declare_plugin!"
+                        .to_owned(),
+                })?;
+
+            check_declaration(&*declaration).map_err(|reason| Error::IncompatiblePlugin {
+                path: path.to_owned(),
+                reason,
+            })?;
+
+            ((*declaration).register)(self);
+            std::mem::forget(library);
+
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn no_methods(_: &mut MethodRegistry) {}
+
+        #[test]
+        fn core_build_covers_dependencies() {
+            assert!(CORE_BUILD.contains("; ndarray "));
+            assert_eq!(CORE_BUILD.contains("tinygl "), cfg!(feature = "gpu-core"));
+        }
+
+        #[test]
+        fn check_declaration_versions() {
+            let mut declaration = PluginDeclaration {
+                abi_version: PLUGIN_ABI_VERSION,
+                core_build: CORE_BUILD.as_ptr() as *const _,
+                rustc_version: RUSTC_VERSION.as_ptr() as *const _,
+                register: no_methods,
+            };
+
+            unsafe {
+                assert_eq!(check_declaration(&declaration), Ok(()));
+
+                declaration.rustc_version = b"rustc 0.0.0\0".as_ptr() as *const _;
+                assert!(check_declaration(&declaration)
+                    .unwrap_err()
+                    .starts_with("plugin built with rustc 0.0.0"));
+
+                // Same txkit-core version and features, but another ndarray release
+                let other_ndarray = CORE_BUILD.replace("ndarray ", "ndarray 0.0.0+");
+                declaration.core_build = other_ndarray.as_ptr() as *const _;
+                assert!(check_declaration(&declaration)
+                    .unwrap_err()
+                    .starts_with("plugin built with txkit-core"));
+
+                declaration.core_build = b"0.0.0 ()\0".as_ptr() as *const _;
+                assert!(check_declaration(&declaration)
+                    .unwrap_err()
+                    .starts_with("plugin built with txkit-core 0.0.0"));
+
+                declaration.abi_version = PLUGIN_ABI_VERSION + 1;
+                assert!(check_declaration(&declaration)
+                    .unwrap_err()
+                    .starts_with("plugin ABI version"));
+            }
+        }
+    }
+}
@@ -2,7 +2,7 @@
 
 use std::collections::HashMap;
 
-use super::Method;
+use super::{Method, MethodInfo, ParamInfo};
 
 /// Type of a method constructor
 pub type MethodConstructor = Box<dyn Fn() -> Box<dyn Method>>;
@@ -26,4 +26,29 @@ impl MethodRegistry {
     pub fn build(&self, name: &str) -> Option<Box<dyn Method>> {
         self.method_constructors.get(name).and_then(|v| Some(v()))
     }
+
+    /// Names of the registered methods, in alphabetical order
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<_> = self
+            .method_constructors
+            .keys()
+            .map(String::as_str)
+            .collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// Describe the method named `name`
+    ///
+    /// Returns `None` if the method is not registered, or if it does not expose its metadata.
+    pub fn info(&self, name: &str) -> Option<MethodInfo> {
+        self.build(name).and_then(|method| method.info())
+    }
+
+    /// Describe the parameters of the method named `name`
+    ///
+    /// Returns `None` if the method is not registered, or if it does not expose its parameters.
+    pub fn param_schema(&self, name: &str) -> Option<Vec<ParamInfo>> {
+        self.build(name).and_then(|method| method.param_schema())
+    }
 }
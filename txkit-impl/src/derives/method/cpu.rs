@@ -25,12 +25,14 @@ impl CpuDirectiveMethodKind {
 pub struct CpuDirectiveMethod {
     pub kind: CpuDirectiveMethodKind,
     pub params_struct_name: String,
+    pub init: Option<String>,
 }
 
 impl CpuDirectiveMethod {
     pub fn parse_from(list: &syn::MetaList) -> Result<Self> {
         let mut kind = None;
         let mut params = None;
+        let mut init = None;
 
         for item in &list.nested {
             match item {
@@ -46,6 +48,13 @@ impl CpuDirectiveMethod {
                 })) if path.get_ident().map(|id| *id == "params").unwrap_or(false) => {
                     params = Some(s.value().to_string());
                 }
+                syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                    path,
+                    lit: syn::Lit::Str(s),
+                    ..
+                })) if path.get_ident().map(|id| *id == "init").unwrap_or(false) => {
+                    init = Some(s.value().to_string());
+                }
                 _ => {}
             }
         }
@@ -55,6 +64,7 @@ impl CpuDirectiveMethod {
                 .ok_or_else(|| anyhow!("missing `iter = \"...\"` in cpu directive method"))?,
             params_struct_name: params
                 .ok_or_else(|| anyhow!("missing `params = \"...\"` in cpu directive method"))?,
+            init,
         })
     }
 }
@@ -95,7 +105,7 @@ pub fn process_txkit_cpu_directive(
     input: &DeriveInput,
     list: &syn::MetaList,
 ) -> Result<(TokenStream, CpuDirective)> {
-    use quote::quote;
+    use quote::{format_ident, quote};
 
     let cpu_directive = CpuDirective::parse_from(list)?;
 
@@ -108,6 +118,23 @@ pub fn process_txkit_cpu_directive(
         let CpuDirectiveMethodKind::Iter { path } = &method.kind;
         let path: syn::Path = syn::parse_str(path)?;
 
+        // If an init function is specified, its result replaces the params given to the iter
+        // function. This is needed when the params are not Sync. The init function also gets the
+        // method, to keep state across frames.
+        let init = if let Some(init) = &method.init {
+            let init: syn::Path = syn::parse_str(init)?;
+            quote! { let params = &#init(self, dim, params)?; }
+        } else {
+            quote! {}
+        };
+
+        // One branch for each element type the target can be mapped as
+        let element_types = ["u8", "f32", "u16", "f16", "i32"];
+        let accessors = element_types
+            .iter()
+            .map(|t| format_ident!("as_{}_nd_array_mut", t));
+        let conversions = element_types.iter().map(|t| format_ident!("into_{}", t));
+
         generated.push(quote! {
             #[cfg(feature = "cpu")]
             impl ::txkit_core::method::CpuMethod for #struct_name {
@@ -119,31 +146,74 @@ pub fn process_txkit_cpu_directive(
                     tgt: &mut ::txkit_core::image::Image,
                     params: &Self::Params,
                 ) -> ::txkit_core::Result<()> {
-                    use ::txkit_core::image::IntoElementType;
+                    use ::txkit_core::image::{ImageDataType, IntoElementType};
                     use ::ndarray::par_azip;
 
-                    let dim = tgt.dim();
+                    if tgt.element_type() == ImageDataType::Int32 && !self.integer_values() {
+                        return Err(::txkit_core::Error::FormatNotSupported);
+                    }
+
+                    // Iter functions compute fragments on the plane of the full image that the
+                    // target is a region of
+                    let region = tgt.region();
+                    let dim = region.full_dim(tgt.dim().channels);
+                    #init
                     let mut data_mut = tgt.data_mut()?;
 
-                    if let Some(data) = data_mut.as_u8_nd_array_mut() {
-                        ctx.thread_pool.install(|| {
-                            par_azip!((index idx, o in data) {
-                                *o = #path(idx, dim, params).into_u8();
+                    #(
+                        if let Some(data) = data_mut.#accessors() {
+                            ctx.thread_pool.install(|| {
+                                // Like shaders, iter functions only compute the first channels
+                                par_azip!((index idx, o in data) {
+                                    *o = if idx.3 < ::txkit_core::method::OUTPUT_CHANNELS {
+                                        #path(region.fragment(idx), dim, params).#conversions()
+                                    } else {
+                                        Default::default()
+                                    };
+                                });
                             });
-                        });
 
-                        Ok(())
-                    } else if let Some(data) = data_mut.as_f32_nd_array_mut() {
-                        ctx.thread_pool.install(|| {
-                            par_azip!((index idx, o in data) {
-                                *o = #path(idx, dim, params).into_f32();
-                            });
-                        });
+                            return Ok(());
+                        }
+                    )*
+
+                    Err(::txkit_core::Error::FormatNotSupported)
+                }
 
-                        Ok(())
-                    } else {
-                        Err(::txkit_core::Error::FormatNotSupported)
+                fn compute_fragments_cpu(
+                    &mut self,
+                    ctx: &mut ::txkit_core::context::CpuContext,
+                    fragments: &[::txkit_core::image::Fragment],
+                    dim: ::txkit_core::image::ImageDim,
+                    params: &Self::Params,
+                    values: &mut [f32],
+                ) -> ::txkit_core::Result<()> {
+                    use ::txkit_core::image::IntoElementType;
+                    use ::ndarray::{ArrayView1, ArrayViewMut1, Zip};
+
+                    if fragments.len() != values.len() {
+                        return Err(::txkit_core::Error::ImageDimensionMismatch);
                     }
+
+                    #init
+
+                    ctx.thread_pool.install(|| {
+                        Zip::from(ArrayViewMut1::from(values))
+                            .and(ArrayView1::from(fragments))
+                            .par_for_each(|o, frag| {
+                                *o = if frag.channel < ::txkit_core::method::OUTPUT_CHANNELS {
+                                    #path(*frag, dim, params).into_f32()
+                                } else {
+                                    0.
+                                };
+                            });
+                    });
+
+                    Ok(())
+                }
+
+                fn integer_values(&self) -> bool {
+                    ::txkit_core::method::iter_values_are_integers(#path)
                 }
             }
         });
@@ -339,20 +339,26 @@ pub fn process_txkit_gpu_directive(
                     &mut self,
                     ctx: &mut ::txkit_core::context::GpuContext,
                     tgt: &mut ::txkit_core::image::gpu::GpuImageData,
+                    region: &::txkit_core::image::ImageRegion,
                     params: &Self::Params,
                 ) -> ::txkit_core::Result<()> {
                     use ::tinygl::wrappers::ProgramCommonExt;
                     use ::txkit_core::{image::{ImageDataBase, ImageDimGpuExt}, method::GpuMethodParams};
 
                     let dim = tgt.dim().into_cgmath();
+                    let resolution = region.full_dim(1).into_cgmath();
+                    let offset = ::cgmath::vec3(region.x, region.y, region.z);
                     ctx.render_to_framebuffer(tgt, |gl, layer| {
                         unsafe {
                             self.#program_field_name.use_program(gl);
                         }
 
                         // Common parameters
-                        self.#program_field_name.set_i_resolution(gl, dim);
+                        self.#program_field_name.set_i_resolution(gl, resolution);
                         self.#program_field_name.set_i_layer(gl, layer);
+                        self.#program_field_name.set_i_offset(gl, offset);
+                        self.#program_field_name.set_i_scale(gl, region.scale);
+                        self.#program_field_name.set_i_size(gl, dim);
 
                         // Method parameters
                         params.apply(gl, &self.#program_field_name);
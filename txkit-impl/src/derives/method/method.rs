@@ -3,18 +3,71 @@ use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
 use syn::DeriveInput;
 
+#[derive(Debug, Default)]
+pub struct MethodDirective {
+    /// Function checking the params before they are used by either backend
+    pub check: Option<String>,
+    /// Function checking that the target can hold the values computed with the params
+    pub check_target: Option<String>,
+    /// Number of image dimensions the method varies along, if it is less than 3
+    pub max_dimensions: Option<usize>,
+}
+
+impl MethodDirective {
+    pub fn parse_from(list: &syn::MetaList) -> Result<Self> {
+        let mut result = Self::default();
+
+        for item in &list.nested {
+            match item {
+                syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                    path,
+                    lit: syn::Lit::Str(s),
+                    ..
+                })) if path.get_ident().map(|id| *id == "check").unwrap_or(false) => {
+                    result.check = Some(s.value());
+                }
+                syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                    path,
+                    lit: syn::Lit::Str(s),
+                    ..
+                })) if path
+                    .get_ident()
+                    .map(|id| *id == "check_target")
+                    .unwrap_or(false) =>
+                {
+                    result.check_target = Some(s.value());
+                }
+                syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                    path,
+                    lit: syn::Lit::Int(i),
+                    ..
+                })) if path
+                    .get_ident()
+                    .map(|id| *id == "max_dimensions")
+                    .unwrap_or(false) =>
+                {
+                    result.max_dimensions = Some(i.base10_parse()?);
+                }
+                _ => {
+                    return Err(anyhow!(
+                        "unexpected {:?} in top-level method txkit directive",
+                        item
+                    ));
+                }
+            }
+        }
+
+        Ok(result)
+    }
+}
+
 pub fn process_txkit_method_directive(
     input: &DeriveInput,
     list: &syn::MetaList,
     gpu_directives: &[super::gpu::GpuDirective],
     cpu_directives: &[super::cpu::CpuDirective],
 ) -> Result<TokenStream> {
-    // Ensure the list is formatted correctly
-    if list.nested.len() != 0 {
-        return Err(anyhow!(
-            "unexpected tokens in top-level method txkit directive"
-        ));
-    }
+    let method_directive = MethodDirective::parse_from(list)?;
 
     let struct_name = &input.ident;
 
@@ -45,21 +98,30 @@ pub fn process_txkit_method_directive(
 
         quote! {
             #[cfg(feature = "gpu")]
-            Context::Gpu(gpu_context) => tgt
-                .as_gpu_image_mut()
-                .ok_or_else(|| Error::FormatNotSupported)
-                .and_then(|tgt| {
-                    use ::txkit_core::method::GpuMethod;
-
-                    // Initialize GPU if needed
-                    if let None = self.gpu {
-                        self.gpu = Some(#gpu_s_name::new(gpu_context)?);
-                    }
-
-                    // Compute result using initialized GPU resources
-                    let gpu = self.gpu.as_mut().unwrap();
-                    gpu.compute_gpu(gpu_context, tgt, params)
-                }),
+            Context::Gpu(gpu_context) => {
+                // Shaders compute normalized values, see the Int32 check of CPU methods
+                if tgt.element_type().is_integer() {
+                    return Err(Error::FormatNotSupported);
+                }
+
+                let region = tgt.region();
+
+                tgt
+                    .as_gpu_image_mut()
+                    .ok_or_else(|| Error::FormatNotSupported)
+                    .and_then(|tgt| {
+                        use ::txkit_core::method::GpuMethod;
+
+                        // Initialize GPU if needed
+                        if let None = self.gpu {
+                            self.gpu = Some(#gpu_s_name::new(gpu_context)?);
+                        }
+
+                        // Compute result using initialized GPU resources
+                        let gpu = self.gpu.as_mut().unwrap();
+                        gpu.compute_gpu(gpu_context, tgt, &region, params)
+                    })
+            },
             #[cfg(not(feature = "gpu"))]
             Context::Gpu(_) => Err(Error::ContextNotSupported),
         }
@@ -85,6 +147,46 @@ pub fn process_txkit_method_directive(
         }
     };
 
+    // Methods which render the same slice on every layer declare it
+    let max_dimensions = method_directive.max_dimensions.unwrap_or(3);
+
+    // The check function rejects params that the backends would handle differently
+    let check = if let Some(check) = &method_directive.check {
+        let check: syn::Path = syn::parse_str(check)?;
+        quote! { #check(params)?; }
+    } else {
+        quote! {}
+    };
+
+    let check_target = if let Some(check_target) = &method_directive.check_target {
+        let check_target: syn::Path = syn::parse_str(check_target)?;
+        quote! { #check_target(tgt, params)?; }
+    } else {
+        quote! {}
+    };
+
+    let cpu_fragments_code = if cpu_struct_name.is_some() {
+        quote! {
+            #[cfg(feature = "cpu")]
+            fn compute_fragments(
+                &mut self,
+                ctx: &mut ::txkit_core::context::CpuContext,
+                fragments: &[::txkit_core::image::Fragment],
+                dim: ::txkit_core::image::ImageDim,
+                params: Option<&dyn std::any::Any>,
+                values: &mut [f32],
+            ) -> ::txkit_core::Result<()> {
+                use ::txkit_core::method::CpuMethod;
+                let mut default_params: Option<<Self as CpuMethod>::Params> = None;
+                let params = ::txkit_core::method::downcast_params(params, &mut default_params)?;
+                #check
+                self.compute_fragments_cpu(ctx, fragments, dim, params, values)
+            }
+        }
+    } else {
+        quote! {}
+    };
+
     let params_type: syn::Type = syn::parse_str(
         &gpu_directives
             .iter()
@@ -100,6 +202,37 @@ pub fn process_txkit_method_directive(
             })?,
     )?;
 
+    // The description is the first paragraph of the doc comment of the method
+    let description = crate::util::doc_lines(&input.attrs)
+        .into_iter()
+        .take_while(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let supports_cpu = if cpu_struct_name.is_some() {
+        quote! { cfg!(feature = "cpu") }
+    } else {
+        quote! { false }
+    };
+
+    // Only CPU methods computing integer values can render to Int32 targets
+    let integer_values = if cpu_struct_name.is_some() {
+        quote! {
+            #[cfg(feature = "cpu")]
+            let integer_values = ::txkit_core::method::CpuMethod::integer_values(self);
+            #[cfg(not(feature = "cpu"))]
+            let integer_values = false;
+        }
+    } else {
+        quote! { let integer_values = false; }
+    };
+
+    let supports_gpu = if gpu_struct_name.is_some() {
+        quote! { cfg!(feature = "gpu") }
+    } else {
+        quote! { false }
+    };
+
     // Generate the impl
     Ok(TokenStream::from(quote! {
         impl ::txkit_core::method::Method for #struct_name {
@@ -112,12 +245,38 @@ pub fn process_txkit_method_directive(
                 use ::txkit_core::{context::Context, Error};
                 let mut default_params: Option<#params_type> = None;
                 let params = ::txkit_core::method::downcast_params(params, &mut default_params)?;
+                #check
+                #check_target
 
                 match ctx {
                     #gpu_code
                     #cpu_code
                 }
             }
+
+            #cpu_fragments_code
+
+            fn default_params(&self) -> Option<Box<dyn ::txkit_core::method::MethodParams>> {
+                Some(Box::new(<#params_type as Default>::default()))
+            }
+
+            fn info(&self) -> Option<::txkit_core::method::MethodInfo> {
+                use ::txkit_core::image::ImageDataType;
+                #integer_values
+
+                Some(::txkit_core::method::MethodInfo {
+                    description: #description,
+                    cpu: #supports_cpu,
+                    gpu: #supports_gpu,
+                    element_types: ImageDataType::ALL
+                        .iter()
+                        .copied()
+                        .filter(|t| integer_values || !t.is_integer())
+                        .collect(),
+                    max_dimensions: #max_dimensions,
+                    params_type: stringify!(#params_type),
+                })
+            }
         }
     }))
 }
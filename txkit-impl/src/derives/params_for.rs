@@ -156,9 +156,150 @@ fn process_txkit_directive(input: &DeriveInput, list: &syn::MetaList) -> Result<
     Ok(TokenStream::from(quote! { #(#generated)* }))
 }
 
+fn process_method_params(input: &DeriveInput) -> Result<TokenStream> {
+    let struct_name = &input.ident;
+
+    let fields: Vec<_> = match &input.data {
+        syn::Data::Struct(ds) => ds
+            .fields
+            .iter()
+            .filter_map(|field| field.ident.as_ref().map(|id| (id, field)))
+            .collect(),
+        _ => {
+            return Err(anyhow!("unnamed structs are not supported by txkit"));
+        }
+    };
+
+    let field_names: Vec<_> = fields.iter().map(|(id, _)| id.to_string()).collect();
+
+    // Parameters settable by name, i.e. all fields except image bindings
+    let value_fields: Vec<_> = fields
+        .iter()
+        .filter(|(_, field)| {
+            !field.attrs.iter().any(|attr| {
+                attr.path
+                    .get_ident()
+                    .map(|id| id == "image_io" || id == "texture_io")
+                    .unwrap_or(false)
+            })
+        })
+        .collect();
+
+    let value_field_infos = value_fields.iter().map(|(id, field)| {
+        let name = id.to_string();
+        let ty = &field.ty;
+
+        let doc = crate::util::doc_lines(&field.attrs).join(" ");
+
+        quote! {
+            ::txkit_core::method::ParamInfo {
+                name: #name,
+                param_type: <#ty as ::txkit_core::method::ParamField>::TYPE,
+                default: ::txkit_core::method::ParamField::to_param_value(&defaults.#id),
+                doc: #doc,
+            }
+        }
+    });
+
+    let value_field_getters = value_fields.iter().map(|(id, _)| {
+        let name = id.to_string();
+
+        quote! {
+            #name => Some(::txkit_core::method::ParamField::to_param_value(&self.#id)),
+        }
+    });
+    let value_field_setters = value_fields.iter().map(|(id, field)| {
+        let name = id.to_string();
+        let ty = &field.ty;
+
+        quote! {
+            #name => {
+                self.#id = <#ty as ::txkit_core::method::ParamField>::from_param_value(value)
+                    .ok_or_else(|| ::txkit_core::Error::InvalidParameterValue {
+                        name: name.to_owned(),
+                        expected: <#ty as ::txkit_core::method::ParamField>::TYPE.expected(),
+                    })?;
+            }
+        }
+    });
+
+    // Accessors for the well-known lattice parameters
+    let scale_accessors = if field_names.iter().any(|n| n == "scale") {
+        quote! {
+            fn scale(&self) -> Option<f32> {
+                Some(self.scale)
+            }
+
+            fn set_scale(&mut self, scale: f32) -> bool {
+                self.scale = scale;
+                true
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let global_seed_accessors = if field_names.iter().any(|n| n == "global_seed") {
+        quote! {
+            fn global_seed(&self) -> Option<u32> {
+                Some(self.global_seed)
+            }
+
+            fn set_global_seed(&mut self, global_seed: u32) -> bool {
+                self.global_seed = global_seed;
+                true
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    Ok(quote! {
+        impl ::txkit_core::method::MethodParams for #struct_name {
+            fn as_any(&self) -> &dyn ::std::any::Any {
+                self
+            }
+
+            fn clone_params(&self) -> Box<dyn ::txkit_core::method::MethodParams> {
+                Box::new(self.clone())
+            }
+
+            #scale_accessors
+            #global_seed_accessors
+
+            fn schema(&self) -> Vec<::txkit_core::method::ParamInfo> {
+                let defaults = <Self as Default>::default();
+                vec![#(#value_field_infos),*]
+            }
+
+            fn get_param(&self, name: &str) -> Option<::txkit_core::method::ParamValue> {
+                match name {
+                    #(#value_field_getters)*
+                    _ => None,
+                }
+            }
+
+            fn set_param(
+                &mut self,
+                name: &str,
+                value: &::txkit_core::method::ParamValue,
+            ) -> ::txkit_core::Result<()> {
+                match name {
+                    #(#value_field_setters)*
+                    _ => return Err(::txkit_core::Error::UnknownParameter(name.to_owned())),
+                }
+
+                Ok(())
+            }
+        }
+    })
+}
+
 pub fn process_params_for(input: DeriveInput) -> Result<TokenStream> {
     let mut generated: Vec<TokenStream> = Vec::new();
 
+    generated.push(process_method_params(&input)?);
+
     crate::util::process_directive(
         &input.attrs,
         |list| {
@@ -35,3 +35,20 @@ pub fn process_directive<T>(
 
     Ok(())
 }
+
+/// Lines of the doc comments in `attrs`, trimmed
+///
+/// Doc comments are turned into `#[doc = "..."]` attributes, one per line.
+pub fn doc_lines(attrs: &[Attribute]) -> Vec<String> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("doc"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(syn::Meta::NameValue(syn::MetaNameValue {
+                lit: syn::Lit::Str(s),
+                ..
+            })) => Some(s.value().trim().to_owned()),
+            _ => None,
+        })
+        .collect()
+}
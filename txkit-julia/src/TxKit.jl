@@ -23,6 +23,9 @@ import ..libctxkit
 const ImageDataType = UInt32
 const ImageDataType_UInt8 = ImageDataType(0)
 const ImageDataType_Float32 = ImageDataType(1)
+const ImageDataType_UInt16 = ImageDataType(2)
+const ImageDataType_Float16 = ImageDataType(3)
+const ImageDataType_Int32 = ImageDataType(4)
 
 const Context = Ptr{Cvoid}
 
@@ -37,6 +40,32 @@ const Registry = Ptr{Cvoid}
 
 const ImageIo = Ptr{Cvoid}
 
+const Params = Ptr{Cvoid}
+
+const ParamType = UInt32
+const ParamType_UInt = ParamType(0)
+const ParamType_Int = ParamType(1)
+const ParamType_Float = ParamType(2)
+const ParamType_Vector2 = ParamType(3)
+const ParamType_Vector3 = ParamType(4)
+const ParamType_Vector4 = ParamType(5)
+
+struct MethodDesc
+    name::Ptr{Cchar}
+    description::Ptr{Cchar}
+    params_type::Ptr{Cchar}
+    cpu::Bool
+    gpu::Bool
+    element_types::UInt32
+    max_dimensions::UInt
+end
+
+struct ParamDesc
+    name::Ptr{Cchar}
+    doc::Ptr{Cchar}
+    param_type::ParamType
+end
+
 struct ImageDim
     width::UInt
     height::UInt
@@ -44,6 +73,16 @@ struct ImageDim
     channels::UInt
 end
 
+struct ImageRegion
+    x::Float32
+    y::Float32
+    z::Float32
+    scale::Float32
+    width::UInt
+    height::UInt
+    depth::UInt
+end
+
 txkit_context_destroy(ctx::Context) = ccall((:txkit_context_destroy, libctxkit), Cvoid, (Context,), ctx)
 txkit_context_new_cpu() = ccall((:txkit_context_new_cpu, libctxkit), Context, ())
 txkit_context_new_gpu() = ccall((:txkit_context_new_gpu, libctxkit), Context, ())
@@ -54,25 +93,45 @@ txkit_image_destroy(image::Image) = ccall((:txkit_image_destroy, libctxkit), Cvo
 txkit_image_dim(image::Image) = ccall((:txkit_image_dim, libctxkit), ImageDim, (Image,), image)
 txkit_image_element_type(image::Image) = ccall((:txkit_image_element_type, libctxkit), ImageDataType, (Image,), image)
 txkit_image_map_read(image::Image) = ccall((:txkit_image_map_read, libctxkit), MappedImageDataRead, (Image,), image)
+txkit_image_map_read_data_f16(read_map::MappedImageDataRead) = ccall((:txkit_image_map_read_data_f16, libctxkit), Ptr{Float16}, (MappedImageDataRead,), read_map)
 txkit_image_map_read_data_f32(read_map::MappedImageDataRead) = ccall((:txkit_image_map_read_data_f32, libctxkit), Ptr{Cfloat}, (MappedImageDataRead,), read_map)
+txkit_image_map_read_data_i32(read_map::MappedImageDataRead) = ccall((:txkit_image_map_read_data_i32, libctxkit), Ptr{Int32}, (MappedImageDataRead,), read_map)
+txkit_image_map_read_data_u16(read_map::MappedImageDataRead) = ccall((:txkit_image_map_read_data_u16, libctxkit), Ptr{UInt16}, (MappedImageDataRead,), read_map)
 txkit_image_map_read_data_u8(read_map::MappedImageDataRead) = ccall((:txkit_image_map_read_data_u8, libctxkit), Ptr{UInt8}, (MappedImageDataRead,), read_map)
 txkit_image_map_write(image::Image) = ccall((:txkit_image_map_write, libctxkit), MappedImageDataWrite, (Image,), image)
+txkit_image_map_write_data_f16(write_map::MappedImageDataWrite) = ccall((:txkit_image_map_write_data_f16, libctxkit), Ptr{Float16}, (MappedImageDataWrite,), write_map)
 txkit_image_map_write_data_f32(write_map::MappedImageDataWrite) = ccall((:txkit_image_map_write_data_f32, libctxkit), Ptr{Cfloat}, (MappedImageDataWrite,), write_map)
+txkit_image_map_write_data_i32(write_map::MappedImageDataWrite) = ccall((:txkit_image_map_write_data_i32, libctxkit), Ptr{Int32}, (MappedImageDataWrite,), write_map)
+txkit_image_map_write_data_u16(write_map::MappedImageDataWrite) = ccall((:txkit_image_map_write_data_u16, libctxkit), Ptr{UInt16}, (MappedImageDataWrite,), write_map)
 txkit_image_map_write_data_u8(write_map::MappedImageDataWrite) = ccall((:txkit_image_map_write_data_u8, libctxkit), Ptr{UInt8}, (MappedImageDataWrite,), write_map)
 txkit_image_new_cpu(dim::ImageDim, element_type::ImageDataType) = ccall((:txkit_image_new_cpu, libctxkit), Image, (ImageDim, ImageDataType), dim, element_type)
+txkit_image_new_cpu_from_buffer(dim::ImageDim, element_type::ImageDataType, data::Ptr{Cvoid}, strides::Ptr{UInt}, release::Ptr{Cvoid}, user_data::Ptr{Cvoid}) = ccall((:txkit_image_new_cpu_from_buffer, libctxkit), Image, (ImageDim, ImageDataType, Ptr{Cvoid}, Ptr{UInt}, Ptr{Cvoid}, Ptr{Cvoid}), dim, element_type, data, strides, release, user_data)
 txkit_image_new_gpu_1d(dim::ImageDim, element_type::ImageDataType, context::Context) = ccall((:txkit_image_new_gpu_1d, libctxkit), Image, (ImageDim, ImageDataType, Context), dim, element_type, context)
 txkit_image_new_gpu_2d(dim::ImageDim, element_type::ImageDataType, context::Context) = ccall((:txkit_image_new_gpu_2d, libctxkit), Image, (ImageDim, ImageDataType, Context), dim, element_type, context)
 txkit_image_new_gpu_3d(dim::ImageDim, element_type::ImageDataType, context::Context) = ccall((:txkit_image_new_gpu_3d, libctxkit), Image, (ImageDim, ImageDataType, Context), dim, element_type, context)
 txkit_image_download(image::Image) = ccall((:txkit_image_download, libctxkit), Int32, (Image,), image)
 txkit_image_upload(image::Image) = ccall((:txkit_image_upload, libctxkit), Int32, (Image,), image)
+txkit_image_region(image::Image) = ccall((:txkit_image_region, libctxkit), ImageRegion, (Image,), image)
+txkit_image_set_region(image::Image, region::ImageRegion) = ccall((:txkit_image_set_region, libctxkit), Int32, (Image, ImageRegion), image, region)
 txkit_image_unmap_read(read_map::MappedImageDataRead) = ccall((:txkit_image_unmap_read, libctxkit), Cvoid, (MappedImageDataRead,), read_map)
 txkit_image_unmap_write(write_map::MappedImageDataRead) = ccall((:txkit_image_unmap_write, libctxkit), Cvoid, (MappedImageDataWrite,), write_map)
 
 txkit_method_compute(ctx::Context, method::TextureMethod, tgt::Image, params::Ptr{Cvoid}, params_size::UInt) = ccall((:txkit_method_compute, libctxkit), Int32, (Context, TextureMethod, Image, Ptr{Cvoid}, UInt), ctx, method, tgt, params, params_size)
 txkit_method_destroy(method::TextureMethod) = ccall((:txkit_method_destroy, libctxkit), Cvoid, (TextureMethod,), method)
 txkit_method_new(registry::Registry, method_name::AbstractString) = ccall((:txkit_method_new, libctxkit), TextureMethod, (Registry, Cstring), registry, method_name)
+txkit_method_compute_params(ctx::Context, method::TextureMethod, tgt::Image, params::Params) = ccall((:txkit_method_compute_params, libctxkit), Int32, (Context, TextureMethod, Image, Params), ctx, method, tgt, params)
+txkit_method_params_new(method::TextureMethod) = ccall((:txkit_method_params_new, libctxkit), Params, (TextureMethod,), method)
+
+txkit_params_count(params::Params) = ccall((:txkit_params_count, libctxkit), UInt, (Params,), params)
+txkit_params_describe(params::Params, index::UInt, desc::Ref{ParamDesc}) = ccall((:txkit_params_describe, libctxkit), Int32, (Params, UInt, Ref{ParamDesc}), params, index, desc)
+txkit_params_destroy(params::Params) = ccall((:txkit_params_destroy, libctxkit), Cvoid, (Params,), params)
+txkit_params_get(params::Params, name::AbstractString, values::Vector{Float64}) = ccall((:txkit_params_get, libctxkit), Int32, (Params, Cstring, Ptr{Float64}, UInt), params, name, values, length(values))
+txkit_params_set(params::Params, name::AbstractString, values::Vector{Float64}) = ccall((:txkit_params_set, libctxkit), Int32, (Params, Cstring, Ptr{Float64}, UInt), params, name, values, length(values))
 
+txkit_registry_count(registry::Registry) = ccall((:txkit_registry_count, libctxkit), UInt, (Registry,), registry)
+txkit_registry_describe(registry::Registry, index::UInt, desc::Ref{MethodDesc}) = ccall((:txkit_registry_describe, libctxkit), Int32, (Registry, UInt, Ref{MethodDesc}), registry, index, desc)
 txkit_registry_destroy(registry::Registry) = ccall((:txkit_registry_destroy, libctxkit), Cvoid, (Registry,), registry)
+txkit_registry_load_plugin(registry::Registry, path::AbstractString) = ccall((:txkit_registry_load_plugin, libctxkit), Int32, (Registry, Cstring), registry, path)
 
 txkit_image_io_destroy(io::ImageIo) = ccall((:txkit_image_io_destroy, libctxkit), Cvoid, (ImageIo,), io)
 txkit_image_io_new() = ccall((:txkit_image_io_new, libctxkit), ImageIo, ())
@@ -90,14 +149,36 @@ struct Vector2_f32
     y::Float32
 end
 
+struct Vector4_f32
+    x::Float32
+    y::Float32
+    z::Float32
+    w::Float32
+end
+
+const LatticeOutput = Int32
+
+const LatticeOutput_Value = LatticeOutput(0)
+const LatticeOutput_Derivatives = LatticeOutput(1)
+
+const LatticeDimensions = Int32
+
+const LatticeDimensions_2D = LatticeDimensions(0)
+const LatticeDimensions_3D = LatticeDimensions(1)
+const LatticeDimensions_4D = LatticeDimensions(2)
+
 struct GradientNoiseParams
     global_seed::UInt32
     scale::Float32
     stats_mode::StatsMode
     stats_look_at::Vector2_f32
+    noise_output::LatticeOutput
+    noise_dimensions::LatticeDimensions
+    time::Float32
+    period::Vector4_f32
 end
 
-GradientNoiseParams() = GradientNoiseParams(0, 32., StatsMode_Normal, Vector2_f32(0., 0.))
+GradientNoiseParams() = GradientNoiseParams(0, 32., StatsMode_Normal, Vector2_f32(0., 0.), LatticeOutput_Value, LatticeDimensions_2D, 0., Vector4_f32(0., 0., 0., 0.))
 
 const PhasorNoiseProfile = Int32
 
@@ -126,6 +207,7 @@ struct PhasorNoiseParams
     scale::Float32
     stats_mode::StatsMode
     stats_look_at::Vector2_f32
+    period::Vector2_f32
 
     noise_lookahead::Int32
     kernel_count::Int32
@@ -142,25 +224,61 @@ struct PhasorNoiseParams
     io::ImageIo
 end
 
-PhasorNoiseParams() = PhasorNoiseParams(0, 32., StatsMode_Normal, Vector2_f32(0., 0.), 1, 8, PhasorNoiseProfile_Sin, PhasorNoiseWeights_None, PhasorNoisePointDistribution_StratPoisson, 4., 0., 1., 0, txkit_image_io_new())
+PhasorNoiseParams() = PhasorNoiseParams(0, 32., StatsMode_Normal, Vector2_f32(0., 0.), Vector2_f32(0., 0.), 1, 8, PhasorNoiseProfile_Sin, PhasorNoiseWeights_None, PhasorNoisePointDistribution_StratPoisson, 4., 0., 1., 0, txkit_image_io_new())
+
+const GaborNoiseOrientationMode = Int32
+
+const GaborNoiseOrientationMode_Isotropic = GaborNoiseOrientationMode(0)
+const GaborNoiseOrientationMode_Anisotropic = GaborNoiseOrientationMode(1)
+const GaborNoiseOrientationMode_Field = GaborNoiseOrientationMode(2)
+
+struct GaborNoiseParams
+    global_seed::UInt32
+    scale::Float32
+    stats_mode::StatsMode
+    stats_look_at::Vector2_f32
+
+    noise_lookahead::Int32
+    kernel_count::Int32
+    noise_orientation_mode::GaborNoiseOrientationMode
+    noise_weights::PhasorNoiseWeights
+    noise_point_distribution::PhasorNoisePointDistribution
+
+    noise_frequency::Float32
+    noise_angle::Float32
+
+    jitter_amount::Float32
+    jitter_max::Int32
+
+    io::ImageIo
+end
+
+GaborNoiseParams() = GaborNoiseParams(0, 32., StatsMode_Normal, Vector2_f32(0., 0.), 1, 8, GaborNoiseOrientationMode_Anisotropic, PhasorNoiseWeights_None, PhasorNoisePointDistribution_StratPoisson, 4., 0., 1., 0, txkit_image_io_new())
 
 struct SimplexNoiseParams
     global_seed::UInt32
     scale::Float32
     stats_mode::StatsMode
     stats_look_at::Vector2_f32
+    noise_output::LatticeOutput
+    noise_dimensions::LatticeDimensions
+    time::Float32
 end
 
-SimplexNoiseParams() = SimplexNoiseParams(0, 32., StatsMode_Normal, Vector2_f32(0., 0.))
+SimplexNoiseParams() = SimplexNoiseParams(0, 32., StatsMode_Normal, Vector2_f32(0., 0.), LatticeOutput_Value, LatticeDimensions_2D, 0.)
 
 struct ValueNoiseParams
     global_seed::UInt32
     scale::Float32
     stats_mode::StatsMode
     stats_look_at::Vector2_f32
+    noise_output::LatticeOutput
+    noise_dimensions::LatticeDimensions
+    time::Float32
+    period::Vector4_f32
 end
 
-ValueNoiseParams() = ValueNoiseParams(0, 32., StatsMode_Normal, Vector2_f32(0., 0.))
+ValueNoiseParams() = ValueNoiseParams(0, 32., StatsMode_Normal, Vector2_f32(0., 0.), LatticeOutput_Value, LatticeDimensions_2D, 0., Vector4_f32(0., 0., 0., 0.))
 
 struct WhiteNoiseParams
     global_seed::UInt32
@@ -168,17 +286,94 @@ end
 
 WhiteNoiseParams() = WhiteNoiseParams(0)
 
+const WorleyNoiseOutput = Int32
+
+const WorleyNoiseOutput_F1 = WorleyNoiseOutput(0)
+const WorleyNoiseOutput_F2 = WorleyNoiseOutput(1)
+const WorleyNoiseOutput_F2MinusF1 = WorleyNoiseOutput(2)
+const WorleyNoiseOutput_CellId = WorleyNoiseOutput(3)
+
+const WorleyNoiseMetric = Int32
+
+const WorleyNoiseMetric_Euclidean = WorleyNoiseMetric(0)
+const WorleyNoiseMetric_Manhattan = WorleyNoiseMetric(1)
+const WorleyNoiseMetric_Chebyshev = WorleyNoiseMetric(2)
+
+struct WorleyNoiseParams
+    global_seed::UInt32
+    scale::Float32
+    stats_mode::StatsMode
+    stats_look_at::Vector2_f32
+
+    noise_lookahead::Int32
+    point_count::Int32
+    noise_output::WorleyNoiseOutput
+    distance_metric::WorleyNoiseMetric
+    noise_point_distribution::PhasorNoisePointDistribution
+
+    jitter_amount::Float32
+    jitter_max::Int32
+end
+
+WorleyNoiseParams() = WorleyNoiseParams(0, 32., StatsMode_Normal, Vector2_f32(0., 0.), 1, 1, WorleyNoiseOutput_F1, WorleyNoiseMetric_Euclidean, PhasorNoisePointDistribution_StratPoisson, 1., 0)
+
 struct DebugParams
     alpha_value::Cfloat
 end
 
 DebugParams() = DebugParams(1.0)
 
+const FractalMode = Int32
+
+const FractalMode_Fbm = FractalMode(0)
+const FractalMode_Turbulence = FractalMode(1)
+const FractalMode_Ridged = FractalMode(2)
+const FractalMode_Hybrid = FractalMode(3)
+
+struct FractalParams
+    octaves::Int32
+    lacunarity::Float32
+    gain::Float32
+    fractal_mode::FractalMode
+    offset::Float32
+end
+
+FractalParams() = FractalParams(4, 2., 0.5, FractalMode_Fbm, 1.)
+
+struct DomainWarpParams
+    amplitude::Float32
+    warp_depth::Int32
+
+    io::ImageIo
+end
+
+DomainWarpParams() = DomainWarpParams(0.1, 1, txkit_image_io_new())
+
+struct BlueNoiseParams
+    global_seed::UInt32
+    sigma::Float32
+end
+
+BlueNoiseParams() = BlueNoiseParams(0, 1.5)
+
+const CurlNoisePotential = Int32
+
+const CurlNoisePotential_Gradient = CurlNoisePotential(0)
+const CurlNoisePotential_Simplex = CurlNoisePotential(1)
+
+struct CurlNoiseParams
+    global_seed::UInt32
+    scale::Float32
+    noise_potential::CurlNoisePotential
+end
+
+CurlNoiseParams() = CurlNoiseParams(0, 32., CurlNoisePotential_Gradient)
+
 txkit_registry_new_builtin() = ccall((:txkit_registry_new_builtin, libctxkit), Registry, ())
 
 end # module
 
-import .Api.Vector2_f32, .Api.StatsMode, .Api.StatsMode_Normal,
+import .Api.Vector2_f32, .Api.Vector4_f32, .Api.StatsMode, .Api.StatsMode_Normal,
        .Api.StatsMode_Process, .Api.StatsMode_LookAt, .Api.GradientNoiseParams,
        .Api.PhasorNoiseProfile, .Api.PhasorNoiseProfile_Complex,
        .Api.PhasorNoiseProfile_Real, .Api.PhasorNoiseProfile_Imag,
@@ -188,15 +383,38 @@ import .Api.Vector2_f32, .Api.StatsMode, .Api.StatsMode_Normal,
        .Api.PhasorNoiseWeights_Uniform, .Api.PhasorNoisePointDistribution,
        .Api.PhasorNoisePointDistribution_StratPoisson,
        .Api.PhasorNoisePointDistribution_Poisson, .Api.PhasorNoiseParams,
+       .Api.GaborNoiseOrientationMode, .Api.GaborNoiseOrientationMode_Isotropic,
+       .Api.GaborNoiseOrientationMode_Anisotropic,
+       .Api.GaborNoiseOrientationMode_Field, .Api.GaborNoiseParams,
        .Api.SimplexNoiseParams, .Api.ValueNoiseParams, .Api.WhiteNoiseParams,
-       .Api.DebugParams
-
-export Vector2_f32, StatsMode_Normal, StatsMode_Process, StatsMode_LookAt, GradientNoiseParams, PhasorNoiseParams,
+       .Api.WorleyNoiseOutput, .Api.WorleyNoiseOutput_F1,
+       .Api.WorleyNoiseOutput_F2, .Api.WorleyNoiseOutput_F2MinusF1,
+       .Api.WorleyNoiseOutput_CellId, .Api.WorleyNoiseMetric,
+       .Api.WorleyNoiseMetric_Euclidean, .Api.WorleyNoiseMetric_Manhattan,
+       .Api.WorleyNoiseMetric_Chebyshev, .Api.WorleyNoiseParams, .Api.DebugParams,
+       .Api.FractalMode, .Api.FractalMode_Fbm, .Api.FractalMode_Turbulence,
+       .Api.FractalMode_Ridged, .Api.FractalMode_Hybrid, .Api.FractalParams,
+       .Api.DomainWarpParams, .Api.BlueNoiseParams, .Api.CurlNoisePotential,
+       .Api.CurlNoisePotential_Gradient, .Api.CurlNoisePotential_Simplex,
+       .Api.CurlNoiseParams, .Api.LatticeOutput, .Api.LatticeOutput_Value,
+       .Api.LatticeOutput_Derivatives, .Api.LatticeDimensions,
+       .Api.LatticeDimensions_2D, .Api.LatticeDimensions_3D,
+       .Api.LatticeDimensions_4D
+
+export Vector2_f32, Vector4_f32, StatsMode_Normal, StatsMode_Process, StatsMode_LookAt, GradientNoiseParams, PhasorNoiseParams,
        PhasorNoiseProfile, PhasorNoiseProfile_Complex, PhasorNoiseProfile_Real, PhasorNoiseProfile_Imag,
        PhasorNoiseProfile_Sin, PhasorNoiseProfile_Saw, PhasorNoiseProfile_Impulses, PhasorNoiseWeights,
        PhasorNoiseWeights_None, PhasorNoiseWeights_Bernoulli, PhasorNoiseWeights_Uniform, PhasorNoisePointDistribution,
-       PhasorNoisePointDistribution_StratPoisson, PhasorNoisePointDistribution_Poisson, SimplexNoiseParams,
-       ValueNoiseParams, WhiteNoiseParams, DebugParams, StatsMode
+       PhasorNoisePointDistribution_StratPoisson, PhasorNoisePointDistribution_Poisson, GaborNoiseOrientationMode,
+       GaborNoiseOrientationMode_Isotropic, GaborNoiseOrientationMode_Anisotropic, GaborNoiseOrientationMode_Field,
+       GaborNoiseParams, SimplexNoiseParams,
+       ValueNoiseParams, WhiteNoiseParams, WorleyNoiseOutput, WorleyNoiseOutput_F1, WorleyNoiseOutput_F2,
+       WorleyNoiseOutput_F2MinusF1, WorleyNoiseOutput_CellId, WorleyNoiseMetric, WorleyNoiseMetric_Euclidean,
+       WorleyNoiseMetric_Manhattan, WorleyNoiseMetric_Chebyshev, WorleyNoiseParams, DebugParams, FractalMode,
+       FractalMode_Fbm, FractalMode_Turbulence, FractalMode_Ridged, FractalMode_Hybrid, FractalParams, DomainWarpParams,
+       BlueNoiseParams, CurlNoisePotential, CurlNoisePotential_Gradient, CurlNoisePotential_Simplex,
+       CurlNoiseParams, StatsMode, LatticeOutput, LatticeOutput_Value, LatticeOutput_Derivatives,
+       LatticeDimensions, LatticeDimensions_2D, LatticeDimensions_3D, LatticeDimensions_4D
 
 struct Context
     context::Api.Context
@@ -232,20 +450,20 @@ function destroy(context::Context)
     Api.txkit_context_destroy(context.context)
 end
 
-import .Api.ImageDim, .Api.ImageDataType
+import .Api.ImageDim, .Api.ImageRegion, .Api.ImageDataType
 
 struct Image{E}
     image::Api.Image
 end
 
-function new_image(type::Symbol, dim::ImageDim, etype::Union{Type{UInt8}, Type{Float32}}, dims::Integer, context::Context)
-    element_type = if etype == UInt8
-        Api.ImageDataType_UInt8
-    elseif etype == Float32
-        Api.ImageDataType_Float32
-    else
-        error("unknown element type: " * string(etype))
-    end
+# Julia element types of images, and the matching txkit element types
+const ElementType = Union{Type{UInt8}, Type{Float32}, Type{UInt16}, Type{Float16}, Type{Int32}}
+const ELEMENT_TYPES = Dict(UInt8 => Api.ImageDataType_UInt8, Float32 => Api.ImageDataType_Float32,
+                           UInt16 => Api.ImageDataType_UInt16, Float16 => Api.ImageDataType_Float16,
+                           Int32 => Api.ImageDataType_Int32)
+
+function new_image(type::Symbol, dim::ImageDim, etype::ElementType, dims::Integer, context::Context)
+    element_type = ELEMENT_TYPES[etype]
 
     ptr = if type == :cpu
         Api.txkit_image_new_cpu(dim, element_type)
@@ -270,7 +488,7 @@ function new_image(type::Symbol, dim::ImageDim, etype::Union{Type{UInt8}, Type{F
     Image{etype}(ptr)
 end
 
-function new_image(f::Function, type::Symbol, dim::ImageDim, etype::Union{Type{UInt8}, Type{Float32}}, dims::Integer, context::Context)
+function new_image(f::Function, type::Symbol, dim::ImageDim, etype::ElementType, dims::Integer, context::Context)
     img = new_image(type, dim, etype, dims, context)
 
     try
@@ -280,6 +498,30 @@ function new_image(f::Function, type::Symbol, dim::ImageDim, etype::Union{Type{U
     end
 end
 
+# Wrap an array with (channels, width, height, depth) axes as a CPU image, without copying it. The
+# image is only valid while `f` runs, and writes to it go straight to the array.
+function wrap_image(f::Function, array::StridedArray{E, 4}) where {E <: Union{UInt8, Float32, UInt16, Float16, Int32}}
+    channels, width, height, depth = size(array)
+    array_strides = UInt[reverse(strides(array))...]
+
+    GC.@preserve array array_strides begin
+        ptr = Api.txkit_image_new_cpu_from_buffer(ImageDim(width, height, depth, channels), ELEMENT_TYPES[E],
+                                                  Ptr{Cvoid}(pointer(array)), pointer(array_strides), C_NULL, C_NULL)
+
+        if ptr == C_NULL
+            error("error creating image: " * unsafe_string(Api.txkit_get_last_error()))
+        end
+
+        img = Image{E}(ptr)
+
+        try
+            f(img)
+        finally
+            destroy(img)
+        end
+    end
+end
+
 function destroy(image::Image)
     Api.txkit_image_destroy(image.image)
 end
@@ -301,6 +543,18 @@ function upload(image::Image)
     nothing
 end
 
+function region(image::Image)
+    Api.txkit_image_region(image.image)
+end
+
+function set_region(image::Image, region::ImageRegion)
+    if Api.txkit_image_set_region(image.image, region) != 0
+        error("error setting image region: " * unsafe_string(Api.txkit_get_last_error()))
+    end
+
+    nothing
+end
+
 function map_read(f::Function, image::Image{E}) where {E}
     map = Api.txkit_image_map_read(image.image)
 
@@ -313,10 +567,16 @@ function map_read(f::Function, image::Image{E}) where {E}
             Api.txkit_image_map_read_data_u8(map)
         elseif E == Float32
             Api.txkit_image_map_read_data_f32(map)
+        elseif E == UInt16
+            Api.txkit_image_map_read_data_u16(map)
+        elseif E == Float16
+            Api.txkit_image_map_read_data_f16(map)
+        elseif E == Int32
+            Api.txkit_image_map_read_data_i32(map)
         end
 
         if map_read_data == C_NULL
-            error("error obtaining pointer to data for map: " * unsafe_string(Api.txkit_get_last_error()))
+            error("error obtaining pointer to data for map: the image data is not packed in row-major order")
         end
 
         # Wrap the array returned by txkit
@@ -343,10 +603,16 @@ function map_write(f::Function, image::Image{E}) where {E}
             Api.txkit_image_map_write_data_u8(map)
         elseif E == Float32
             Api.txkit_image_map_write_data_f32(map)
+        elseif E == UInt16
+            Api.txkit_image_map_write_data_u16(map)
+        elseif E == Float16
+            Api.txkit_image_map_write_data_f16(map)
+        elseif E == Int32
+            Api.txkit_image_map_write_data_i32(map)
         end
 
         if map_write_data == C_NULL
-            error("error obtaining pointer to data for map: " * unsafe_string(Api.txkit_get_last_error()))
+            error("error obtaining pointer to data for map: the image data is not packed in row-major order")
         end
 
         # Wrap the array returned by txkit
@@ -389,6 +655,31 @@ function destroy(registry::Registry)
     Api.txkit_registry_destroy(registry.registry)
 end
 
+function load_plugin(registry::Registry, path::AbstractString)
+    if Api.txkit_registry_load_plugin(registry.registry, path) != 0
+        error("error loading plugin: " * unsafe_string(Api.txkit_get_last_error()))
+    end
+
+    nothing
+end
+
+function method_descs(registry::Registry)
+    map(0:Api.txkit_registry_count(registry.registry) - 1) do index
+        desc = Ref{Api.MethodDesc}()
+
+        if Api.txkit_registry_describe(registry.registry, UInt(index), desc) != 0
+            error("error describing method: " * unsafe_string(Api.txkit_get_last_error()))
+        end
+
+        element_types = filter(t -> desc[].element_types & (UInt32(1) << t) != 0,
+                               sort(collect(values(ELEMENT_TYPES))))
+
+        (name = unsafe_string(desc[].name), description = unsafe_string(desc[].description),
+         params_type = unsafe_string(desc[].params_type), cpu = desc[].cpu, gpu = desc[].gpu,
+         element_types = element_types, max_dimensions = Int(desc[].max_dimensions))
+    end
+end
+
 struct TextureMethod
     method::Api.TextureMethod
 end
@@ -431,6 +722,82 @@ function compute(context::Context, method::TextureMethod, target::Image, params:
     nothing
 end
 
+struct MethodParams
+    params::Api.Params
+end
+
+function new_params(method::TextureMethod)
+    ptr = Api.txkit_method_params_new(method.method)
+
+    if ptr == C_NULL
+        error("error creating parameters: " * unsafe_string(Api.txkit_get_last_error()))
+    end
+
+    MethodParams(ptr)
+end
+
+function new_params(f::Function, method::TextureMethod)
+    params = new_params(method)
+
+    try
+        f(params)
+    finally
+        destroy(params)
+    end
+end
+
+function destroy(params::MethodParams)
+    Api.txkit_params_destroy(params.params)
+end
+
+function param_descs(params::MethodParams)
+    map(0:Api.txkit_params_count(params.params) - 1) do index
+        desc = Ref{Api.ParamDesc}()
+
+        if Api.txkit_params_describe(params.params, UInt(index), desc) != 0
+            error("error describing parameter: " * unsafe_string(Api.txkit_get_last_error()))
+        end
+
+        (name = unsafe_string(desc[].name), doc = unsafe_string(desc[].doc), param_type = desc[].param_type)
+    end
+end
+
+function param_components(params::MethodParams, name::AbstractString)
+    for desc in param_descs(params)
+        if desc.name == name
+            return desc.param_type < Api.ParamType_Vector2 ? 1 : Int(desc.param_type - Api.ParamType_Vector2) + 2
+        end
+    end
+
+    error("unknown parameter: " * name)
+end
+
+function get_param(params::MethodParams, name::AbstractString)
+    values = zeros(Float64, param_components(params, name))
+
+    if Api.txkit_params_get(params.params, name, values) != 0
+        error("error getting parameter: " * unsafe_string(Api.txkit_get_last_error()))
+    end
+
+    length(values) == 1 ? values[1] : values
+end
+
+function set_param(params::MethodParams, name::AbstractString, value::Union{Real, AbstractVector{<:Real}})
+    if Api.txkit_params_set(params.params, name, Float64[value...]) != 0
+        error("error setting parameter: " * unsafe_string(Api.txkit_get_last_error()))
+    end
+
+    nothing
+end
+
+function compute(context::Context, method::TextureMethod, target::Image, params::MethodParams)
+    if Api.txkit_method_compute_params(context.context, method.method, target.image, params.params) != 0
+        error("error computing result: " * unsafe_string(Api.txkit_get_last_error()))
+    end
+
+    nothing
+end
+
 struct ImageIo
     io::Api.ImageIo
 end
@@ -473,7 +840,7 @@ end
 
 set_texture_binding(io::ImageIo, index::UInt, image::Image) = set_texture_binding(io.io, index, image.image)
 
-export Api, Context, new_context, ImageDim, Image, new_image, destroy, download, upload, map_read, map_write, TextureMethod, new_method, compute, Registry, new_registry, set_image_binding, set_texture_binding
+export Api, Context, new_context, ImageDim, ImageRegion, Image, new_image, wrap_image, destroy, download, upload, region, set_region, map_read, map_write, TextureMethod, new_method, compute, MethodParams, new_params, param_descs, get_param, set_param, Registry, new_registry, method_descs, load_plugin, set_image_binding, set_texture_binding
 
 end # module
 